@@ -1,4 +1,6 @@
 use crate::io::asset_pack::{AssetPack, DirectoryEntry};
+use crate::io::raster::{self, Colormap, Normalization, RasterFormat};
+use serde::Deserialize;
 use serde_json::Value;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -70,6 +72,64 @@ pub fn write_text_file(path: String, content: String) -> Result<(), String> {
     Ok(())
 }
 
+/// Write bytes to `path` via a temp file and rename, creating parent directories.
+pub(crate) fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    let temp_path = stage_write(path, bytes)?;
+    commit_staged(&temp_path, path)
+}
+
+/// First half of `write_atomic`: write `bytes` to the temp file next to
+/// `path` and return its path. No temp file is left behind on failure.
+pub(crate) fn stage_write(path: &Path, bytes: &[u8]) -> Result<PathBuf, String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    let temp_path = path.with_extension("tmp");
+    if let Err(e) = fs::write(&temp_path, bytes) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(format!("Failed to write temp file: {}", e));
+    }
+    Ok(temp_path)
+}
+
+/// Second half of `write_atomic`: rename a staged temp file over `path`,
+/// deleting it if the rename fails.
+pub(crate) fn commit_staged(temp_path: &Path, path: &Path) -> Result<(), String> {
+    fs::rename(temp_path, path).map_err(|e| {
+        let _ = fs::remove_file(temp_path);
+        format!("Failed to rename: {}", e)
+    })
+}
+
+#[derive(Deserialize)]
+pub struct GridImageRequest {
+    /// Destination file path
+    pub path: String,
+    /// Row-major samples, `width * height` of them
+    pub values: Vec<f32>,
+    pub width: u32,
+    pub height: u32,
+    pub format: RasterFormat,
+    pub normalization: Normalization,
+    /// Only used by `png_colormap` (defaults to grayscale)
+    #[serde(default)]
+    pub colormap: Option<Colormap>,
+}
+
+/// Export an evaluated grid as a 16-bit PNG, RAW16 or colormapped PNG.
+#[tauri::command]
+pub fn export_grid_image(request: GridImageRequest) -> Result<(), String> {
+    let bytes = raster::render(
+        &request.values,
+        request.width,
+        request.height,
+        request.format,
+        request.normalization,
+        request.colormap,
+    )?;
+    write_atomic(Path::new(&request.path), &bytes)
+}
+
 /// Copy a file from source to destination, creating parent directories.
 #[tauri::command]
 pub fn copy_file(source: String, destination: String) -> Result<(), String> {
@@ -179,7 +239,8 @@ pub fn create_blank_project(target_path: String) -> Result<(), String> {
     let manifest = serde_json::json!({
         "name": dir_name,
         "version": "1.0.0",
-        "description": ""
+        "description": "",
+        "serverVersion": crate::schema::versions::CURRENT
     });
     fs::write(
         target.join("manifest.json"),
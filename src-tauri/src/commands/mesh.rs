@@ -0,0 +1,198 @@
+use crate::commands::io::write_atomic;
+use crate::io::mesh_export::{self, MeshFormat};
+use crate::io::vox;
+use crate::mesh::greedy::{self, BlockVolume, MaterialMesh};
+use crate::mesh::heightfield::heightfield;
+use crate::mesh::surface_nets::{self, DensityVolume, TriangleMesh};
+use crate::noise::evaluator::DensityEvaluator;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Largest sample count per axis accepted by `extract_isosurface`.
+const MAX_VOLUME_RESOLUTION: u32 = 256;
+
+#[derive(Deserialize)]
+pub struct IsosurfaceRequest {
+    /// The density graph as V2 JSON
+    pub graph: Value,
+    /// World-space corners of the sampled box (inclusive)
+    pub min: [f64; 3],
+    pub max: [f64; 3],
+    /// Samples along each axis
+    pub resolution: [u32; 3],
+    /// Density at the surface; higher values are solid
+    #[serde(default)]
+    pub iso_level: f32,
+    /// JSON Pointer of the node to mesh (defaults to the root)
+    #[serde(default)]
+    pub pointer: Option<String>,
+}
+
+/// Sample a density graph in a 3D box and extract a closed isosurface mesh.
+#[tauri::command]
+pub fn extract_isosurface(request: IsosurfaceRequest) -> Result<TriangleMesh, String> {
+    if request
+        .resolution
+        .iter()
+        .any(|&r| !(2..=MAX_VOLUME_RESOLUTION).contains(&r))
+    {
+        return Err(format!(
+            "Volume resolution must be between 2 and {} per axis",
+            MAX_VOLUME_RESOLUTION
+        ));
+    }
+    // An empty or inverted box gives a zero or negative step, which breaks
+    // normals and flips the winding
+    if (0..3).any(|axis| {
+        let (min, max) = (request.min[axis], request.max[axis]);
+        !(min.is_finite() && max.is_finite() && max > min)
+    }) {
+        return Err("Volume bounds must be finite, with max above min on every axis".to_string());
+    }
+
+    let pointer = request.pointer.clone().unwrap_or_default();
+    let evaluator = DensityEvaluator::from_json_pointers(&request.graph, &[pointer], false)
+        .map_err(|e| format!("Parse error: {}", e))?;
+
+    let dims = request.resolution.map(|r| r as usize);
+    let step =
+        [0, 1, 2].map(|axis| (request.max[axis] - request.min[axis]) / (dims[axis] - 1) as f64);
+
+    // Evaluate one x-row of the volume per batch.
+    let xs: Vec<f64> = (0..dims[0])
+        .map(|x| request.min[0] + x as f64 * step[0])
+        .collect();
+    let mut ys = vec![0.0; dims[0]];
+    let mut zs = vec![0.0; dims[0]];
+    let mut row = vec![0.0; dims[0]];
+    let mut values = Vec::with_capacity(dims[0] * dims[1] * dims[2]);
+
+    for z in 0..dims[2] {
+        zs.fill(request.min[2] + z as f64 * step[2]);
+        for y in 0..dims[1] {
+            ys.fill(request.min[1] + y as f64 * step[1]);
+            evaluator.evaluate_batch(&xs, &ys, &zs, &mut row);
+            values.extend(row.iter().map(|v| *v as f32));
+        }
+    }
+
+    let volume = DensityVolume {
+        dims,
+        values,
+        origin: request.min,
+        step,
+    };
+    Ok(surface_nets::extract(
+        &volume.padded(request.iso_level),
+        request.iso_level,
+    ))
+}
+
+#[derive(Deserialize)]
+pub struct VoxelMeshRequest {
+    /// Volume size in blocks
+    pub size_x: u32,
+    pub size_y: u32,
+    pub size_z: u32,
+    /// Block ids, y fastest then x then z (the `bridge_fetch_chunk` layout)
+    pub blocks: Vec<i32>,
+    /// Block id (as a string) -> block name
+    #[serde(default)]
+    pub palette: HashMap<String, String>,
+}
+
+impl VoxelMeshRequest {
+    fn volume(&self) -> Result<BlockVolume<'_>, String> {
+        let expected = self
+            .size_x
+            .checked_mul(self.size_y)
+            .and_then(|n| n.checked_mul(self.size_z))
+            .ok_or_else(|| {
+                format!(
+                    "Volume of {}x{}x{} blocks is too large",
+                    self.size_x, self.size_y, self.size_z
+                )
+            })? as usize;
+        let size = [self.size_x, self.size_y, self.size_z].map(|s| s as usize);
+        if self.blocks.len() != expected {
+            return Err(format!(
+                "Expected {} blocks for a {}x{}x{} volume, got {}",
+                expected,
+                size[0],
+                size[1],
+                size[2],
+                self.blocks.len()
+            ));
+        }
+        Ok(BlockVolume {
+            size,
+            blocks: &self.blocks,
+        })
+    }
+}
+
+/// Greedy-mesh a block volume into per-material quads with per-vertex AO.
+#[tauri::command]
+pub fn mesh_voxels(request: VoxelMeshRequest) -> Result<Vec<MaterialMesh>, String> {
+    Ok(greedy::mesh_blocks(&request.volume()?, &request.palette))
+}
+
+/// Write a mesh (e.g. from `extract_isosurface`) as OBJ or binary glTF.
+#[tauri::command]
+pub fn export_mesh(path: String, mesh: TriangleMesh, format: MeshFormat) -> Result<(), String> {
+    let bytes = mesh_export::encode(&mesh, format)?;
+    write_atomic(Path::new(&path), &bytes)
+}
+
+#[derive(Deserialize)]
+pub struct HeightfieldExportRequest {
+    /// Destination file path
+    pub path: String,
+    pub format: MeshFormat,
+    /// Row-major heights, `resolution * resolution` of them
+    pub values: Vec<f32>,
+    pub resolution: u32,
+    /// World-space x/z corners of the grid
+    pub range_min: [f64; 2],
+    pub range_max: [f64; 2],
+    /// Multiplier from grid value to world height
+    #[serde(default = "default_height_scale")]
+    pub height_scale: f64,
+}
+
+fn default_height_scale() -> f64 {
+    1.0
+}
+
+/// Triangulate an evaluated heightfield and write it as OBJ or binary glTF.
+#[tauri::command]
+pub fn export_heightfield(request: HeightfieldExportRequest) -> Result<(), String> {
+    let resolution = request.resolution as usize;
+    if resolution < 2 || request.values.len() != resolution * resolution {
+        return Err(format!(
+            "Expected {} values for a {}x{} heightfield, got {}",
+            resolution * resolution,
+            resolution,
+            resolution,
+            request.values.len()
+        ));
+    }
+    let mesh = heightfield(
+        &request.values,
+        resolution,
+        request.range_min,
+        request.range_max,
+        request.height_scale,
+    );
+    let bytes = mesh_export::encode(&mesh, request.format)?;
+    write_atomic(Path::new(&request.path), &bytes)
+}
+
+/// Write a block volume as a MagicaVoxel `.vox` model.
+#[tauri::command]
+pub fn export_vox(path: String, volume: VoxelMeshRequest) -> Result<(), String> {
+    let bytes = vox::encode(&volume.volume()?, &volume.palette)?;
+    write_atomic(Path::new(&path), &bytes)
+}
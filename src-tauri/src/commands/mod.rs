@@ -1,6 +1,7 @@
 pub mod bridge;
 pub mod hardware;
 pub mod io;
+pub mod mesh;
 pub mod preview;
 pub mod process;
 pub mod validate;
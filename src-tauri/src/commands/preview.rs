@@ -1,6 +1,17 @@
+use crate::noise::cache::{GridKey, PreviewCache};
 use crate::noise::evaluator::DensityEvaluator;
+use crate::noise::profiler::NodeProfile;
+use crate::noise::ranges::{self, RangeAnalysis};
+use crate::noise::trace::TraceNode;
+use crate::preview::binary::{self, GridDtype};
+use crate::preview::contours::{self, ContourOptions, Contours};
+use crate::preview::jobs::{self, GridSpec, PreviewJobs};
+use crate::preview::stats::{self, GridStats, StatsOptions};
+use crate::preview::tiles::{TileConfig, TileLayer, TileMap, TileMaps};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use tauri::ipc::{Channel, InvokeResponseBody};
+use tauri::Manager;
 
 #[derive(Deserialize)]
 pub struct EvaluateRequest {
@@ -13,6 +24,21 @@ pub struct EvaluateRequest {
     pub range_max: f64,
     /// Y level for 2D evaluation
     pub y_level: f64,
+    /// Record per-node call counts and timings while evaluating
+    #[serde(default)]
+    pub profile: bool,
+    /// JSON Pointer of the node to preview as the output (defaults to the root)
+    #[serde(default)]
+    pub pointer: Option<String>,
+    /// Additional subtrees to evaluate in the same pass, sharing caches
+    #[serde(default)]
+    pub pointers: Vec<String>,
+    /// Compute histogram and summary statistics for the primary output
+    #[serde(default)]
+    pub stats: Option<StatsOptions>,
+    /// Trace iso-lines of the primary output
+    #[serde(default)]
+    pub contours: Option<ContourOptions>,
 }
 
 #[derive(Serialize)]
@@ -24,36 +50,327 @@ pub struct EvaluateResponse {
     /// Min/max values in the result (for normalization)
     pub min_value: f32,
     pub max_value: f32,
+    /// Per-node cost, present when the request enabled profiling
+    pub profile: Option<Vec<NodeProfile>>,
+    /// One grid per entry of `EvaluateRequest::pointers`, in request order
+    pub subgraphs: Vec<SubgraphGrid>,
+    /// Statistics for `values`, present when the request asked for them
+    pub stats: Option<GridStats>,
+    /// Number of unchanged subtrees whose samples were reused from earlier requests
+    pub cached_subtrees: usize,
+    /// Contour polylines for `values`, present when the request asked for them
+    pub contours: Option<Contours>,
+}
+
+#[derive(Serialize)]
+pub struct SubgraphGrid {
+    /// JSON Pointer of the evaluated subtree
+    pub pointer: String,
+    /// Flattened NxN density values (row-major)
+    pub values: Vec<f32>,
+    pub min_value: f32,
+    pub max_value: f32,
+}
+
+/// Values and running min/max for one evaluated grid.
+struct GridBuffer {
+    values: Vec<f32>,
+    min_value: f32,
+    max_value: f32,
+}
+
+impl GridBuffer {
+    fn with_capacity(len: usize) -> Self {
+        GridBuffer {
+            values: Vec::with_capacity(len),
+            min_value: f32::MAX,
+            max_value: f32::MIN,
+        }
+    }
+
+    fn extend(&mut self, row: &[f64]) {
+        for &val in row {
+            let val = val as f32;
+            self.min_value = self.min_value.min(val);
+            self.max_value = self.max_value.max(val);
+            self.values.push(val);
+        }
+    }
 }
 
 /// Evaluate a density function graph at an NxN grid of positions.
+///
+/// Unless profiling, subtree samples are kept in the session cache so that
+/// after an edit only the changed subtrees and their ancestors are evaluated.
 #[tauri::command]
-pub fn evaluate_density(request: EvaluateRequest) -> Result<EvaluateResponse, String> {
-    let evaluator =
-        DensityEvaluator::from_json(&request.graph).map_err(|e| format!("Parse error: {}", e))?;
+pub fn evaluate_density(
+    request: EvaluateRequest,
+    cache: tauri::State<'_, PreviewCache>,
+) -> Result<EvaluateResponse, String> {
+    let mut pointers = vec![request.pointer.clone().unwrap_or_default()];
+    pointers.extend(request.pointers.iter().cloned());
+
+    let mut evaluator = if request.profile {
+        DensityEvaluator::from_json_pointers(&request.graph, &pointers, true)
+    } else {
+        let grid = GridKey::new(
+            request.resolution,
+            request.range_min,
+            request.range_max,
+            request.y_level,
+        );
+        DensityEvaluator::from_json_cached(&request.graph, &pointers, &cache.0, grid)
+    }
+    .map_err(|e| format!("Parse error: {}", e))?;
 
     let n = request.resolution as usize;
-    let mut values = Vec::with_capacity(n * n);
+    let mut grids: Vec<GridBuffer> = (0..evaluator.output_count())
+        .map(|_| GridBuffer::with_capacity(n * n))
+        .collect();
     let step = (request.range_max - request.range_min) / n as f64;
 
-    let mut min_val = f32::MAX;
-    let mut max_val = f32::MIN;
+    // Evaluate one row of the grid per batch.
+    let xs: Vec<f64> = (0..n)
+        .map(|x_idx| request.range_min + (x_idx as f64 + 0.5) * step)
+        .collect();
+    let ys = vec![request.y_level; n];
+    let mut zs = vec![0.0; n];
+    let mut row = vec![0.0; n];
 
     for z_idx in 0..n {
-        let z = request.range_min + (z_idx as f64 + 0.5) * step;
-        for x_idx in 0..n {
-            let x = request.range_min + (x_idx as f64 + 0.5) * step;
-            let val = evaluator.evaluate(x, request.y_level, z) as f32;
-            min_val = min_val.min(val);
-            max_val = max_val.max(val);
-            values.push(val);
+        zs.fill(request.range_min + (z_idx as f64 + 0.5) * step);
+        evaluator.set_sample_index(z_idx * n);
+        evaluator.evaluate_batch(&xs, &ys, &zs, &mut row);
+        grids[0].extend(&row);
+        for (i, grid) in grids.iter_mut().enumerate().skip(1) {
+            evaluator.evaluate_output_batch(i, &xs, &ys, &zs, &mut row);
+            grid.extend(&row);
         }
     }
 
+    evaluator.store_grids(&cache.0);
+
+    let mut grids = grids.into_iter();
+    let main = grids.next().expect("primary output");
+    let subgraphs = request
+        .pointers
+        .into_iter()
+        .zip(grids)
+        .map(|(pointer, grid)| SubgraphGrid {
+            pointer,
+            values: grid.values,
+            min_value: grid.min_value,
+            max_value: grid.max_value,
+        })
+        .collect();
+
+    let stats = request
+        .stats
+        .as_ref()
+        .map(|options| stats::compute(&main.values, main.min_value, main.max_value, options));
+    let contours = request
+        .contours
+        .as_ref()
+        .map(|options| contours::extract(&main.values, request.resolution, options));
+
     Ok(EvaluateResponse {
-        values,
+        values: main.values,
         resolution: request.resolution,
-        min_value: min_val,
-        max_value: max_val,
+        min_value: main.min_value,
+        max_value: main.max_value,
+        profile: evaluator.profile(),
+        subgraphs,
+        stats,
+        cached_subtrees: evaluator.cache_hits(),
+        contours,
+    })
+}
+
+/// Evaluate the primary output like `evaluate_density`, but return it as a raw
+/// little-endian buffer (see `preview::binary`) instead of a JSON float array.
+///
+/// Profile, subgraph and stats data are only available through the JSON command.
+#[tauri::command]
+pub fn evaluate_density_binary(
+    request: EvaluateRequest,
+    dtype: Option<GridDtype>,
+    cache: tauri::State<'_, PreviewCache>,
+) -> Result<tauri::ipc::Response, String> {
+    let response = evaluate_density(
+        EvaluateRequest {
+            profile: false,
+            pointers: Vec::new(),
+            stats: None,
+            contours: None,
+            ..request
+        },
+        cache,
+    )?;
+    Ok(tauri::ipc::Response::new(binary::encode_grid(
+        &response.values,
+        response.resolution,
+        response.min_value,
+        response.max_value,
+        dtype.unwrap_or_default(),
+    )))
+}
+
+#[derive(Deserialize)]
+pub struct PreviewJobRequest {
+    /// Identifies the preview pane; a new job for the same view cancels the old one
+    pub view: String,
+    /// The density graph as V2 JSON
+    pub graph: Value,
+    /// World coordinate range
+    pub range_min: f64,
+    pub range_max: f64,
+    /// Y level for 2D evaluation
+    pub y_level: f64,
+    /// Refinement levels, evaluated and streamed coarse to fine
+    #[serde(default = "default_job_resolutions")]
+    pub resolutions: Vec<u32>,
+    #[serde(default)]
+    pub dtype: GridDtype,
+}
+
+fn default_job_resolutions() -> Vec<u32> {
+    vec![32, 128, 512]
+}
+
+/// Start a progressive preview job and return its id.
+///
+/// Each refinement level is sent over `channel` as a binary grid buffer
+/// (see `preview::binary`). The graph is parsed before returning, so parse
+/// errors are reported directly rather than through the channel.
+#[tauri::command]
+pub fn start_preview_job(
+    request: PreviewJobRequest,
+    channel: Channel<InvokeResponseBody>,
+    jobs: tauri::State<'_, PreviewJobs>,
+) -> Result<u64, String> {
+    let evaluator = DensityEvaluator::from_json(&request.graph)
+        .map_err(|e| format!("Parse error: {}", e))?;
+
+    let mut resolutions = request.resolutions;
+    resolutions.sort_unstable();
+    resolutions.dedup();
+
+    let jobs = jobs.inner().clone();
+    let (id, cancel) = jobs.start(&request.view);
+    let spec = GridSpec {
+        range_min: request.range_min,
+        range_max: request.range_max,
+        y_level: request.y_level,
+        dtype: request.dtype,
+    };
+
+    std::thread::spawn(move || {
+        jobs::run_levels(&evaluator, &spec, &resolutions, &cancel, |grid| {
+            channel.send(InvokeResponseBody::Raw(grid)).is_ok()
+        });
+        jobs.finish(id);
+    });
+
+    Ok(id)
+}
+
+/// Cancel a running preview job. Returns false if it had already finished.
+#[tauri::command]
+pub fn cancel_preview_job(job_id: u64, jobs: tauri::State<'_, PreviewJobs>) -> bool {
+    jobs.cancel(job_id)
+}
+
+#[derive(Deserialize)]
+pub struct TileMapRequest {
+    /// The density graph as V2 JSON
+    pub graph: Value,
+    pub layer: TileLayer,
+    #[serde(flatten)]
+    pub config: TileConfig,
+}
+
+#[derive(Serialize)]
+pub struct TileMapInfo {
+    /// Pass to `get_map_tile`; also names the map's disk cache directory
+    pub map_id: String,
+    pub tile_size: u32,
+    pub max_zoom: u32,
+}
+
+/// Parse a graph for tiled rendering. Maps with identical inputs share an id,
+/// so reopening an unchanged graph reuses its cached tiles.
+#[tauri::command]
+pub fn open_tile_map(
+    request: TileMapRequest,
+    app: tauri::AppHandle,
+    maps: tauri::State<'_, TileMaps>,
+) -> Result<TileMapInfo, String> {
+    let cache_root = app.path().app_cache_dir().ok().map(|dir| dir.join("tiles"));
+    let map = maps.open(TileMap::new(
+        &request.graph,
+        request.layer,
+        request.config,
+        cache_root.as_deref(),
+    )?);
+    Ok(TileMapInfo {
+        map_id: map.id.clone(),
+        tile_size: map.tile_size(),
+        max_zoom: map.max_zoom(),
     })
 }
+
+/// Render (or load from cache) tile (x, y) at zoom `z` of an open map, as a
+/// binary grid buffer (see `preview::binary`).
+#[tauri::command]
+pub async fn get_map_tile(
+    map_id: String,
+    z: u32,
+    x: u32,
+    y: u32,
+    maps: tauri::State<'_, TileMaps>,
+) -> Result<tauri::ipc::Response, String> {
+    let map = maps
+        .get(&map_id)
+        .ok_or_else(|| format!("Tile map {} is not open", map_id))?;
+    Ok(tauri::ipc::Response::new(map.tile(z, x, y)?))
+}
+
+#[derive(Deserialize)]
+pub struct ProbeRequest {
+    /// The density graph as V2 JSON
+    pub graph: Value,
+    /// World position to probe
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+#[derive(Serialize)]
+pub struct ProbeResponse {
+    /// Final density value at the probed position
+    pub value: f64,
+    /// Evaluation tree mirroring the graph JSON
+    pub trace: TraceNode,
+}
+
+/// Evaluate a density graph at a single position and return every node's output.
+#[tauri::command]
+pub fn probe_density(request: ProbeRequest) -> Result<ProbeResponse, String> {
+    let evaluator = DensityEvaluator::from_json_traced(&request.graph)
+        .map_err(|e| format!("Parse error: {}", e))?;
+
+    let trace = evaluator
+        .probe(request.x, request.y, request.z)
+        .ok_or("Probe produced no trace")?;
+
+    Ok(ProbeResponse {
+        value: trace.value,
+        trace,
+    })
+}
+
+/// Statically analyze the output range of every node in a density graph.
+#[tauri::command]
+pub fn analyze_density_ranges(graph: Value) -> RangeAnalysis {
+    ranges::analyze(&graph)
+}
@@ -1,7 +1,17 @@
-use crate::schema::validation::{ValidationError, ValidationResult};
+use crate::commands::io::{commit_staged, stage_write};
+use crate::schema::describe::{self, SchemaDescription};
+use crate::schema::lint::{self, LintConfig};
+use crate::schema::migration::{self, MigrationReport};
+use crate::schema::patch::{self, PatchOp};
+use crate::schema::spans::{Position, Span, SpanIndex};
+use crate::schema::validation::{
+    validate_server_version, DiagnosticCode, Fix, Severity, ValidationError, ValidationResult,
+};
+use crate::schema::versions;
 use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
 
 /// Validate an entire asset pack directory against V2 schema.
 #[tauri::command]
@@ -12,6 +22,10 @@ pub fn validate_asset_pack(path: String) -> Result<ValidationResult, String> {
     }
 
     let mut errors: Vec<ValidationError> = Vec::new();
+    let mut parsed: Vec<(String, Value)> = Vec::new();
+    let mut sources: HashMap<String, String> = HashMap::new();
+    let version = versions::project_version(&pack_path);
+    let target = version.as_deref().unwrap_or(versions::CURRENT);
 
     // Recursively find all JSON files
     let json_files = find_json_files(&pack_path);
@@ -26,29 +40,70 @@ pub fn validate_asset_pack(path: String) -> Result<ValidationResult, String> {
         match fs::read_to_string(file_path) {
             Ok(content) => match serde_json::from_str::<Value>(&content) {
                 Ok(value) => {
-                    let file_errors = crate::schema::validation::validate_asset(&relative, &value);
+                    let file_errors =
+                        crate::schema::validation::validate_asset(&relative, &value, target);
                     errors.extend(file_errors);
+                    sources.insert(relative.clone(), content);
+                    parsed.push((relative, value));
                 }
                 Err(e) => {
+                    let position = Position {
+                        line: e.line(),
+                        column: e.column(),
+                    };
                     errors.push(ValidationError {
+                        code: DiagnosticCode::InvalidJson,
+                        path: format!("{}#", relative),
                         file: relative,
                         field: String::new(),
                         message: format!("Invalid JSON: {}", e),
-                        severity: crate::schema::validation::Severity::Error,
+                        severity: Severity::Error,
+                        span: Some(Span {
+                            start: position,
+                            end: position,
+                        }),
+                        fixes: Vec::new(),
                     });
                 }
             },
             Err(e) => {
                 errors.push(ValidationError {
+                    code: DiagnosticCode::ReadFailed,
+                    path: format!("{}#", relative),
                     file: relative,
                     field: String::new(),
                     message: format!("Cannot read file: {}", e),
-                    severity: crate::schema::validation::Severity::Error,
+                    severity: Severity::Error,
+                    span: None,
+                    fixes: Vec::new(),
                 });
             }
         }
     }
 
+    // Pack-level rules need every file parsed first
+    errors.extend(crate::schema::references::validate_references(&parsed));
+    if let Some(version) = &version {
+        errors.extend(validate_server_version(version));
+    }
+
+    // Project lint config and inline `$Suppress` keys filter and re-rank
+    let config = LintConfig::load(&pack_path).unwrap_or_else(|message| {
+        errors.push(ValidationError {
+            code: DiagnosticCode::InvalidConfig,
+            file: lint::CONFIG_FILE.to_string(),
+            field: String::new(),
+            path: String::new(),
+            message,
+            severity: Severity::Error,
+            span: None,
+            fixes: Vec::new(),
+        });
+        None
+    });
+    let mut errors = lint::apply_lint(errors, &config.unwrap_or_default(), &parsed);
+    attach_spans(&mut errors, &sources);
+
     Ok(ValidationResult {
         valid: errors.is_empty(),
         errors,
@@ -56,6 +111,160 @@ pub fn validate_asset_pack(path: String) -> Result<ValidationResult, String> {
     })
 }
 
+/// Apply quick fixes to the files of the pack at `path`.
+///
+/// Returns the relative paths of the files changed; see `write_patched`.
+#[tauri::command]
+pub fn apply_fixes(path: String, fixes: Vec<Fix>) -> Result<Vec<String>, String> {
+    let pack_path = PathBuf::from(&path);
+    if !pack_path.is_dir() {
+        return Err(format!("Not a directory: {}", path));
+    }
+    write_patched(&pack_path, &fixes)
+}
+
+/// Upgrade the pack at `path` to server version `to`, by default the one
+/// the schema describes.
+///
+/// With `dry_run` the planned changes are only reported. Otherwise they are
+/// written atomically along with the new `serverVersion` in `manifest.json`.
+/// The manifest covers its whole project, so every JSON file under the
+/// project directory is migrated even when `path` is a subdirectory.
+/// Change paths are relative to the project directory.
+#[tauri::command]
+pub fn migrate_pack(
+    path: String,
+    to: Option<String>,
+    dry_run: bool,
+) -> Result<MigrationReport, String> {
+    let pack_path = PathBuf::from(&path);
+    if !pack_path.is_dir() {
+        return Err(format!("Not a directory: {}", path));
+    }
+    let root = versions::project_root(&pack_path)
+        .ok_or_else(|| format!("No manifest.json found for {}", path))?;
+    let from = versions::project_version(&pack_path)
+        .ok_or_else(|| "manifest.json has no serverVersion".to_string())?;
+    let to = to.unwrap_or_else(|| versions::CURRENT.to_string());
+
+    let mut files = Vec::new();
+    for file_path in find_json_files(&root.to_path_buf()) {
+        let relative = file_path
+            .strip_prefix(root)
+            .unwrap_or(&file_path)
+            .to_string_lossy()
+            .to_string();
+        if relative == "manifest.json" {
+            continue;
+        }
+        let content = fs::read_to_string(&file_path)
+            .map_err(|e| format!("Cannot read {}: {}", relative, e))?;
+        let value = serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid JSON in {}: {}", relative, e))?;
+        files.push((relative, value));
+    }
+
+    let mut changes = migration::plan(versions::VERSIONS, &from, &to, &files)?;
+    if from != to {
+        changes.push(Fix {
+            title: format!("Set serverVersion to {}", to),
+            file: "manifest.json".to_string(),
+            edits: vec![PatchOp::Add {
+                path: "/serverVersion".to_string(),
+                value: Value::String(to.clone()),
+            }],
+        });
+    }
+    if !dry_run {
+        write_patched(root, &changes)?;
+    }
+    Ok(MigrationReport {
+        from,
+        to,
+        changes,
+        applied: !dry_run,
+    })
+}
+
+/// Describe the V2 schema: every category and type with its fields, value
+/// kinds, defaults, constraints, docs and child slots.
+#[tauri::command]
+pub fn get_schema() -> SchemaDescription {
+    describe::describe_schema()
+}
+
+/// Apply `fixes` to JSON files under `base`, given by relative paths.
+///
+/// Every fix is applied in memory first; if any edit fails nothing is
+/// written. Files are then written to temp files and renamed into place.
+/// Returns the relative paths of the files changed.
+fn write_patched(base: &Path, fixes: &[Fix]) -> Result<Vec<String>, String> {
+    let mut documents: BTreeMap<String, Value> = BTreeMap::new();
+    for fix in fixes {
+        let relative = Path::new(&fix.file);
+        if !relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+        {
+            return Err(format!("Fix targets a path outside the pack: {}", fix.file));
+        }
+        if !documents.contains_key(&fix.file) {
+            let content = fs::read_to_string(base.join(relative))
+                .map_err(|e| format!("Cannot read {}: {}", fix.file, e))?;
+            let value = serde_json::from_str(&content)
+                .map_err(|e| format!("Invalid JSON in {}: {}", fix.file, e))?;
+            documents.insert(fix.file.clone(), value);
+        }
+        let document = documents.get_mut(&fix.file).expect("document loaded above");
+        patch::apply(document, &fix.edits)
+            .map_err(|e| format!("Cannot apply '{}' to {}: {}", fix.title, fix.file, e))?;
+    }
+
+    // Write every temp file before renaming any, so a failed write leaves
+    // the pack untouched. Temps not renamed into place are deleted.
+    let mut staged = Vec::new();
+    for (file, value) in &documents {
+        let target = base.join(file);
+        let temp = serde_json::to_string_pretty(value)
+            .map_err(|e| format!("Failed to serialize {}: {}", file, e))
+            .and_then(|json| stage_write(&target, json.as_bytes()));
+        match temp {
+            Ok(temp) => staged.push((temp, target)),
+            Err(e) => {
+                discard(&staged);
+                return Err(format!("Cannot write {}: {}", file, e));
+            }
+        }
+    }
+    for (i, (temp, target)) in staged.iter().enumerate() {
+        if let Err(e) = commit_staged(temp, target) {
+            discard(&staged[i + 1..]);
+            return Err(format!("Cannot replace {}: {}", target.display(), e));
+        }
+    }
+    Ok(documents.into_keys().collect())
+}
+
+/// Delete staged temp files that won't be renamed into place.
+fn discard(staged: &[(PathBuf, PathBuf)]) {
+    for (temp, _) in staged {
+        let _ = fs::remove_file(temp);
+    }
+}
+
+/// Fill in each error's source span from its JSON path.
+fn attach_spans(errors: &mut [ValidationError], sources: &HashMap<String, String>) {
+    let indexes: HashMap<&str, SpanIndex> = sources
+        .iter()
+        .map(|(file, source)| (file.as_str(), SpanIndex::new(source)))
+        .collect();
+    for error in errors.iter_mut().filter(|e| e.span.is_none()) {
+        if let Some((file, pointer)) = error.path.split_once('#') {
+            error.span = indexes.get(file).and_then(|index| index.locate(pointer));
+        }
+    }
+}
+
 fn find_json_files(dir: &PathBuf) -> Vec<PathBuf> {
     let mut files = Vec::new();
     if let Ok(entries) = fs::read_dir(dir) {
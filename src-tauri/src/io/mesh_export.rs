@@ -0,0 +1,144 @@
+use crate::mesh::surface_nets::TriangleMesh;
+use serde::Deserialize;
+use serde_json::json;
+use std::fmt::Write;
+
+/// Mesh file formats accepted by the export commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MeshFormat {
+    /// Wavefront OBJ text with vertex normals.
+    Obj,
+    /// Binary glTF 2.0 with a single mesh.
+    Glb,
+}
+
+/// Encode a mesh in `format`.
+pub fn encode(mesh: &TriangleMesh, format: MeshFormat) -> Result<Vec<u8>, String> {
+    match format {
+        MeshFormat::Obj => Ok(to_obj(mesh).into_bytes()),
+        MeshFormat::Glb => to_glb(mesh),
+    }
+}
+
+/// Wavefront OBJ with one `v`/`vn` pair per vertex and `f v//vn` faces.
+pub fn to_obj(mesh: &TriangleMesh) -> String {
+    let mut out = String::from("# TerraNova export\n");
+    for p in mesh.positions.chunks_exact(3) {
+        let _ = writeln!(out, "v {} {} {}", p[0], p[1], p[2]);
+    }
+    for n in mesh.normals.chunks_exact(3) {
+        let _ = writeln!(out, "vn {} {} {}", n[0], n[1], n[2]);
+    }
+    for tri in mesh.indices.chunks_exact(3) {
+        let [a, b, c] = [tri[0] + 1, tri[1] + 1, tri[2] + 1];
+        let _ = writeln!(out, "f {a}//{a} {b}//{b} {c}//{c}");
+    }
+    out
+}
+
+const GLB_MAGIC: &[u8; 4] = b"glTF";
+const GLB_VERSION: u32 = 2;
+const CHUNK_JSON: u32 = 0x4E4F_534A;
+const CHUNK_BIN: u32 = 0x004E_4942;
+
+// glTF enums for accessors and buffer views.
+const FLOAT: u32 = 5126;
+const UNSIGNED_INT: u32 = 5125;
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+/// Binary glTF: positions, normals and u32 indices in one buffer.
+pub fn to_glb(mesh: &TriangleMesh) -> Result<Vec<u8>, String> {
+    let vertex_count = mesh.positions.len() / 3;
+    if vertex_count == 0 || mesh.normals.len() != mesh.positions.len() {
+        return Err("Mesh has no vertices or mismatched normals".to_string());
+    }
+
+    // POSITION accessors must carry bounds.
+    let mut lo = [f32::MAX; 3];
+    let mut hi = [f32::MIN; 3];
+    for p in mesh.positions.chunks_exact(3) {
+        for axis in 0..3 {
+            lo[axis] = lo[axis].min(p[axis]);
+            hi[axis] = hi[axis].max(p[axis]);
+        }
+    }
+
+    let mut bin = Vec::new();
+    bin.extend(mesh.positions.iter().flat_map(|v| v.to_le_bytes()));
+    let normals_offset = bin.len();
+    bin.extend(mesh.normals.iter().flat_map(|v| v.to_le_bytes()));
+    let indices_offset = bin.len();
+    bin.extend(mesh.indices.iter().flat_map(|v| v.to_le_bytes()));
+    let buffer_length = bin.len();
+
+    let document = json!({
+        "asset": { "version": "2.0", "generator": "TerraNova" },
+        "scene": 0,
+        "scenes": [{ "nodes": [0] }],
+        "nodes": [{ "mesh": 0 }],
+        "meshes": [{
+            "primitives": [{
+                "attributes": { "POSITION": 0, "NORMAL": 1 },
+                "indices": 2,
+            }],
+        }],
+        "buffers": [{ "byteLength": buffer_length }],
+        "bufferViews": [
+            { "buffer": 0, "byteOffset": 0, "byteLength": normals_offset, "target": ARRAY_BUFFER },
+            {
+                "buffer": 0,
+                "byteOffset": normals_offset,
+                "byteLength": indices_offset - normals_offset,
+                "target": ARRAY_BUFFER,
+            },
+            {
+                "buffer": 0,
+                "byteOffset": indices_offset,
+                "byteLength": buffer_length - indices_offset,
+                "target": ELEMENT_ARRAY_BUFFER,
+            },
+        ],
+        "accessors": [
+            {
+                "bufferView": 0,
+                "componentType": FLOAT,
+                "count": vertex_count,
+                "type": "VEC3",
+                "min": lo,
+                "max": hi,
+            },
+            { "bufferView": 1, "componentType": FLOAT, "count": vertex_count, "type": "VEC3" },
+            {
+                "bufferView": 2,
+                "componentType": UNSIGNED_INT,
+                "count": mesh.indices.len(),
+                "type": "SCALAR",
+            },
+        ],
+    });
+    let mut json =
+        serde_json::to_vec(&document).map_err(|e| format!("Failed to serialize glTF: {}", e))?;
+
+    // Chunks are 4-byte aligned: JSON pads with spaces, BIN with zeros.
+    while json.len() % 4 != 0 {
+        json.push(b' ');
+    }
+    while bin.len() % 4 != 0 {
+        bin.push(0);
+    }
+
+    let total = 12 + 8 + json.len() + 8 + bin.len();
+    let mut out = Vec::with_capacity(total);
+    out.extend_from_slice(GLB_MAGIC);
+    out.extend_from_slice(&GLB_VERSION.to_le_bytes());
+    out.extend_from_slice(&(total as u32).to_le_bytes());
+    out.extend_from_slice(&(json.len() as u32).to_le_bytes());
+    out.extend_from_slice(&CHUNK_JSON.to_le_bytes());
+    out.extend_from_slice(&json);
+    out.extend_from_slice(&(bin.len() as u32).to_le_bytes());
+    out.extend_from_slice(&CHUNK_BIN.to_le_bytes());
+    out.extend_from_slice(&bin);
+    Ok(out)
+}
@@ -1,2 +1,7 @@
 pub mod asset_pack;
+pub mod mesh_export;
+pub mod raster;
 pub mod template;
+pub mod vox;
+#[cfg(test)]
+mod tests;
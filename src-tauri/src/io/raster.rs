@@ -0,0 +1,216 @@
+use serde::Deserialize;
+
+/// How grid values map onto the output's 0..1 intensity range.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(tag = "mode", rename_all = "lowercase")]
+pub enum Normalization {
+    /// Stretch the grid's own finite min..max.
+    Auto,
+    /// Map `min..max` to black..white, clamping values outside it.
+    Fixed { min: f64, max: f64 },
+    /// White where the value is at or above `level`, black elsewhere.
+    Threshold { level: f64 },
+}
+
+impl Normalization {
+    /// Map every value to 0..1. Non-finite values become 0.
+    pub fn apply(&self, values: &[f32]) -> Vec<f64> {
+        let (min, max) = match *self {
+            Normalization::Threshold { level } => {
+                return values
+                    .iter()
+                    .map(|&v| if v as f64 >= level { 1.0 } else { 0.0 })
+                    .collect();
+            }
+            Normalization::Fixed { min, max } => (min, max),
+            Normalization::Auto => values
+                .iter()
+                .filter(|v| v.is_finite())
+                .fold((f64::MAX, f64::MIN), |(lo, hi), &v| {
+                    (lo.min(v as f64), hi.max(v as f64))
+                }),
+        };
+        let range = max - min;
+        values
+            .iter()
+            .map(|&v| {
+                if !v.is_finite() || range <= 0.0 {
+                    0.0
+                } else {
+                    ((v as f64 - min) / range).clamp(0.0, 1.0)
+                }
+            })
+            .collect()
+    }
+}
+
+/// The preview colormaps, matching `src/utils/colormaps.ts`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Colormap {
+    BlueRed,
+    Grayscale,
+    Terrain,
+    Viridis,
+    RedBlack,
+}
+
+impl Colormap {
+    /// RGB color for `t` in 0..1, ported band for band from the UI ramps.
+    pub fn ramp(self, t: f64) -> [u8; 3] {
+        let c = t.clamp(0.0, 1.0);
+        // JS `Math.round` and `f64::round` agree for the non-negative channels here.
+        let rgb = |r: f64, g: f64, b: f64| [r.round() as u8, g.round() as u8, b.round() as u8];
+
+        match self {
+            Colormap::BlueRed => {
+                if c < 0.25 {
+                    let f = c / 0.25;
+                    rgb(0.0, f * 255.0, 255.0)
+                } else if c < 0.5 {
+                    let f = (c - 0.25) / 0.25;
+                    rgb(0.0, 255.0, (1.0 - f) * 255.0)
+                } else if c < 0.75 {
+                    let f = (c - 0.5) / 0.25;
+                    rgb(f * 255.0, 255.0, 0.0)
+                } else {
+                    let f = (c - 0.75) / 0.25;
+                    rgb(255.0, (1.0 - f) * 255.0, 0.0)
+                }
+            }
+            Colormap::Grayscale => {
+                let v = (c * 255.0).round() as u8;
+                [v, v, v]
+            }
+            Colormap::Terrain => {
+                if c < 0.2 {
+                    let f = c / 0.2;
+                    rgb(f * 30.0, 30.0 + f * 70.0, 100.0 + f * 155.0)
+                } else if c < 0.4 {
+                    let f = (c - 0.2) / 0.2;
+                    rgb(30.0 + f * 20.0, 100.0 + f * 100.0, 255.0 - f * 205.0)
+                } else if c < 0.6 {
+                    let f = (c - 0.4) / 0.2;
+                    rgb(50.0 + f * 90.0, 200.0 - f * 50.0, 50.0 - f * 20.0)
+                } else if c < 0.8 {
+                    let f = (c - 0.6) / 0.2;
+                    rgb(140.0 + f * 60.0, 150.0 - f * 50.0, 30.0 + f * 30.0)
+                } else {
+                    let f = (c - 0.8) / 0.2;
+                    rgb(200.0 + f * 55.0, 100.0 + f * 155.0, 60.0 + f * 195.0)
+                }
+            }
+            Colormap::Viridis => {
+                if c < 0.25 {
+                    let f = c / 0.25;
+                    rgb(68.0 - f * 2.0, 1.0 + f * 53.0, 84.0 + f * 38.0)
+                } else if c < 0.5 {
+                    let f = (c - 0.25) / 0.25;
+                    rgb(66.0 - f * 33.0, 54.0 + f * 73.0, 122.0 + f * 7.0)
+                } else if c < 0.75 {
+                    let f = (c - 0.5) / 0.25;
+                    rgb(33.0 + f * 61.0, 127.0 + f * 63.0, 129.0 - f * 40.0)
+                } else {
+                    let f = (c - 0.75) / 0.25;
+                    rgb(94.0 + f * 159.0, 190.0 + f * 38.0, 89.0 - f * 69.0)
+                }
+            }
+            Colormap::RedBlack => rgb(c * 255.0, c * c * 60.0, c * c * 20.0),
+        }
+    }
+}
+
+/// Output encodings for an exported grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RasterFormat {
+    /// 16-bit grayscale PNG.
+    Png16,
+    /// Headerless little-endian 16-bit samples, row by row.
+    Raw16,
+    /// 8-bit RGB PNG through a colormap.
+    PngColormap,
+}
+
+fn to_u16(t: f64) -> u16 {
+    (t * u16::MAX as f64).round() as u16
+}
+
+/// Wrap already-packed row-major pixel bytes in a PNG.
+fn encode_png(
+    width: u32,
+    height: u32,
+    color: png::ColorType,
+    depth: png::BitDepth,
+    data: &[u8],
+) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut out, width, height);
+        encoder.set_color(color);
+        encoder.set_depth(depth);
+        let mut writer = encoder
+            .write_header()
+            .map_err(|e| format!("Failed to write PNG header: {}", e))?;
+        writer
+            .write_image_data(data)
+            .map_err(|e| format!("Failed to write PNG data: {}", e))?;
+    }
+    Ok(out)
+}
+
+/// Render a row-major `width` x `height` grid to bytes in `format`.
+///
+/// `colormap` is only used by `PngColormap` and defaults to grayscale.
+pub fn render(
+    values: &[f32],
+    width: u32,
+    height: u32,
+    format: RasterFormat,
+    normalization: Normalization,
+    colormap: Option<Colormap>,
+) -> Result<Vec<u8>, String> {
+    let expected = width as usize * height as usize;
+    if values.len() != expected || expected == 0 {
+        return Err(format!(
+            "Expected {} values for a {}x{} grid, got {}",
+            expected,
+            width,
+            height,
+            values.len()
+        ));
+    }
+    let samples = normalization.apply(values);
+
+    match format {
+        RasterFormat::Png16 => {
+            // PNG stores 16-bit samples big-endian.
+            let data: Vec<u8> = samples
+                .iter()
+                .flat_map(|&t| to_u16(t).to_be_bytes())
+                .collect();
+            encode_png(
+                width,
+                height,
+                png::ColorType::Grayscale,
+                png::BitDepth::Sixteen,
+                &data,
+            )
+        }
+        RasterFormat::Raw16 => Ok(samples
+            .iter()
+            .flat_map(|&t| to_u16(t).to_le_bytes())
+            .collect()),
+        RasterFormat::PngColormap => {
+            let colormap = colormap.unwrap_or(Colormap::Grayscale);
+            let data: Vec<u8> = samples.iter().flat_map(|&t| colormap.ramp(t)).collect();
+            encode_png(
+                width,
+                height,
+                png::ColorType::Rgb,
+                png::BitDepth::Eight,
+                &data,
+            )
+        }
+    }
+}
@@ -0,0 +1,282 @@
+#[cfg(test)]
+mod tests {
+    use crate::io::raster::{render, Colormap, Normalization, RasterFormat};
+
+    /// Decode a PNG, returning (color type, bit depth, width, height, pixel bytes).
+    fn decode(bytes: &[u8]) -> (png::ColorType, png::BitDepth, u32, u32, Vec<u8>) {
+        let mut reader = png::Decoder::new(bytes).read_info().unwrap();
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buf).unwrap();
+        buf.truncate(info.buffer_size());
+        (
+            info.color_type,
+            info.bit_depth,
+            info.width,
+            info.height,
+            buf,
+        )
+    }
+
+    // ── Raster export ─────────────────────────────────────────────────
+
+    #[test]
+    fn png16_round_trips_auto_normalized_samples() {
+        let values = [-1.0, 0.0, 1.0, f32::NAN, 0.5, -1.0];
+        let bytes = render(
+            &values,
+            3,
+            2,
+            RasterFormat::Png16,
+            Normalization::Auto,
+            None,
+        )
+        .unwrap();
+
+        let (color, depth, width, height, data) = decode(&bytes);
+        assert_eq!(color, png::ColorType::Grayscale);
+        assert_eq!(depth, png::BitDepth::Sixteen);
+        assert_eq!((width, height), (3, 2));
+        let samples: Vec<u16> = data
+            .chunks_exact(2)
+            .map(|b| u16::from_be_bytes([b[0], b[1]]))
+            .collect();
+        assert_eq!(samples, vec![0, 32768, 65535, 0, 49151, 0]);
+    }
+
+    #[test]
+    fn raw16_is_headerless_little_endian() {
+        let values = [0.0, 10.0, 20.0, 30.0];
+        let fixed = Normalization::Fixed {
+            min: 0.0,
+            max: 20.0,
+        };
+        let bytes = render(&values, 2, 2, RasterFormat::Raw16, fixed, None).unwrap();
+        assert_eq!(bytes.len(), 8);
+        let samples: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]))
+            .collect();
+        assert_eq!(
+            samples,
+            vec![0, 32768, 65535, 65535],
+            "values above max clamp"
+        );
+
+        let mask = Normalization::Threshold { level: 15.0 };
+        let bytes = render(&values, 4, 1, RasterFormat::Raw16, mask, None).unwrap();
+        assert_eq!(bytes, vec![0, 0, 0, 0, 255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn colormapped_png_uses_ui_ramps() {
+        let values = [0.0, 0.5, 1.0];
+        let bytes = render(
+            &values,
+            3,
+            1,
+            RasterFormat::PngColormap,
+            Normalization::Auto,
+            Some(Colormap::BlueRed),
+        )
+        .unwrap();
+        let (color, depth, _, _, data) = decode(&bytes);
+        assert_eq!(color, png::ColorType::Rgb);
+        assert_eq!(depth, png::BitDepth::Eight);
+        assert_eq!(data, vec![0, 0, 255, 0, 255, 0, 255, 0, 0]);
+
+        // Spot checks against src/utils/colormaps.ts.
+        assert_eq!(Colormap::Terrain.ramp(0.5), [95, 175, 40]);
+        assert_eq!(Colormap::Viridis.ramp(0.0), [68, 1, 84]);
+        assert_eq!(Colormap::RedBlack.ramp(1.0), [255, 60, 20]);
+        assert_eq!(Colormap::Grayscale.ramp(2.0), [255, 255, 255]);
+    }
+
+    #[test]
+    fn render_rejects_mismatched_dimensions() {
+        let err = render(
+            &[0.0; 5],
+            2,
+            2,
+            RasterFormat::Raw16,
+            Normalization::Auto,
+            None,
+        );
+        assert!(err.unwrap_err().contains("Expected 4 values"));
+    }
+
+    // ── Mesh export ───────────────────────────────────────────────────
+
+    /// A 3x3 ramp rising along x.
+    fn ramp_mesh() -> crate::mesh::surface_nets::TriangleMesh {
+        let values = [0.0, 1.0, 2.0, 0.0, 1.0, 2.0, 0.0, 1.0, 2.0];
+        crate::mesh::heightfield::heightfield(&values, 3, [0.0, 0.0], [4.0, 4.0], 2.0)
+    }
+
+    #[test]
+    fn heightfield_faces_up_and_tilts_normals_downhill() {
+        let mesh = ramp_mesh();
+        assert_eq!(mesh.positions.len(), 9 * 3);
+        assert_eq!(mesh.indices.len(), 4 * 6);
+        // The last sample: x = 4, height 2 * 2, z = 4.
+        assert_eq!(&mesh.positions[24..27], &[4.0, 4.0, 4.0]);
+        for n in mesh.normals.chunks_exact(3) {
+            assert!(n[0] < 0.0 && n[1] > 0.0 && n[2] == 0.0);
+        }
+    }
+
+    #[test]
+    fn obj_export_parses_back() {
+        let mesh = ramp_mesh();
+        let obj = crate::io::mesh_export::to_obj(&mesh);
+
+        let mut vertices = Vec::new();
+        let mut normals = 0;
+        let mut faces = Vec::new();
+        for line in obj.lines() {
+            let mut parts = line.split_whitespace();
+            match parts.next() {
+                Some("v") => {
+                    vertices.push(parts.map(|p| p.parse::<f32>().unwrap()).collect::<Vec<_>>())
+                }
+                Some("vn") => normals += 1,
+                Some("f") => faces.extend(parts.map(|p| {
+                    let (v, n) = p.split_once("//").unwrap();
+                    assert_eq!(v, n);
+                    v.parse::<u32>().unwrap() - 1
+                })),
+                _ => {}
+            }
+        }
+        assert_eq!(vertices.concat(), mesh.positions);
+        assert_eq!(normals, 9);
+        assert_eq!(faces, mesh.indices);
+    }
+
+    #[test]
+    fn glb_export_has_valid_chunks_and_accessors() {
+        let mesh = ramp_mesh();
+        let glb = crate::io::mesh_export::to_glb(&mesh).unwrap();
+        let u32_at =
+            |offset: usize| u32::from_le_bytes(glb[offset..offset + 4].try_into().unwrap());
+
+        assert_eq!(&glb[0..4], b"glTF");
+        assert_eq!(u32_at(4), 2);
+        assert_eq!(u32_at(8) as usize, glb.len());
+
+        let json_len = u32_at(12) as usize;
+        assert_eq!(u32_at(16), 0x4E4F534A);
+        let doc: serde_json::Value = serde_json::from_slice(&glb[20..20 + json_len]).unwrap();
+        let bin_start = 20 + json_len;
+        assert_eq!(u32_at(bin_start + 4), 0x004E4942);
+        let bin = &glb[bin_start + 8..bin_start + 8 + u32_at(bin_start) as usize];
+
+        assert_eq!(doc["accessors"][0]["count"], 9);
+        assert_eq!(
+            doc["accessors"][0]["max"],
+            serde_json::json!([4.0, 4.0, 4.0])
+        );
+        assert_eq!(doc["accessors"][2]["count"], 24);
+
+        // Read the index buffer view back out of BIN.
+        let view = &doc["bufferViews"][2];
+        let offset = view["byteOffset"].as_u64().unwrap() as usize;
+        let length = view["byteLength"].as_u64().unwrap() as usize;
+        let indices: Vec<u32> = bin[offset..offset + length]
+            .chunks_exact(4)
+            .map(|b| u32::from_le_bytes(b.try_into().unwrap()))
+            .collect();
+        assert_eq!(indices, mesh.indices);
+    }
+
+    // ── .vox export ───────────────────────────────────────────────────
+
+    #[test]
+    fn vox_export_swaps_to_z_up_and_builds_palette() {
+        use crate::io::vox::{block_color, encode};
+        use crate::mesh::greedy::BlockVolume;
+        use std::collections::HashMap;
+
+        // 2x3x1 volume: grass at (1, 2, 0), stone at (0, 0, 0).
+        let size = [2, 3, 1];
+        let mut blocks = vec![0; 6];
+        blocks[5] = 5; // (z * size_x + x) * size_y + y
+        blocks[0] = 9;
+        let palette = HashMap::from([
+            ("5".to_string(), "Soil_Grass".to_string()),
+            ("9".to_string(), "Rock_Stone".to_string()),
+        ]);
+        let bytes = encode(
+            &BlockVolume {
+                size,
+                blocks: &blocks,
+            },
+            &palette,
+        )
+        .unwrap();
+
+        let i32_at =
+            |offset: usize| i32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap());
+        assert_eq!(&bytes[0..4], b"VOX ");
+        assert_eq!(i32_at(4), 150);
+        assert_eq!(&bytes[8..12], b"MAIN");
+        assert_eq!(i32_at(16) as usize, bytes.len() - 20);
+
+        // Walk the MAIN children.
+        let mut chunks = HashMap::new();
+        let mut offset = 20;
+        while offset < bytes.len() {
+            let id = std::str::from_utf8(&bytes[offset..offset + 4])
+                .unwrap()
+                .to_string();
+            let len = i32_at(offset + 4) as usize;
+            chunks.insert(id, bytes[offset + 12..offset + 12 + len].to_vec());
+            offset += 12 + len;
+        }
+
+        let size_chunk: Vec<i32> = chunks["SIZE"]
+            .chunks_exact(4)
+            .map(|b| i32::from_le_bytes(b.try_into().unwrap()))
+            .collect();
+        assert_eq!(size_chunk, vec![2, 1, 3]);
+
+        let xyzi = &chunks["XYZI"];
+        assert_eq!(u32::from_le_bytes(xyzi[0..4].try_into().unwrap()), 2);
+        let voxels: Vec<&[u8]> = xyzi[4..].chunks_exact(4).collect();
+        assert_eq!(voxels, vec![&[0, 0, 0, 2][..], &[1, 0, 2, 1][..]]);
+
+        let rgba = &chunks["RGBA"];
+        assert_eq!(rgba.len(), 1024);
+        assert_eq!(&rgba[0..4], &block_color(Some("Soil_Grass")));
+        assert_eq!(&rgba[4..8], &block_color(Some("Rock_Stone")));
+        assert_ne!(rgba[0..4], rgba[4..8]);
+    }
+
+    #[test]
+    fn vox_colors_match_frontend_block_color_map() {
+        use crate::io::vox::block_color;
+
+        // Exact names first, then the first matching keyword, else gray.
+        assert_eq!(
+            block_color(Some("hytale:Coal_Ore")),
+            [0x3a, 0x3a, 0x3a, 255]
+        );
+        assert_eq!(block_color(Some("Copper_Ore")), [0x90, 0x80, 0x70, 255]);
+        assert_eq!(block_color(Some("Soil_Grass")), [0x5c, 0xb8, 0x5c, 255]);
+        assert_eq!(block_color(Some("air")), [0, 0, 0, 0]);
+        assert_eq!(block_color(Some("Mystery")), [0x80, 0x80, 0x80, 255]);
+        assert_eq!(block_color(None), [0x80, 0x80, 0x80, 255]);
+    }
+
+    #[test]
+    fn vox_export_rejects_oversized_volumes() {
+        use crate::mesh::greedy::BlockVolume;
+
+        let blocks = vec![1; 257];
+        let volume = BlockVolume {
+            size: [257, 1, 1],
+            blocks: &blocks,
+        };
+        let err = crate::io::vox::encode(&volume, &Default::default()).unwrap_err();
+        assert!(err.contains("256"));
+    }
+}
@@ -0,0 +1,207 @@
+use crate::mesh::greedy::BlockVolume;
+use std::collections::{BTreeMap, HashMap};
+
+const VOX_VERSION: i32 = 150;
+/// MagicaVoxel models are at most 256 voxels along each axis.
+const MAX_MODEL_SIZE: usize = 256;
+/// Color index 0 is empty, leaving 255 palette slots.
+const MAX_COLORS: usize = 255;
+
+/// Encode a block volume as a MagicaVoxel `.vox` file.
+///
+/// Each distinct block id gets one palette slot, in id order, colored from
+/// its name in `palette` (id string -> block name). MagicaVoxel is z-up, so
+/// block y becomes voxel z and block z becomes voxel y.
+pub fn encode(volume: &BlockVolume, palette: &HashMap<String, String>) -> Result<Vec<u8>, String> {
+    let [sx, sy, sz] = volume.size;
+    if [sx, sy, sz].iter().any(|&s| s == 0 || s > MAX_MODEL_SIZE) {
+        return Err(format!(
+            "Volume must be between 1 and {} blocks per axis, got {}x{}x{}",
+            MAX_MODEL_SIZE, sx, sy, sz
+        ));
+    }
+    if volume.blocks.len() != sx * sy * sz {
+        return Err(format!(
+            "Expected {} blocks, got {}",
+            sx * sy * sz,
+            volume.blocks.len()
+        ));
+    }
+
+    let mut slots: BTreeMap<i32, u8> = BTreeMap::new();
+    for &id in volume.blocks.iter().filter(|&&id| id > 0) {
+        slots.entry(id).or_insert(0);
+    }
+    if slots.len() > MAX_COLORS {
+        return Err(format!(
+            "Volume has {} block types; .vox palettes hold at most {}",
+            slots.len(),
+            MAX_COLORS
+        ));
+    }
+    for (slot, index) in slots.values_mut().enumerate() {
+        *index = slot as u8 + 1;
+    }
+
+    let mut voxels = Vec::new();
+    let mut count: u32 = 0;
+    for z in 0..sz {
+        for x in 0..sx {
+            for y in 0..sy {
+                let id = volume.blocks[(z * sx + x) * sy + y];
+                if let Some(&index) = slots.get(&id) {
+                    voxels.extend_from_slice(&[x as u8, z as u8, y as u8, index]);
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    let mut size = Vec::with_capacity(12);
+    for extent in [sx, sz, sy] {
+        size.extend_from_slice(&(extent as i32).to_le_bytes());
+    }
+    let mut xyzi = count.to_le_bytes().to_vec();
+    xyzi.extend_from_slice(&voxels);
+
+    // Palette entry i is color index i + 1; unused entries stay opaque gray.
+    let mut rgba = [[128, 128, 128, 255]; 256];
+    for (&id, &index) in &slots {
+        let name = palette.get(&id.to_string()).map(String::as_str);
+        rgba[index as usize - 1] = block_color(name);
+    }
+    let rgba: Vec<u8> = rgba.concat();
+
+    let mut children = Vec::new();
+    write_chunk(&mut children, b"SIZE", &size, &[]);
+    write_chunk(&mut children, b"XYZI", &xyzi, &[]);
+    write_chunk(&mut children, b"RGBA", &rgba, &[]);
+
+    let mut out = b"VOX ".to_vec();
+    out.extend_from_slice(&VOX_VERSION.to_le_bytes());
+    write_chunk(&mut out, b"MAIN", &[], &children);
+    Ok(out)
+}
+
+fn write_chunk(out: &mut Vec<u8>, id: &[u8; 4], content: &[u8], children: &[u8]) {
+    out.extend_from_slice(id);
+    out.extend_from_slice(&(content.len() as i32).to_le_bytes());
+    out.extend_from_slice(&(children.len() as i32).to_le_bytes());
+    out.extend_from_slice(content);
+    out.extend_from_slice(children);
+}
+
+/// Block name -> color, mirroring `BLOCK_COLORS` in
+/// `src/utils/blockColorMap.ts` so exports match the 3D preview.
+const BLOCK_COLORS: &[(&str, &str)] = &[
+    // Core terrain
+    ("air", "#00000000"),
+    ("stone", "#909090"),
+    ("grass", "#5cb85c"),
+    ("dirt", "#a0724a"),
+    ("sand", "#d4c590"),
+    ("gravel", "#a0a0a0"),
+    ("bedrock", "#2a2a2a"),
+    ("cobblestone", "#7a7a7a"),
+    ("clay", "#b87333"),
+    // Rock variants
+    ("granite", "#9e8b7e"),
+    ("slate", "#5c5c6e"),
+    ("limestone", "#c4b99a"),
+    ("basalt", "#3d3d3d"),
+    ("sandstone", "#d2b48c"),
+    ("marble", "#e0ddd5"),
+    ("quartzite", "#d6cec0"),
+    // Soil variants
+    ("mud", "#6b4423"),
+    ("moss", "#4a7a4a"),
+    // Snow / Ice
+    ("snow", "#e8e8f0"),
+    ("ice", "#b0e0e6"),
+    ("packed_ice", "#9dcad4"),
+    // Wood / Organic
+    ("wood", "#8b6b4a"),
+    ("log", "#8b6b4a"),
+    ("planks", "#c4a870"),
+    ("leaves", "#3e8a3e"),
+    ("cactus", "#5c9e3e"),
+    // Fluids
+    ("water", "#4169e1"),
+    ("lava", "#ff4500"),
+    // Ores / Special
+    ("coal_ore", "#3a3a3a"),
+    ("iron_ore", "#b8916e"),
+    ("gold_ore", "#ffd700"),
+    // Vegetation
+    ("tall_grass", "#5cb85c"),
+    ("flower", "#d63384"),
+    ("mushroom", "#c0392b"),
+];
+
+/// Keyword -> color for names without an exact entry, mirroring
+/// `KEYWORD_COLORS`; the first keyword contained in the name wins.
+const KEYWORD_COLORS: &[(&str, &str)] = &[
+    ("lava", "#ff4500"),
+    ("magma", "#b22222"),
+    ("water", "#4169e1"),
+    ("ice", "#b0e0e6"),
+    ("snow", "#e8e8f0"),
+    ("stone", "#909090"),
+    ("rock", "#909090"),
+    ("granite", "#9e8b7e"),
+    ("slate", "#5c5c6e"),
+    ("basalt", "#3d3d3d"),
+    ("limestone", "#c4b99a"),
+    ("sandstone", "#d2b48c"),
+    ("marble", "#e0ddd5"),
+    ("grass", "#5cb85c"),
+    ("dirt", "#a0724a"),
+    ("soil", "#a0724a"),
+    ("mud", "#6b4423"),
+    ("clay", "#b87333"),
+    ("sand", "#d4c590"),
+    ("gravel", "#a0a0a0"),
+    ("moss", "#4a7a4a"),
+    ("wood", "#8b6b4a"),
+    ("log", "#8b6b4a"),
+    ("plank", "#c4a870"),
+    ("leaves", "#3e8a3e"),
+    ("leaf", "#3e8a3e"),
+    ("bedrock", "#2a2a2a"),
+    ("cobble", "#7a7a7a"),
+    ("ore", "#908070"),
+    ("crystal", "#88ccee"),
+    ("glass", "#c0e8ff"),
+];
+
+/// Color of blocks matching neither table.
+const UNKNOWN_COLOR: &str = "#808080";
+
+/// Preview color for a block name, resolved like `resolveBlockColor` in the
+/// frontend: without the `hytale:` prefix and lowercased, by exact name,
+/// then by keyword, else gray.
+pub fn block_color(name: Option<&str>) -> [u8; 4] {
+    let name = name
+        .map(|n| n.strip_prefix("hytale:").unwrap_or(n).to_lowercase())
+        .unwrap_or_default();
+    let color = BLOCK_COLORS
+        .iter()
+        .find(|(block, _)| *block == name)
+        .or_else(|| {
+            KEYWORD_COLORS
+                .iter()
+                .find(|(keyword, _)| name.contains(keyword))
+        })
+        .map_or(UNKNOWN_COLOR, |(_, color)| color);
+    parse_hex(color)
+}
+
+/// Parse `#rrggbb` or `#rrggbbaa`.
+fn parse_hex(color: &str) -> [u8; 4] {
+    let channel = |i: usize| {
+        color
+            .get(1 + i * 2..3 + i * 2)
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+    };
+    [0, 1, 2, 3].map(|i| channel(i).unwrap_or(if i == 3 { 255 } else { 128 }))
+}
@@ -1,11 +1,19 @@
 mod bridge;
 mod commands;
 mod io;
+mod mesh;
 mod noise;
+mod preview;
 mod schema;
 
 use bridge::client::BridgeState;
-use commands::{bridge as bridge_commands, hardware, io as io_commands, preview, process, validate};
+use commands::{
+    bridge as bridge_commands, hardware, io as io_commands, mesh as mesh_commands,
+    preview as preview_commands, process, validate,
+};
+use noise::cache::PreviewCache;
+use preview::jobs::PreviewJobs;
+use preview::tiles::TileMaps;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -15,6 +23,9 @@ pub fn run() {
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_process::init())
         .manage(BridgeState::default())
+        .manage(PreviewCache::default())
+        .manage(PreviewJobs::default())
+        .manage(TileMaps::default())
         .invoke_handler(tauri::generate_handler![
             io_commands::open_asset_pack,
             io_commands::save_asset_pack,
@@ -26,8 +37,24 @@ pub fn run() {
             io_commands::list_directory,
             io_commands::create_from_template,
             io_commands::create_blank_project,
+            io_commands::export_grid_image,
             validate::validate_asset_pack,
-            preview::evaluate_density,
+            validate::apply_fixes,
+            validate::migrate_pack,
+            validate::get_schema,
+            preview_commands::evaluate_density,
+            preview_commands::evaluate_density_binary,
+            preview_commands::start_preview_job,
+            preview_commands::cancel_preview_job,
+            preview_commands::open_tile_map,
+            preview_commands::get_map_tile,
+            preview_commands::probe_density,
+            preview_commands::analyze_density_ranges,
+            mesh_commands::extract_isosurface,
+            mesh_commands::mesh_voxels,
+            mesh_commands::export_mesh,
+            mesh_commands::export_heightfield,
+            mesh_commands::export_vox,
             bridge_commands::bridge_connect,
             bridge_commands::bridge_disconnect,
             bridge_commands::bridge_status,
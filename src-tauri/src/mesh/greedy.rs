@@ -0,0 +1,184 @@
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// Block ids on a regular grid, in the layout `bridge_fetch_chunk` returns:
+/// y fastest, then x, then z (`blocks[(z * size_x + x) * size_y + y]`).
+///
+/// Ids of zero or below are air.
+pub struct BlockVolume<'a> {
+    /// Size along x, y and z.
+    pub size: [usize; 3],
+    pub blocks: &'a [i32],
+}
+
+impl BlockVolume<'_> {
+    fn get(&self, pos: [i64; 3]) -> i32 {
+        let [sx, sy, sz] = self.size.map(|s| s as i64);
+        let [x, y, z] = pos;
+        if x < 0 || y < 0 || z < 0 || x >= sx || y >= sy || z >= sz {
+            return 0;
+        }
+        self.blocks
+            .get(((z * sx + x) * sy + y) as usize)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    fn is_solid(&self, pos: [i64; 3]) -> bool {
+        self.get(pos) > 0
+    }
+}
+
+/// Merged quads for one block id.
+///
+/// Positions are in block units relative to the volume origin. Each quad has
+/// four vertices and two triangles.
+#[derive(Debug, Default, Serialize)]
+pub struct MaterialMesh {
+    pub block_id: i32,
+    /// Block name from the palette, if it lists this id.
+    pub name: Option<String>,
+    pub positions: Vec<f32>,
+    pub normals: Vec<f32>,
+    /// Ambient occlusion per vertex: 0 (open) to 3 (fully occluded).
+    pub ao: Vec<u8>,
+    pub indices: Vec<u32>,
+}
+
+/// A visible block face before merging: block id plus per-corner AO.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct FaceCell {
+    block_id: i32,
+    ao: [u8; 4],
+}
+
+/// Quad corners in (u, v) face coordinates, counter-clockwise seen from the
+/// positive side of the face axis.
+const CORNERS: [[i64; 2]; 4] = [[0, 0], [1, 0], [1, 1], [0, 1]];
+
+/// Build greedy-merged, ambient-occluded quads for every exposed block face.
+///
+/// Faces are merged when they share a block id and identical AO at all four
+/// corners, so merging never changes shading. Meshes are returned in block
+/// id order; `palette` maps id strings to block names.
+pub fn mesh_blocks(volume: &BlockVolume, palette: &HashMap<String, String>) -> Vec<MaterialMesh> {
+    let mut meshes: BTreeMap<i32, MaterialMesh> = BTreeMap::new();
+
+    for axis in 0..3 {
+        // u x v = axis, so CORNERS wind counter-clockwise seen from +axis.
+        let (u_axis, v_axis) = ((axis + 1) % 3, (axis + 2) % 3);
+        let (u_size, v_size) = (volume.size[u_axis], volume.size[v_axis]);
+
+        for positive in [true, false] {
+            let dir: i64 = if positive { 1 } else { -1 };
+
+            for slice in 0..volume.size[axis] {
+                let mut mask: Vec<Option<FaceCell>> = vec![None; u_size * v_size];
+                for v in 0..v_size {
+                    for u in 0..u_size {
+                        let mut pos = [0i64; 3];
+                        pos[axis] = slice as i64;
+                        pos[u_axis] = u as i64;
+                        pos[v_axis] = v as i64;
+
+                        let block_id = volume.get(pos);
+                        let mut neighbor = pos;
+                        neighbor[axis] += dir;
+                        if block_id <= 0 || volume.is_solid(neighbor) {
+                            continue;
+                        }
+
+                        let ao = CORNERS.map(|[cu, cv]| {
+                            let mut side_u = neighbor;
+                            side_u[u_axis] += cu * 2 - 1;
+                            let mut side_v = neighbor;
+                            side_v[v_axis] += cv * 2 - 1;
+                            let mut corner = side_u;
+                            corner[v_axis] += cv * 2 - 1;
+                            vertex_ao(
+                                volume.is_solid(side_u),
+                                volume.is_solid(side_v),
+                                volume.is_solid(corner),
+                            )
+                        });
+                        mask[v * u_size + u] = Some(FaceCell { block_id, ao });
+                    }
+                }
+
+                // Greedy merge: grow along u, then along v while rows match.
+                for v in 0..v_size {
+                    let mut u = 0;
+                    while u < u_size {
+                        let Some(cell) = mask[v * u_size + u] else {
+                            u += 1;
+                            continue;
+                        };
+                        let mut w = 1;
+                        while u + w < u_size && mask[v * u_size + u + w] == Some(cell) {
+                            w += 1;
+                        }
+                        let mut h = 1;
+                        while v + h < v_size
+                            && (0..w).all(|du| mask[(v + h) * u_size + u + du] == Some(cell))
+                        {
+                            h += 1;
+                        }
+                        for dv in 0..h {
+                            for du in 0..w {
+                                mask[(v + dv) * u_size + u + du] = None;
+                            }
+                        }
+
+                        let mesh = meshes.entry(cell.block_id).or_insert_with(|| MaterialMesh {
+                            block_id: cell.block_id,
+                            name: palette.get(&cell.block_id.to_string()).cloned(),
+                            ..Default::default()
+                        });
+                        let plane = slice as f32 + if positive { 1.0 } else { 0.0 };
+                        let mut normal = [0.0f32; 3];
+                        normal[axis] = dir as f32;
+
+                        let base = (mesh.positions.len() / 3) as u32;
+                        for [cu, cv] in CORNERS {
+                            let mut p = [0.0f32; 3];
+                            p[axis] = plane;
+                            p[u_axis] = (u + cu as usize * w) as f32;
+                            p[v_axis] = (v + cv as usize * h) as f32;
+                            mesh.positions.extend_from_slice(&p);
+                            mesh.normals.extend_from_slice(&normal);
+                        }
+                        mesh.ao.extend_from_slice(&cell.ao);
+
+                        // Split along the less occluded diagonal so a single dark
+                        // corner doesn't bleed across the quad, and wind outward.
+                        let [a0, a1, a2, a3] = cell.ao;
+                        let tris: [u32; 6] = if a0 as u16 + a2 as u16 <= a1 as u16 + a3 as u16 {
+                            [0, 1, 2, 0, 2, 3]
+                        } else {
+                            [1, 2, 3, 1, 3, 0]
+                        };
+                        for i in 0..2 {
+                            let mut tri = [tris[i * 3], tris[i * 3 + 1], tris[i * 3 + 2]];
+                            if !positive {
+                                tri.swap(1, 2);
+                            }
+                            mesh.indices.extend(tri.iter().map(|t| base + t));
+                        }
+
+                        u += w;
+                    }
+                }
+            }
+        }
+    }
+
+    meshes.into_values().collect()
+}
+
+/// Occlusion of a face corner from its two edge neighbours and the diagonal.
+fn vertex_ao(side_a: bool, side_b: bool, corner: bool) -> u8 {
+    if side_a && side_b {
+        return 3;
+    }
+    side_a as u8 + side_b as u8 + corner as u8
+}
@@ -0,0 +1,63 @@
+use super::surface_nets::TriangleMesh;
+
+/// Triangulate a row-major `resolution` x `resolution` heightfield.
+///
+/// Sample (col, row) sits at world x = `min[0]`..`max[0]` and z =
+/// `min[1]`..`max[1]`, with height `value * height_scale` on y. Triangles
+/// face +y and normals come from central differences; an axis with a
+/// zero-width range has no slope along it, so its normals stay finite.
+pub fn heightfield(
+    values: &[f32],
+    resolution: usize,
+    min: [f64; 2],
+    max: [f64; 2],
+    height_scale: f64,
+) -> TriangleMesh {
+    let mut mesh = TriangleMesh::default();
+    if resolution < 2 || values.len() != resolution * resolution {
+        return mesh;
+    }
+
+    let step = [0, 1].map(|axis| (max[axis] - min[axis]) / (resolution - 1) as f64);
+    let height = |col: usize, row: usize| values[row * resolution + col] as f64 * height_scale;
+    let slope = |rise: f64, run: f64| if run == 0.0 { 0.0 } else { rise / run };
+
+    for row in 0..resolution {
+        for col in 0..resolution {
+            mesh.positions.extend_from_slice(&[
+                (min[0] + col as f64 * step[0]) as f32,
+                height(col, row) as f32,
+                (min[1] + row as f64 * step[1]) as f32,
+            ]);
+
+            let (left, right) = (col.saturating_sub(1), (col + 1).min(resolution - 1));
+            let (up, down) = (row.saturating_sub(1), (row + 1).min(resolution - 1));
+            let dx = slope(
+                height(right, row) - height(left, row),
+                (right - left) as f64 * step[0],
+            );
+            let dz = slope(
+                height(col, down) - height(col, up),
+                (down - up) as f64 * step[1],
+            );
+            let length = (dx * dx + 1.0 + dz * dz).sqrt();
+            mesh.normals.extend_from_slice(&[
+                (-dx / length) as f32,
+                (1.0 / length) as f32,
+                (-dz / length) as f32,
+            ]);
+        }
+    }
+
+    for row in 0..resolution - 1 {
+        for col in 0..resolution - 1 {
+            let i = (row * resolution + col) as u32;
+            let r = resolution as u32;
+            // Counter-clockwise seen from above.
+            mesh.indices
+                .extend_from_slice(&[i, i + r, i + 1, i + 1, i + r, i + r + 1]);
+        }
+    }
+
+    mesh
+}
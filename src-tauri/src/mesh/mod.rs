@@ -0,0 +1,5 @@
+pub mod greedy;
+pub mod heightfield;
+pub mod surface_nets;
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,220 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Densities sampled on a regular 3D grid.
+///
+/// Values are stored x-fastest: `values[x + dims[0] * (y + dims[1] * z)]`.
+pub struct DensityVolume {
+    pub dims: [usize; 3],
+    pub values: Vec<f32>,
+    /// World position of the sample at index (0, 0, 0).
+    pub origin: [f64; 3],
+    /// World distance between neighbouring samples on each axis.
+    pub step: [f64; 3],
+}
+
+impl DensityVolume {
+    fn get(&self, x: usize, y: usize, z: usize) -> f32 {
+        self.values[x + self.dims[0] * (y + self.dims[1] * z)]
+    }
+
+    /// Surround the volume with one layer of samples below `iso_level`, so
+    /// surfaces crossing the box boundary are capped and the mesh is closed.
+    pub fn padded(&self, iso_level: f32) -> DensityVolume {
+        let [dx, dy, dz] = self.dims;
+        let dims = [dx + 2, dy + 2, dz + 2];
+        let outside = iso_level - 1.0;
+        let mut values = vec![outside; dims[0] * dims[1] * dims[2]];
+        for z in 0..dz {
+            for y in 0..dy {
+                for x in 0..dx {
+                    values[(x + 1) + dims[0] * ((y + 1) + dims[1] * (z + 1))] = self.get(x, y, z);
+                }
+            }
+        }
+        DensityVolume {
+            dims,
+            values,
+            origin: [
+                self.origin[0] - self.step[0],
+                self.origin[1] - self.step[1],
+                self.origin[2] - self.step[2],
+            ],
+            step: self.step,
+        }
+    }
+}
+
+/// Indexed triangle mesh with flattened `[x, y, z, ...]` attribute arrays.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TriangleMesh {
+    pub positions: Vec<f32>,
+    pub normals: Vec<f32>,
+    pub indices: Vec<u32>,
+}
+
+/// Corner offsets of a cell; bit 0 is x, bit 1 is y, bit 2 is z.
+const CORNERS: [[usize; 3]; 8] = [
+    [0, 0, 0],
+    [1, 0, 0],
+    [0, 1, 0],
+    [1, 1, 0],
+    [0, 0, 1],
+    [1, 0, 1],
+    [0, 1, 1],
+    [1, 1, 1],
+];
+
+/// The 12 cell edges as pairs of corner indices.
+const EDGES: [[usize; 2]; 12] = [
+    [0, 1],
+    [2, 3],
+    [4, 5],
+    [6, 7],
+    [0, 2],
+    [1, 3],
+    [4, 6],
+    [5, 7],
+    [0, 4],
+    [1, 5],
+    [2, 6],
+    [3, 7],
+];
+
+/// Extract the surface where the density crosses `iso_level` using surface nets.
+///
+/// Samples above the iso-level are solid. Each cell the surface passes
+/// through gets one vertex at the mean of its edge crossings, and every grid
+/// edge with a sign change emits a quad joining the four cells around it,
+/// wound counter-clockwise when seen from outside. Normals point out of the
+/// solid, along the negative density gradient of the trilinear field.
+pub fn extract(volume: &DensityVolume, iso_level: f32) -> TriangleMesh {
+    let [dx, dy, dz] = volume.dims;
+    let mut mesh = TriangleMesh::default();
+    if dx < 2 || dy < 2 || dz < 2 {
+        return mesh;
+    }
+
+    let inside = |x: usize, y: usize, z: usize| volume.get(x, y, z) > iso_level;
+    let cell_index = |x: usize, y: usize, z: usize| x + (dx - 1) * (y + (dy - 1) * z);
+    let mut cell_vertex: HashMap<usize, u32> = HashMap::new();
+
+    for z in 0..dz - 1 {
+        for y in 0..dy - 1 {
+            for x in 0..dx - 1 {
+                let corner_values: [f32; 8] =
+                    CORNERS.map(|[cx, cy, cz]| volume.get(x + cx, y + cy, z + cz));
+                let mask = corner_values.iter().enumerate().fold(0u8, |m, (i, v)| {
+                    if *v > iso_level {
+                        m | (1 << i)
+                    } else {
+                        m
+                    }
+                });
+                if mask == 0 || mask == 0xff {
+                    continue;
+                }
+
+                // Mean of the edge crossings, in cell-local coordinates.
+                let mut sum = [0.0f64; 3];
+                let mut crossings = 0;
+                for [a, b] in EDGES {
+                    let (va, vb) = (corner_values[a], corner_values[b]);
+                    if (va > iso_level) == (vb > iso_level) {
+                        continue;
+                    }
+                    let t = ((iso_level - va) / (vb - va)) as f64;
+                    for axis in 0..3 {
+                        let (pa, pb) = (CORNERS[a][axis] as f64, CORNERS[b][axis] as f64);
+                        sum[axis] += pa + t * (pb - pa);
+                    }
+                    crossings += 1;
+                }
+                let local = sum.map(|s| s / crossings as f64);
+
+                let cell = [x, y, z];
+                for axis in 0..3 {
+                    let world =
+                        volume.origin[axis] + (cell[axis] as f64 + local[axis]) * volume.step[axis];
+                    mesh.positions.push(world as f32);
+                }
+                let normal = trilinear_normal(&corner_values, local, volume.step);
+                mesh.normals.extend_from_slice(&normal);
+
+                cell_vertex.insert(cell_index(x, y, z), (mesh.positions.len() / 3 - 1) as u32);
+            }
+        }
+    }
+
+    // One quad per sign-changing grid edge. For an edge along `a`, the other
+    // two axes (b, c) follow cyclically so that b x c = a.
+    for z in 0..dz {
+        for y in 0..dy {
+            for x in 0..dx {
+                let p = [x, y, z];
+                for a in 0..3 {
+                    let (b, c) = ((a + 1) % 3, (a + 2) % 3);
+                    if p[a] + 1 >= volume.dims[a] || p[b] == 0 || p[c] == 0 {
+                        continue;
+                    }
+                    let mut q = p;
+                    q[a] += 1;
+                    let solid_low = inside(p[0], p[1], p[2]);
+                    if solid_low == inside(q[0], q[1], q[2]) {
+                        continue;
+                    }
+
+                    let cell_at = |db: usize, dc: usize| {
+                        let mut cell = p;
+                        cell[b] -= db;
+                        cell[c] -= dc;
+                        if cell[b] >= volume.dims[b] - 1 || cell[c] >= volume.dims[c] - 1 {
+                            return None;
+                        }
+                        cell_vertex
+                            .get(&cell_index(cell[0], cell[1], cell[2]))
+                            .copied()
+                    };
+                    let quad = [cell_at(1, 1), cell_at(0, 1), cell_at(0, 0), cell_at(1, 0)];
+                    let [Some(v0), Some(v1), Some(v2), Some(v3)] = quad else {
+                        continue;
+                    };
+
+                    if solid_low {
+                        mesh.indices.extend_from_slice(&[v0, v1, v2, v0, v2, v3]);
+                    } else {
+                        mesh.indices.extend_from_slice(&[v0, v2, v1, v0, v3, v2]);
+                    }
+                }
+            }
+        }
+    }
+
+    mesh
+}
+
+/// Outward unit normal at `local` inside a cell, from the trilinear gradient.
+fn trilinear_normal(corner_values: &[f32; 8], local: [f64; 3], step: [f64; 3]) -> [f32; 3] {
+    let mut gradient = [0.0f64; 3];
+    for (i, &value) in corner_values.iter().enumerate() {
+        let corner = CORNERS[i];
+        let weights = [0, 1, 2].map(|axis| {
+            if corner[axis] == 1 {
+                local[axis]
+            } else {
+                1.0 - local[axis]
+            }
+        });
+        for axis in 0..3 {
+            let sign = if corner[axis] == 1 { 1.0 } else { -1.0 };
+            let others: f64 = (0..3).filter(|&o| o != axis).map(|o| weights[o]).product();
+            gradient[axis] += value as f64 * sign * others / step[axis];
+        }
+    }
+
+    let length = gradient.iter().map(|g| g * g).sum::<f64>().sqrt();
+    if length == 0.0 {
+        return [0.0, 1.0, 0.0];
+    }
+    gradient.map(|g| (-g / length) as f32)
+}
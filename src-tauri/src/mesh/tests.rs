@@ -0,0 +1,231 @@
+#[cfg(test)]
+mod tests {
+    use crate::mesh::surface_nets::{extract, DensityVolume};
+    use std::collections::HashMap;
+
+    /// Sphere of `radius` centred in an n³ box, positive inside.
+    fn sphere_volume(n: usize, radius: f64) -> DensityVolume {
+        let center = (n - 1) as f64 / 2.0;
+        let mut values = Vec::with_capacity(n * n * n);
+        for z in 0..n {
+            for y in 0..n {
+                for x in 0..n {
+                    let d = [x, y, z]
+                        .map(|c| c as f64 - center)
+                        .iter()
+                        .map(|c| c * c)
+                        .sum::<f64>()
+                        .sqrt();
+                    values.push((radius - d) as f32);
+                }
+            }
+        }
+        DensityVolume {
+            dims: [n, n, n],
+            values,
+            origin: [0.0; 3],
+            step: [1.0; 3],
+        }
+    }
+
+    /// Every undirected edge must be used by exactly two triangles, once in each direction.
+    fn assert_watertight(indices: &[u32]) {
+        let mut directed: HashMap<(u32, u32), usize> = HashMap::new();
+        for tri in indices.chunks_exact(3) {
+            for i in 0..3 {
+                *directed.entry((tri[i], tri[(i + 1) % 3])).or_default() += 1;
+            }
+        }
+        for (&(a, b), &count) in &directed {
+            assert_eq!(count, 1, "edge {}->{} repeated", a, b);
+            assert_eq!(
+                directed.get(&(b, a)),
+                Some(&1),
+                "edge {}->{} has no twin",
+                a,
+                b
+            );
+        }
+    }
+
+    // ── Surface nets ──────────────────────────────────────────────────
+
+    #[test]
+    fn sphere_mesh_is_closed_with_outward_normals() {
+        let volume = sphere_volume(12, 4.0);
+        let mesh = extract(&volume, 0.0);
+
+        assert!(!mesh.indices.is_empty());
+        assert_eq!(mesh.positions.len(), mesh.normals.len());
+        assert_watertight(&mesh.indices);
+
+        let center = 5.5f32;
+        for (pos, normal) in mesh
+            .positions
+            .chunks_exact(3)
+            .zip(mesh.normals.chunks_exact(3))
+        {
+            let offset = [pos[0] - center, pos[1] - center, pos[2] - center];
+            let radius = offset.iter().map(|c| c * c).sum::<f32>().sqrt();
+            assert!((radius - 4.0).abs() < 0.5, "vertex at radius {}", radius);
+            let dot: f32 = offset.iter().zip(normal).map(|(o, n)| o * n).sum();
+            assert!(dot > 0.0, "normal points inward");
+        }
+
+        // Triangle winding agrees with the normals.
+        let vertex = |i: u32| {
+            let i = i as usize * 3;
+            [
+                mesh.positions[i],
+                mesh.positions[i + 1],
+                mesh.positions[i + 2],
+            ]
+        };
+        for tri in mesh.indices.chunks_exact(3) {
+            let [a, b, c] = [vertex(tri[0]), vertex(tri[1]), vertex(tri[2])];
+            let u = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+            let v = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+            let face = [
+                u[1] * v[2] - u[2] * v[1],
+                u[2] * v[0] - u[0] * v[2],
+                u[0] * v[1] - u[1] * v[0],
+            ];
+            let centroid: Vec<f32> = (0..3)
+                .map(|k| (a[k] + b[k] + c[k]) / 3.0 - center)
+                .collect();
+            let dot: f32 = face.iter().zip(&centroid).map(|(f, o)| f * o).sum();
+            assert!(dot >= 0.0, "triangle wound inward");
+        }
+    }
+
+    #[test]
+    fn padding_caps_surfaces_cut_by_the_box() {
+        // Solid everywhere: no surface inside, but padding closes the box.
+        let volume = DensityVolume {
+            dims: [3, 3, 3],
+            values: vec![1.0; 27],
+            origin: [10.0, 0.0, 0.0],
+            step: [2.0, 1.0, 1.0],
+        };
+        assert!(extract(&volume, 0.0).indices.is_empty());
+
+        let mesh = extract(&volume.padded(0.0), 0.0);
+        assert!(!mesh.indices.is_empty());
+        assert_watertight(&mesh.indices);
+
+        let xs: Vec<f32> = mesh.positions.chunks_exact(3).map(|p| p[0]).collect();
+        let min_x = xs.iter().cloned().fold(f32::MAX, f32::min);
+        let max_x = xs.iter().cloned().fold(f32::MIN, f32::max);
+        assert!(min_x > 8.0 && min_x < 10.0, "min x {}", min_x);
+        assert!(max_x > 14.0 && max_x < 16.0, "max x {}", max_x);
+    }
+
+    // ── Greedy voxel meshing ──────────────────────────────────────────
+
+    /// Build a volume from (x, y, z, id) entries in the chunk layout.
+    fn blocks(size: [usize; 3], solid: &[([usize; 3], i32)]) -> Vec<i32> {
+        let mut blocks = vec![0; size[0] * size[1] * size[2]];
+        for &([x, y, z], id) in solid {
+            blocks[(z * size[0] + x) * size[1] + y] = id;
+        }
+        blocks
+    }
+
+    #[test]
+    fn greedy_merges_flat_slab_into_six_quads() {
+        use crate::mesh::greedy::{mesh_blocks, BlockVolume};
+
+        let size = [4, 1, 3];
+        let solid: Vec<([usize; 3], i32)> = (0..4)
+            .flat_map(|x| (0..3).map(move |z| ([x, 0, z], 7)))
+            .collect();
+        let data = blocks(size, &solid);
+        let palette = HashMap::from([("7".to_string(), "Rock_Stone".to_string())]);
+        let meshes = mesh_blocks(
+            &BlockVolume {
+                size,
+                blocks: &data,
+            },
+            &palette,
+        );
+
+        assert_eq!(meshes.len(), 1);
+        let mesh = &meshes[0];
+        assert_eq!(mesh.block_id, 7);
+        assert_eq!(mesh.name.as_deref(), Some("Rock_Stone"));
+        assert_eq!(mesh.indices.len(), 6 * 6, "one quad per side");
+        assert_eq!(mesh.ao, vec![0; 24], "nothing occludes a lone slab");
+
+        // Weld vertices by position: the six quads close a box.
+        let mut ids: HashMap<[u32; 3], u32> = HashMap::new();
+        let welded: Vec<u32> = mesh
+            .indices
+            .iter()
+            .map(|&i| {
+                let p = &mesh.positions[i as usize * 3..i as usize * 3 + 3];
+                let key = [p[0].to_bits(), p[1].to_bits(), p[2].to_bits()];
+                let next = ids.len() as u32;
+                *ids.entry(key).or_insert(next)
+            })
+            .collect();
+        assert_eq!(ids.len(), 8);
+        assert_watertight(&welded);
+    }
+
+    #[test]
+    fn greedy_splits_by_material_and_ambient_occlusion() {
+        use crate::mesh::greedy::{mesh_blocks, BlockVolume};
+
+        // A 3x3 floor with a pillar block on top of the centre.
+        let size = [3, 2, 3];
+        let mut solid: Vec<([usize; 3], i32)> = (0..3)
+            .flat_map(|x| (0..3).map(move |z| ([x, 0, z], 1)))
+            .collect();
+        solid.push(([1, 1, 1], 2));
+        let data = blocks(size, &solid);
+        let meshes = mesh_blocks(
+            &BlockVolume {
+                size,
+                blocks: &data,
+            },
+            &HashMap::new(),
+        );
+
+        assert_eq!(
+            meshes.iter().map(|m| m.block_id).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert!(meshes[0].name.is_none());
+
+        // The floor's top face is occluded around the pillar, so it can't be
+        // a single quad: the eight tops around the pillar each touch it.
+        let floor = &meshes[0];
+        let top_quads: Vec<&[f32]> = floor
+            .normals
+            .chunks_exact(12)
+            .filter(|n| n[1] == 1.0)
+            .collect();
+        assert!(top_quads.len() > 1);
+        assert!(floor.ao.iter().any(|&ao| ao > 0));
+
+        // The pillar's bottom face is hidden by the floor.
+        let pillar = &meshes[1];
+        assert_eq!(pillar.indices.len(), 5 * 6);
+        assert!(pillar.normals.chunks_exact(3).all(|n| n[1] != -1.0));
+    }
+
+    #[test]
+    fn heightfield_with_zero_width_range_keeps_finite_normals() {
+        use crate::mesh::heightfield::heightfield;
+
+        let values = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+        let mesh = heightfield(&values, 3, [0.0, 5.0], [4.0, 5.0], 1.0);
+        assert_eq!(mesh.normals.len(), 9 * 3);
+        assert!(mesh.normals.iter().all(|n| n.is_finite()));
+        // Only the x slope remains: every normal tilts toward -x.
+        assert!(mesh
+            .normals
+            .chunks_exact(3)
+            .all(|n| n[0] < 0.0 && n[2] == 0.0));
+    }
+}
@@ -0,0 +1,282 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use super::evaluator::DensityEvaluator;
+use super::ranges::{self, Interval};
+
+/// Samples per side of the grid used when the selector range isn't static
+/// or its gradient is needed.
+const SAMPLE_GRID: usize = 64;
+/// Blocks between grid samples.
+const SAMPLE_SPACING: f64 = 64.0;
+/// Bounds closer than this are treated as touching.
+const EPSILON: f64 = 1e-9;
+
+/// Kind of issue found in a `NoiseRange`'s biome bands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum BandFindingKind {
+    /// Selector values that no band covers, so only `DefaultBiome` applies.
+    Gap,
+    /// Two bands claim the same selector values.
+    Overlap,
+    /// A band's `Min` is greater than its `Max`.
+    Inverted,
+    /// A band lies entirely outside the selector's output range.
+    Unreachable,
+    /// `DefaultTransitionDistance` is wider than a band is in blocks.
+    TransitionTooWide,
+}
+
+/// One band finding. `band` is the index into `Biomes`, if it is about one.
+#[derive(Debug, Clone, Serialize)]
+pub struct BandFinding {
+    pub band: Option<usize>,
+    pub kind: BandFindingKind,
+    pub message: String,
+}
+
+/// Result of analyzing a `NoiseRange` world structure's bands.
+#[derive(Debug, Clone, Serialize)]
+pub struct BandAnalysis {
+    /// Output range of the selector density.
+    pub selector: Interval,
+    /// True when `selector` was sampled because no static bound exists.
+    pub sampled: bool,
+    /// Mean selector change per block, from sampling; `None` if the
+    /// selector couldn't be evaluated.
+    pub gradient: Option<f64>,
+    pub findings: Vec<BandFinding>,
+}
+
+struct Band<'a> {
+    index: usize,
+    biome: &'a str,
+    range: Interval,
+}
+
+/// Check a `NoiseRange`'s `Biomes` bands against its `Density` selector.
+///
+/// The selector range comes from static range analysis when that is
+/// bounded, otherwise from sampling a grid around the origin at y = 0. The
+/// grid is also sampled for the selector's gradient when
+/// `DefaultTransitionDistance` is set. Selectors that can't be evaluated
+/// faithfully (imports of other files' exports, unknown types) are never
+/// sampled; only their selector-independent findings are reported.
+/// Returns `None` when the structure has no `Density`.
+pub fn analyze_bands(structure: &Map<String, Value>) -> Option<BandAnalysis> {
+    let density = structure.get("Density")?;
+    let transition = structure
+        .get("DefaultTransitionDistance")
+        .and_then(|v| v.as_f64())
+        .unwrap_or(0.0);
+
+    let static_range = ranges::analyze(density).output;
+    let samples = if static_range.is_bounded() && transition <= 0.0 {
+        None
+    } else {
+        DensityEvaluator::from_json_strict(density)
+            .ok()
+            .and_then(|evaluator| sample(&evaluator))
+    };
+    let (selector, sampled) = if static_range.is_bounded() {
+        (static_range, false)
+    } else if let Some(samples) = &samples {
+        (samples.range, true)
+    } else {
+        (Interval::UNBOUNDED, false)
+    };
+    let gradient = samples.as_ref().map(|s| s.gradient);
+    let source = if sampled { "sampled" } else { "static" };
+
+    let mut findings = Vec::new();
+    let mut bands = Vec::new();
+    let entries = structure.get("Biomes").and_then(|v| v.as_array());
+    for (index, entry) in entries.into_iter().flatten().enumerate() {
+        let biome = entry.get("Biome").and_then(|v| v.as_str()).unwrap_or("?");
+        let (Some(min), Some(max)) = (
+            entry.get("Min").and_then(|v| v.as_f64()),
+            entry.get("Max").and_then(|v| v.as_f64()),
+        ) else {
+            continue;
+        };
+
+        if min > max {
+            findings.push(BandFinding {
+                band: Some(index),
+                kind: BandFindingKind::Inverted,
+                message: format!(
+                    "Band '{}' has Min {} greater than Max {}, so it matches nothing",
+                    biome, min, max
+                ),
+            });
+        } else if max < selector.min - EPSILON || min > selector.max + EPSILON {
+            findings.push(BandFinding {
+                band: Some(index),
+                kind: BandFindingKind::Unreachable,
+                message: format!(
+                    "Band '{}' [{}, {}] is outside the selector's {} range [{}, {}]",
+                    biome,
+                    min,
+                    max,
+                    source,
+                    fmt(selector.min),
+                    fmt(selector.max)
+                ),
+            });
+        } else {
+            bands.push(Band {
+                index,
+                biome,
+                range: Interval { min, max },
+            });
+        }
+    }
+
+    bands.sort_by(|a, b| a.range.min.total_cmp(&b.range.min));
+
+    for (i, a) in bands.iter().enumerate() {
+        for b in &bands[i + 1..] {
+            let lo = b.range.min;
+            let hi = a.range.max.min(b.range.max);
+            if hi - lo > EPSILON {
+                findings.push(BandFinding {
+                    band: Some(b.index),
+                    kind: BandFindingKind::Overlap,
+                    message: format!(
+                        "Band '{}' overlaps band '{}' on [{}, {}]",
+                        b.biome, a.biome, lo, hi
+                    ),
+                });
+            }
+        }
+    }
+
+    // Sweep the selector range for values no band covers
+    let gaps = if selector.is_bounded() {
+        gaps(selector, &bands)
+    } else {
+        Vec::new()
+    };
+    let default = structure
+        .get("DefaultBiome")
+        .and_then(|v| v.as_str())
+        .unwrap_or("?");
+    for (lo, hi) in gaps {
+        findings.push(BandFinding {
+            band: None,
+            kind: BandFindingKind::Gap,
+            message: format!(
+                "Selector values in [{}, {}] match no band; only DefaultBiome '{}' applies there",
+                fmt(lo),
+                fmt(hi),
+                default
+            ),
+        });
+    }
+
+    if let Some(gradient) = gradient.filter(|g| *g > 0.0 && transition > 0.0) {
+        for band in &bands {
+            // A band spanning the whole selector range never borders another
+            if band.range.min <= selector.min && band.range.max >= selector.max {
+                continue;
+            }
+            // Only the reachable part of a band takes up space in the world
+            let width = band.range.max.min(selector.max) - band.range.min.max(selector.min);
+            let blocks = width / gradient;
+            if blocks < transition {
+                findings.push(BandFinding {
+                    band: Some(band.index),
+                    kind: BandFindingKind::TransitionTooWide,
+                    message: format!(
+                        "Band '{}' is about {:.0} blocks wide, narrower than DefaultTransitionDistance {}",
+                        band.biome, blocks, transition
+                    ),
+                });
+            }
+        }
+    }
+
+    Some(BandAnalysis {
+        selector,
+        sampled,
+        gradient,
+        findings,
+    })
+}
+
+/// Parts of `selector` not covered by any of the (sorted) bands.
+fn gaps(selector: Interval, bands: &[Band]) -> Vec<(f64, f64)> {
+    let mut covered = selector.min;
+    let mut gaps = Vec::new();
+    for band in bands {
+        if band.range.min - covered > EPSILON {
+            gaps.push((covered, band.range.min));
+        }
+        covered = covered.max(band.range.max);
+    }
+    if selector.max - covered > EPSILON {
+        gaps.push((covered, selector.max));
+    }
+    gaps
+}
+
+/// Format a bound, spelling out infinities.
+fn fmt(v: f64) -> String {
+    if v == f64::INFINITY {
+        "inf".to_string()
+    } else if v == f64::NEG_INFINITY {
+        "-inf".to_string()
+    } else {
+        format!("{}", (v * 1e4).round() / 1e4)
+    }
+}
+
+struct Samples {
+    range: Interval,
+    gradient: f64,
+}
+
+/// Sample the selector on a grid centered on the origin, with each sample's
+/// neighbours one block away in x and z for the gradient.
+fn sample(evaluator: &DensityEvaluator) -> Option<Samples> {
+    let n = SAMPLE_GRID * SAMPLE_GRID;
+    let half = SAMPLE_GRID as f64 * SAMPLE_SPACING / 2.0;
+    let mut xs = Vec::with_capacity(n * 3);
+    let mut zs = Vec::with_capacity(n * 3);
+    for j in 0..SAMPLE_GRID {
+        for i in 0..SAMPLE_GRID {
+            let x = i as f64 * SAMPLE_SPACING - half;
+            let z = j as f64 * SAMPLE_SPACING - half;
+            xs.extend([x, x + 1.0, x]);
+            zs.extend([z, z, z + 1.0]);
+        }
+    }
+    let ys = vec![0.0; xs.len()];
+    let mut out = vec![0.0; xs.len()];
+    evaluator.evaluate_batch(&xs, &ys, &zs, &mut out);
+
+    let mut range = Interval {
+        min: f64::INFINITY,
+        max: f64::NEG_INFINITY,
+    };
+    let mut gradient = 0.0;
+    let mut count = 0usize;
+    for triple in out.chunks_exact(3) {
+        let [v, vx, vz] = [triple[0], triple[1], triple[2]];
+        if !v.is_finite() {
+            continue;
+        }
+        range = range.union(Interval::point(v));
+        if vx.is_finite() && vz.is_finite() {
+            gradient += ((vx - v).powi(2) + (vz - v).powi(2)).sqrt();
+            count += 1;
+        }
+    }
+    if count == 0 {
+        return None;
+    }
+    Some(Samples {
+        range,
+        gradient: gradient / count as f64,
+    })
+}
@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use super::nodes::NodeEval;
+
+/// Sample grid a cached array was evaluated on.
+///
+/// Floats are stored as bits so keys compare exactly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GridKey {
+    resolution: u32,
+    range_min: u64,
+    range_max: u64,
+    y_level: u64,
+}
+
+impl GridKey {
+    pub fn new(resolution: u32, range_min: f64, range_max: f64, y_level: f64) -> Self {
+        GridKey {
+            resolution,
+            range_min: range_min.to_bits(),
+            range_max: range_max.to_bits(),
+            y_level: y_level.to_bits(),
+        }
+    }
+
+    /// Number of samples in the grid.
+    pub fn sample_count(&self) -> usize {
+        self.resolution as usize * self.resolution as usize
+    }
+}
+
+/// Structural hash of a subtree (with imports resolved) plus the grid it was sampled on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    pub subtree: u64,
+    pub grid: GridKey,
+}
+
+struct CacheEntry {
+    /// Canonical text of the subtree, compared on lookup so that hash
+    /// collisions never replay another subtree's samples.
+    subtree: Arc<str>,
+    values: Arc<Vec<f64>>,
+    last_used: u64,
+}
+
+impl CacheEntry {
+    fn bytes(&self) -> usize {
+        entry_bytes(&self.subtree, &self.values)
+    }
+}
+
+fn entry_bytes(subtree: &str, values: &[f64]) -> usize {
+    subtree.len() + std::mem::size_of_val(values)
+}
+
+/// Default budget of the session cache: 256 MiB. A grid of f64 samples
+/// takes 2 MiB at 512² and 8 MiB at 1024², so this holds about 128 and 32
+/// subtree grids respectively.
+pub const DEFAULT_CAPACITY: usize = 256 * 1024 * 1024;
+
+/// LRU cache of per-subtree sample arrays, bounded in bytes.
+pub struct SubtreeCache {
+    entries: HashMap<CacheKey, CacheEntry>,
+    capacity: usize,
+    used: usize,
+    clock: u64,
+}
+
+impl Default for SubtreeCache {
+    fn default() -> Self {
+        SubtreeCache::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl SubtreeCache {
+    /// Create a cache holding at most `capacity` bytes of samples and
+    /// subtree text across all entries.
+    pub fn new(capacity: usize) -> Self {
+        SubtreeCache {
+            entries: HashMap::new(),
+            capacity,
+            used: 0,
+            clock: 0,
+        }
+    }
+
+    /// Budget in bytes.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Look up the sample array of `subtree`, marking it as recently used.
+    pub fn get(&mut self, key: &CacheKey, subtree: &str) -> Option<Arc<Vec<f64>>> {
+        self.clock += 1;
+        let entry = self
+            .entries
+            .get_mut(key)
+            .filter(|e| &*e.subtree == subtree)?;
+        entry.last_used = self.clock;
+        Some(entry.values.clone())
+    }
+
+    /// Store the sample array of `subtree`, evicting least recently used
+    /// entries to stay in budget.
+    ///
+    /// Entries larger than the whole budget are not stored.
+    pub fn insert(&mut self, key: CacheKey, subtree: Arc<str>, values: Arc<Vec<f64>>) {
+        let bytes = entry_bytes(&subtree, &values);
+        if bytes > self.capacity {
+            return;
+        }
+        self.clock += 1;
+        if let Some(old) = self.entries.remove(&key) {
+            self.used -= old.bytes();
+        }
+        while self.used + bytes > self.capacity {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, e)| e.last_used)
+                .map(|(k, _)| *k)
+            else {
+                break;
+            };
+            if let Some(evicted) = self.entries.remove(&oldest) {
+                self.used -= evicted.bytes();
+            }
+        }
+        self.used += bytes;
+        self.entries.insert(
+            key,
+            CacheEntry {
+                subtree,
+                values,
+                last_used: self.clock,
+            },
+        );
+    }
+}
+
+/// Session-wide subtree cache, managed as Tauri state.
+#[derive(Default)]
+pub struct PreviewCache(pub Mutex<SubtreeCache>);
+
+/// Index of the grid sample currently being evaluated.
+#[derive(Default)]
+pub struct GridCursor(AtomicUsize);
+
+impl GridCursor {
+    pub fn set(&self, index: usize) {
+        self.0.store(index, Ordering::Relaxed);
+    }
+
+    fn get(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Returns the cached value for the current sample instead of evaluating a subtree.
+pub struct ReplayNode {
+    pub values: Arc<Vec<f64>>,
+    pub cursor: Arc<GridCursor>,
+}
+
+impl NodeEval for ReplayNode {
+    fn eval(&self, _x: f64, _y: f64, _z: f64) -> f64 {
+        self.values.get(self.cursor.get()).copied().unwrap_or(0.0)
+    }
+
+    /// The cursor marks the first lane; lanes are consecutive samples.
+    fn eval_batch(&self, _xs: &[f64], _ys: &[f64], _zs: &[f64], out: &mut [f64]) {
+        let start = self.cursor.get();
+        match self.values.get(start..start + out.len()) {
+            Some(values) => out.copy_from_slice(values),
+            None => out.fill(0.0),
+        }
+    }
+}
+
+/// Samples of one subtree recorded during a grid pass, stored as f64 bits
+/// so recording needs no lock.
+pub struct Recording(Vec<AtomicU64>);
+
+impl Recording {
+    pub fn new(len: usize) -> Self {
+        Recording((0..len).map(|_| AtomicU64::new(0)).collect())
+    }
+
+    fn set(&self, index: usize, value: f64) {
+        if let Some(slot) = self.0.get(index) {
+            slot.store(value.to_bits(), Ordering::Relaxed);
+        }
+    }
+
+    /// The recorded samples.
+    pub fn values(&self) -> Vec<f64> {
+        self.0
+            .iter()
+            .map(|bits| f64::from_bits(bits.load(Ordering::Relaxed)))
+            .collect()
+    }
+}
+
+/// Evaluates a subtree and records its value for the current sample.
+pub struct RecordNode {
+    pub inner: Box<dyn NodeEval>,
+    pub cursor: Arc<GridCursor>,
+    pub values: Arc<Recording>,
+}
+
+impl NodeEval for RecordNode {
+    fn eval(&self, x: f64, y: f64, z: f64) -> f64 {
+        let value = self.inner.eval(x, y, z);
+        self.values.set(self.cursor.get(), value);
+        value
+    }
+
+    /// The cursor marks the first lane; lanes are consecutive samples.
+    fn eval_batch(&self, xs: &[f64], ys: &[f64], zs: &[f64], out: &mut [f64]) {
+        self.inner.eval_batch(xs, ys, zs, out);
+        let start = self.cursor.get();
+        for (i, value) in out.iter().enumerate() {
+            self.values.set(start + i, *value);
+        }
+    }
+}
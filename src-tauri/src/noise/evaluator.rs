@@ -1,125 +1,678 @@
 use serde_json::Value;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
 
-use super::nodes::NodeEval;
+use super::cache::{
+    CacheKey, GridCursor, GridKey, RecordNode, Recording, ReplayNode, SubtreeCache,
+};
+use super::nodes::{MemoNode, NodeEval, SharedNode};
+use super::profiler::{NodeProfile, ProfiledNode, Profiler};
+use super::trace::{TraceNode, TracedNode, Tracer};
 
 /// Density function evaluator.
 /// Parses a V2 density graph JSON and evaluates it at (x, y, z) coordinates.
 pub struct DensityEvaluator {
-    root: Box<dyn NodeEval>,
+    /// One root per requested output; index 0 is the primary output.
+    roots: Vec<Box<dyn NodeEval>>,
+    profiler: Option<Arc<Profiler>>,
+    tracer: Option<Arc<Tracer>>,
+    /// Set when built with `from_json_cached`.
+    grid: Option<GridSession>,
 }
 
+/// Grid position and pending recordings of a cached evaluation.
+struct GridSession {
+    cursor: Arc<GridCursor>,
+    recorded: Vec<(CacheKey, Arc<str>, Arc<Recording>)>,
+    hits: usize,
+}
+
+/// Node types that evaluate every input exactly once, at their own position.
+///
+/// Subtrees below these sample the same grid as their parent, so their
+/// arrays can be cached and reused. Anything else (transforms, warps) moves
+/// or resamples its inputs and ends caching for its subtree.
+const GRID_ALIGNED_TYPES: &[&str] = &[
+    "Sum",
+    "Multiplier",
+    "Clamp",
+    "Normalizer",
+    "Exported",
+    "Imported",
+];
+
 impl DensityEvaluator {
     /// Parse a V2 density function JSON into an evaluable graph.
     pub fn from_json(json: &Value) -> Result<Self, String> {
-        let root = parse_node(json)?;
-        Ok(DensityEvaluator { root })
+        Self::from_json_pointers(json, &[String::new()], false)
+    }
+
+    /// Like `from_json`, but fails instead of evaluating as zero where the
+    /// graph can't be evaluated faithfully: imports of exports defined
+    /// elsewhere, and unknown types.
+    pub fn from_json_strict(json: &Value) -> Result<Self, String> {
+        let mut parser = GraphParser::new(json);
+        parser.strict = true;
+        let root = parser.parse_node(json, "")?;
+        Ok(DensityEvaluator {
+            roots: vec![root],
+            profiler: None,
+            tracer: None,
+            grid: None,
+        })
+    }
+
+    /// Parse one or more subtrees of a V2 density graph, each addressed by a
+    /// JSON Pointer ("" is the whole graph), optionally timing every node.
+    ///
+    /// Every subtree becomes a separate output (in the order given), but they
+    /// are parsed against the full graph: `Imported` nodes resolve to exports
+    /// anywhere in it, and node ids stay full-graph pointers. Subtrees that
+    /// overlap, and every export, are shared between outputs and memoized per
+    /// position, so evaluating all outputs at one point computes each once.
+    ///
+    /// With `profile` set, use `profile()` after evaluating to read back the
+    /// per-node cost.
+    pub fn from_json_pointers(
+        json: &Value,
+        pointers: &[String],
+        profile: bool,
+    ) -> Result<Self, String> {
+        let profiler = profile.then(|| Arc::new(Profiler::default()));
+        let mut parser = GraphParser::new(json);
+        parser.profiler = profiler.clone();
+        if pointers.len() > 1 {
+            parser.shared_pointers.extend(pointers.iter().cloned());
+        }
+
+        let mut roots = Vec::with_capacity(pointers.len());
+        for pointer in pointers {
+            let node = json
+                .pointer(pointer)
+                .ok_or_else(|| format!("Pointer '{}' does not resolve to a node", pointer))?;
+            roots.push(parser.parse_node(node, pointer)?);
+        }
+
+        Ok(DensityEvaluator {
+            roots,
+            profiler,
+            tracer: None,
+            grid: None,
+        })
+    }
+
+    /// Like `from_json_pointers`, for evaluating every sample of `grid` in order.
+    ///
+    /// Subtrees whose structure (including resolved imports) already has a
+    /// sample array for this grid in `cache` are not parsed at all and replay
+    /// the cached values; the rest record their values as they are evaluated,
+    /// as long as the recordings fit the cache's budget. `cache` is locked
+    /// only for each lookup. Call `set_sample_index()` before each sample and
+    /// `store_grids()` once the whole grid has been evaluated.
+    pub fn from_json_cached(
+        json: &Value,
+        pointers: &[String],
+        cache: &Mutex<SubtreeCache>,
+        grid: GridKey,
+    ) -> Result<Self, String> {
+        let cursor = Arc::new(GridCursor::default());
+        let mut parser = GraphParser::new(json);
+        parser.grid = Some(GridCaching {
+            budget: cache.lock().unwrap().capacity(),
+            cache,
+            key: grid,
+            cursor: cursor.clone(),
+            recorded: Vec::new(),
+            hits: 0,
+            aligned: true,
+        });
+        if pointers.len() > 1 {
+            parser.shared_pointers.extend(pointers.iter().cloned());
+        }
+
+        let mut roots = Vec::with_capacity(pointers.len());
+        for pointer in pointers {
+            let node = json
+                .pointer(pointer)
+                .ok_or_else(|| format!("Pointer '{}' does not resolve to a node", pointer))?;
+            roots.push(parser.parse_node(node, pointer)?);
+        }
+
+        let caching = parser.grid.take().expect("grid caching");
+        Ok(DensityEvaluator {
+            roots,
+            profiler: None,
+            tracer: None,
+            grid: Some(GridSession {
+                cursor,
+                recorded: caching.recorded,
+                hits: caching.hits,
+            }),
+        })
+    }
+
+    /// Parse a graph with every node instrumented for point probing.
+    ///
+    /// Use `probe()` to evaluate a single position and capture its trace.
+    pub fn from_json_traced(json: &Value) -> Result<Self, String> {
+        let tracer = Arc::new(Tracer::default());
+        let mut parser = GraphParser::new(json);
+        parser.tracer = Some(tracer.clone());
+        let root = parser.parse_node(json, "")?;
+        Ok(DensityEvaluator {
+            roots: vec![root],
+            profiler: None,
+            tracer: Some(tracer),
+            grid: None,
+        })
     }
 
     /// Evaluate the density function at a world position.
     pub fn evaluate(&self, x: f64, y: f64, z: f64) -> f64 {
-        self.root.eval(x, y, z)
+        self.evaluate_output(0, x, y, z)
+    }
+
+    /// Number of outputs this evaluator was built with.
+    pub fn output_count(&self) -> usize {
+        self.roots.len()
+    }
+
+    /// Evaluate one of the outputs requested in `from_json_pointers`.
+    pub fn evaluate_output(&self, index: usize, x: f64, y: f64, z: f64) -> f64 {
+        self.roots[index].eval(x, y, z)
+    }
+
+    /// Evaluate the primary output at a batch of positions.
+    ///
+    /// With grid caching, the lanes must be consecutive samples starting at
+    /// the index passed to `set_sample_index()`.
+    pub fn evaluate_batch(&self, xs: &[f64], ys: &[f64], zs: &[f64], out: &mut [f64]) {
+        self.evaluate_output_batch(0, xs, ys, zs, out);
+    }
+
+    /// Batch counterpart of `evaluate_output`.
+    pub fn evaluate_output_batch(
+        &self,
+        index: usize,
+        xs: &[f64],
+        ys: &[f64],
+        zs: &[f64],
+        out: &mut [f64],
+    ) {
+        self.roots[index].eval_batch(xs, ys, zs, out);
+    }
+
+    /// Evaluate one position and return the value each node produced there.
+    ///
+    /// Returns `None` unless this evaluator was built with `from_json_traced`.
+    pub fn probe(&self, x: f64, y: f64, z: f64) -> Option<TraceNode> {
+        let tracer = self.tracer.as_ref()?;
+        tracer.begin(x, y, z);
+        self.evaluate(x, y, z);
+        tracer.finish()
+    }
+
+    /// Set the row-major index of the grid sample about to be evaluated.
+    pub fn set_sample_index(&self, index: usize) {
+        if let Some(grid) = &self.grid {
+            grid.cursor.set(index);
+        }
+    }
+
+    /// Number of subtrees served from the cache instead of being evaluated.
+    pub fn cache_hits(&self) -> usize {
+        self.grid.as_ref().map_or(0, |g| g.hits)
+    }
+
+    /// Move the sample arrays recorded during a full grid pass into `cache`.
+    pub fn store_grids(&mut self, cache: &Mutex<SubtreeCache>) {
+        let Some(grid) = &mut self.grid else {
+            return;
+        };
+        let recorded: Vec<_> = grid
+            .recorded
+            .drain(..)
+            .map(|(key, subtree, values)| (key, subtree, Arc::new(values.values())))
+            .collect();
+        let mut cache = cache.lock().unwrap();
+        for (key, subtree, values) in recorded {
+            cache.insert(key, subtree, values);
+        }
+    }
+
+    /// Per-node timings, if this evaluator was built with profiling enabled.
+    pub fn profile(&self) -> Option<Vec<NodeProfile>> {
+        self.profiler.as_ref().map(|p| p.report())
     }
 }
 
-/// Recursively parse a JSON node into an evaluable node.
-fn parse_node(json: &Value) -> Result<Box<dyn NodeEval>, String> {
-    let obj = json
-        .as_object()
-        .ok_or("Density node must be a JSON object")?;
+/// Append a reference token to a JSON Pointer, escaping per RFC 6901.
+pub fn child_pointer(path: &str, token: &str) -> String {
+    format!("{}/{}", path, token.replace('~', "~0").replace('/', "~1"))
+}
 
-    let node_type = obj
-        .get("Type")
+/// The `Type` of a JSON node, or "" if it has none.
+fn node_type(json: &Value) -> &str {
+    json.get("Type")
         .and_then(|v| v.as_str())
-        .ok_or("Missing 'Type' field")?;
-
-    match node_type {
-        "Constant" => {
-            let value = obj
-                .get("Value")
-                .and_then(|v| v.as_f64())
-                .unwrap_or(0.0);
-            Ok(Box::new(super::nodes::ConstantNode { value }))
-        }
-
-        "SimplexNoise2D" => {
-            let lacunarity = obj.get("Lacunarity").and_then(|v| v.as_f64()).unwrap_or(2.0);
-            let persistence = obj.get("Persistence").and_then(|v| v.as_f64()).unwrap_or(0.5);
-            let scale = obj.get("Scale").and_then(|v| v.as_f64()).unwrap_or(1.0);
-            let octaves = obj.get("Octaves").and_then(|v| v.as_i64()).unwrap_or(1) as i32;
-            let seed = obj.get("Seed").and_then(|v| v.as_str()).unwrap_or("").to_string();
-            Ok(Box::new(super::nodes::SimplexNoise2DNode::new(
-                lacunarity, persistence, scale, octaves, seed,
-            )))
-        }
-
-        "Sum" => {
-            let inputs = parse_inputs(obj)?;
-            Ok(Box::new(super::nodes::SumNode { inputs }))
-        }
-
-        "Clamp" => {
-            let input = parse_single_input(obj)?;
-            let wall_a = obj.get("WallA").and_then(|v| v.as_f64()).unwrap_or(0.0);
-            let wall_b = obj.get("WallB").and_then(|v| v.as_f64()).unwrap_or(1.0);
-            Ok(Box::new(super::nodes::ClampNode {
-                input,
-                min: wall_a,
-                max: wall_b,
-            }))
-        }
-
-        "Normalizer" => {
-            let input = parse_single_input(obj)?;
-            let from_min = obj.get("FromMin").and_then(|v| v.as_f64()).unwrap_or(-1.0);
-            let from_max = obj.get("FromMax").and_then(|v| v.as_f64()).unwrap_or(1.0);
-            let to_min = obj.get("ToMin").and_then(|v| v.as_f64()).unwrap_or(0.0);
-            let to_max = obj.get("ToMax").and_then(|v| v.as_f64()).unwrap_or(1.0);
-            Ok(Box::new(super::nodes::NormalizerNode {
-                input,
-                from_min,
-                from_max,
-                to_min,
-                to_max,
-            }))
-        }
-
-        _ => {
-            // Unknown types evaluate as zero
-            Ok(Box::new(super::nodes::ConstantNode { value: 0.0 }))
+        .unwrap_or_default()
+}
+
+/// Record the pointer of every node carrying an `ExportAs` name.
+pub fn collect_exports(json: &Value, path: &str, exports: &mut HashMap<String, String>) {
+    match json {
+        Value::Object(obj) => {
+            if let Some(name) = obj.get("ExportAs").and_then(|v| v.as_str()) {
+                if !name.is_empty() {
+                    exports.entry(name.to_string()).or_insert_with(|| path.to_string());
+                }
+            }
+            for (key, val) in obj {
+                collect_exports(val, &child_pointer(path, key), exports);
+            }
+        }
+        Value::Array(arr) => {
+            for (i, val) in arr.iter().enumerate() {
+                collect_exports(val, &child_pointer(path, &i.to_string()), exports);
+            }
         }
+        _ => {}
     }
 }
 
-/// Parse the "Inputs" array from a node object.
-fn parse_inputs(
-    obj: &serde_json::Map<String, Value>,
-) -> Result<Vec<Box<dyn NodeEval>>, String> {
-    let inputs_arr = obj
-        .get("Inputs")
-        .and_then(|v| v.as_array())
-        .cloned()
-        .unwrap_or_default();
-
-    let mut nodes = Vec::new();
-    for input_json in &inputs_arr {
-        nodes.push(parse_node(input_json)?);
-    }
-    Ok(nodes)
+/// Parse-time state for `from_json_cached`.
+struct GridCaching<'a> {
+    cache: &'a Mutex<SubtreeCache>,
+    key: GridKey,
+    cursor: Arc<GridCursor>,
+    recorded: Vec<(CacheKey, Arc<str>, Arc<Recording>)>,
+    /// Bytes still available for recordings; more would not fit the cache.
+    budget: usize,
+    hits: usize,
+    /// False while parsing below a node that is not in `GRID_ALIGNED_TYPES`.
+    aligned: bool,
 }
 
-/// Parse the first element of "Inputs" as a single input.
-fn parse_single_input(
-    obj: &serde_json::Map<String, Value>,
-) -> Result<Box<dyn NodeEval>, String> {
-    let inputs_arr = obj
-        .get("Inputs")
-        .and_then(|v| v.as_array())
-        .cloned()
-        .unwrap_or_default();
-
-    if let Some(first) = inputs_arr.first() {
-        parse_node(first)
-    } else {
-        Ok(Box::new(super::nodes::ConstantNode { value: 0.0 }))
+/// Parse-time state shared across the whole graph.
+struct GraphParser<'a> {
+    /// The full graph, used to resolve `Imported` references.
+    graph: &'a Value,
+    /// Export name -> pointer of the exporting node.
+    exports: HashMap<String, String>,
+    /// Pointers whose nodes are parsed once and shared between all references.
+    shared_pointers: HashSet<String>,
+    /// Shared nodes by pointer and whether grid caching applied to their
+    /// inputs: recordings and replays only hold for grid-aligned positions.
+    shared: HashMap<(String, bool), Arc<MemoNode>>,
+    /// Export names currently being resolved, to detect import cycles.
+    importing: Vec<String>,
+    profiler: Option<Arc<Profiler>>,
+    tracer: Option<Arc<Tracer>>,
+    grid: Option<GridCaching<'a>>,
+    /// Reject nodes that would otherwise evaluate as zero.
+    strict: bool,
+}
+
+impl<'a> GraphParser<'a> {
+    fn new(graph: &'a Value) -> Self {
+        let mut exports = HashMap::new();
+        collect_exports(graph, "", &mut exports);
+        let shared_pointers = exports.values().cloned().collect();
+        GraphParser {
+            graph,
+            exports,
+            shared_pointers,
+            shared: HashMap::new(),
+            importing: Vec::new(),
+            profiler: None,
+            tracer: None,
+            grid: None,
+            strict: false,
+        }
+    }
+
+    /// Parse a JSON node located at `path`, replaying or recording its grid
+    /// samples when grid caching applies to it.
+    fn parse_node(&mut self, json: &Value, path: &str) -> Result<Box<dyn NodeEval>, String> {
+        let node_type = json
+            .get("Type")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        let grid_key = match &self.grid {
+            Some(grid) if grid.aligned && node_type != "Constant" => grid.key,
+            _ => return self.parse_shared(json, path),
+        };
+
+        let subtree = self.canonical(json);
+        let mut hasher = DefaultHasher::new();
+        subtree.hash(&mut hasher);
+        let key = CacheKey {
+            subtree: hasher.finish(),
+            grid: grid_key,
+        };
+        let Some(grid) = self.grid.as_mut() else {
+            return self.parse_shared(json, path);
+        };
+        let cursor = grid.cursor.clone();
+        let cached = grid.cache.lock().unwrap().get(&key, &subtree);
+        if let Some(values) = cached {
+            grid.hits += 1;
+            return Ok(Box::new(ReplayNode { values, cursor }));
+        }
+
+        let bytes = subtree.len() + grid_key.sample_count() * std::mem::size_of::<f64>();
+        if bytes > grid.budget {
+            return self.parse_shared(json, path);
+        }
+        grid.budget -= bytes;
+        let values = Arc::new(Recording::new(grid_key.sample_count()));
+        grid.recorded.push((key, subtree.into(), values.clone()));
+        grid.aligned = GRID_ALIGNED_TYPES.contains(&node_type);
+        let inner = self.parse_shared(json, path);
+        if let Some(grid) = self.grid.as_mut() {
+            grid.aligned = true;
+        }
+
+        Ok(Box::new(RecordNode {
+            inner: inner?,
+            cursor,
+            values,
+        }))
+    }
+
+    /// Canonical text of a subtree's structure, with each `Imported` node
+    /// followed by the export it resolves to.
+    fn canonical(&self, json: &Value) -> String {
+        let mut out = String::new();
+        self.write_canonical(json, &mut Vec::new(), &mut out);
+        out
+    }
+
+    fn write_canonical(&self, json: &Value, importing: &mut Vec<String>, out: &mut String) {
+        match json {
+            Value::Array(arr) => {
+                out.push('[');
+                for (i, item) in arr.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    self.write_canonical(item, importing, out);
+                }
+                out.push(']');
+            }
+            Value::Object(obj) => {
+                out.push('{');
+                for (i, (key, val)) in obj.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    out.push_str(&Value::String(key.clone()).to_string());
+                    out.push(':');
+                    self.write_canonical(val, importing, out);
+                }
+                out.push('}');
+                if obj.get("Type").and_then(|v| v.as_str()) != Some("Imported") {
+                    return;
+                }
+                let name = obj.get("Name").and_then(|v| v.as_str()).unwrap_or("");
+                let target = self
+                    .exports
+                    .get(name)
+                    .and_then(|pointer| self.graph.pointer(pointer));
+                if let Some(target) = target {
+                    if !importing.iter().any(|n| n == name) {
+                        importing.push(name.to_string());
+                        out.push_str("=>");
+                        self.write_canonical(target, importing, out);
+                        importing.pop();
+                    }
+                }
+            }
+            scalar => out.push_str(&scalar.to_string()),
+        }
+    }
+
+    /// Parse a JSON node located at `path`, reusing it if it is shared.
+    fn parse_shared(&mut self, json: &Value, path: &str) -> Result<Box<dyn NodeEval>, String> {
+        if !self.shared_pointers.contains(path) {
+            return self.parse_instrumented(json, path);
+        }
+        let aligned = self.grid.as_ref().is_some_and(|g| g.aligned);
+        let key = (path.to_string(), aligned);
+        let node = match self.shared.get(&key) {
+            Some(node) => node.clone(),
+            None => {
+                let node = Arc::new(MemoNode::new(self.parse_profiled(json, path)?));
+                self.shared.insert(key, node.clone());
+                node
+            }
+        };
+        // Traced outside the memo, so references that replay it still show
+        Ok(self.traced(Box::new(SharedNode(node)), json, path))
+    }
+
+    /// Parse a JSON node located at `path`, applying any instrumentation.
+    fn parse_instrumented(&mut self, json: &Value, path: &str) -> Result<Box<dyn NodeEval>, String> {
+        let node = self.parse_profiled(json, path)?;
+        Ok(self.traced(node, json, path))
+    }
+
+    /// Parse a JSON node, timing its evaluations when profiling without a tracer.
+    fn parse_profiled(&mut self, json: &Value, path: &str) -> Result<Box<dyn NodeEval>, String> {
+        let profiler = match (&self.tracer, &self.profiler) {
+            (None, Some(profiler)) => profiler.clone(),
+            _ => return self.parse_node_inner(json, path),
+        };
+
+        let stats = profiler.register(path, node_type(json));
+        let inner = self.parse_node_inner(json, path);
+
+        Ok(Box::new(ProfiledNode {
+            inner: inner?,
+            stats,
+        }))
+    }
+
+    /// Wrap `node` to record its evaluations when tracing.
+    fn traced(&self, node: Box<dyn NodeEval>, json: &Value, path: &str) -> Box<dyn NodeEval> {
+        match self.tracer.clone() {
+            Some(tracer) => Box::new(TracedNode {
+                inner: node,
+                pointer: path.to_string(),
+                node_type: node_type(json).to_string(),
+                tracer,
+            }),
+            None => node,
+        }
+    }
+
+    /// Recursively parse a JSON node into an evaluable node.
+    fn parse_node_inner(&mut self, json: &Value, path: &str) -> Result<Box<dyn NodeEval>, String> {
+        let obj = json
+            .as_object()
+            .ok_or("Density node must be a JSON object")?;
+
+        let node_type = obj
+            .get("Type")
+            .and_then(|v| v.as_str())
+            .ok_or("Missing 'Type' field")?;
+
+        match node_type {
+            "Constant" => {
+                let value = obj
+                    .get("Value")
+                    .and_then(|v| v.as_f64())
+                    .unwrap_or(0.0);
+                Ok(Box::new(super::nodes::ConstantNode { value }))
+            }
+
+            "SimplexNoise2D" => {
+                let lacunarity = obj.get("Lacunarity").and_then(|v| v.as_f64()).unwrap_or(2.0);
+                let persistence = obj.get("Persistence").and_then(|v| v.as_f64()).unwrap_or(0.5);
+                let scale = obj.get("Scale").and_then(|v| v.as_f64()).unwrap_or(1.0);
+                let octaves = obj.get("Octaves").and_then(|v| v.as_i64()).unwrap_or(1) as i32;
+                let seed = obj.get("Seed").and_then(|v| v.as_str()).unwrap_or("").to_string();
+                Ok(Box::new(super::nodes::SimplexNoise2DNode::new(
+                    lacunarity, persistence, scale, octaves, seed,
+                )))
+            }
+
+            "Sum" => {
+                let inputs = self.parse_inputs(obj, path)?;
+                Ok(Box::new(super::nodes::SumNode { inputs }))
+            }
+
+            "Multiplier" => {
+                let inputs = self.parse_inputs(obj, path)?;
+                Ok(Box::new(super::nodes::MultiplierNode { inputs }))
+            }
+
+            "Clamp" => {
+                let input = self.parse_single_input(obj, path)?;
+                let wall_a = obj.get("WallA").and_then(|v| v.as_f64()).unwrap_or(0.0);
+                let wall_b = obj.get("WallB").and_then(|v| v.as_f64()).unwrap_or(1.0);
+                Ok(Box::new(super::nodes::ClampNode {
+                    input,
+                    min: wall_a,
+                    max: wall_b,
+                }))
+            }
+
+            "Normalizer" => {
+                let input = self.parse_single_input(obj, path)?;
+                let from_min = obj.get("FromMin").and_then(|v| v.as_f64()).unwrap_or(-1.0);
+                let from_max = obj.get("FromMax").and_then(|v| v.as_f64()).unwrap_or(1.0);
+                let to_min = obj.get("ToMin").and_then(|v| v.as_f64()).unwrap_or(0.0);
+                let to_max = obj.get("ToMax").and_then(|v| v.as_f64()).unwrap_or(1.0);
+                Ok(Box::new(super::nodes::NormalizerNode {
+                    input,
+                    from_min,
+                    from_max,
+                    to_min,
+                    to_max,
+                }))
+            }
+
+            "Scale" => {
+                let input = self.parse_single_input(obj, path)?;
+                let axis = |key: &str| {
+                    obj.get(key)
+                        .and_then(|v| v.as_f64())
+                        .filter(|v| *v != 0.0)
+                        .unwrap_or(1.0)
+                };
+                Ok(Box::new(super::nodes::ScaleNode {
+                    input,
+                    x: axis("X"),
+                    y: axis("Y"),
+                    z: axis("Z"),
+                }))
+            }
+
+            "Slider" => {
+                let input = self.parse_single_input(obj, path)?;
+                Ok(Box::new(super::nodes::SliderNode {
+                    input,
+                    slide_x: obj.get("SlideX").and_then(|v| v.as_f64()).unwrap_or(0.0),
+                    slide_y: obj.get("SlideY").and_then(|v| v.as_f64()).unwrap_or(0.0),
+                    slide_z: obj.get("SlideZ").and_then(|v| v.as_f64()).unwrap_or(0.0),
+                }))
+            }
+
+            "GradientWarp" => {
+                let mut inputs = self.parse_inputs(obj, path)?.into_iter();
+                let input = inputs
+                    .next()
+                    .unwrap_or_else(|| Box::new(super::nodes::ConstantNode { value: 0.0 }));
+                let warp = inputs
+                    .next()
+                    .unwrap_or_else(|| Box::new(super::nodes::ConstantNode { value: 0.0 }));
+                let sample_range = obj.get("SampleRange").and_then(|v| v.as_f64()).unwrap_or(1.0);
+                Ok(Box::new(super::nodes::GradientWarpNode {
+                    input,
+                    warp,
+                    sample_range: if sample_range > 0.0 { sample_range } else { 1.0 },
+                    warp_factor: obj.get("WarpFactor").and_then(|v| v.as_f64()).unwrap_or(1.0),
+                    is_2d: obj.get("2D").and_then(|v| v.as_bool()).unwrap_or(false),
+                    y_for_2d: obj.get("YFor2D").and_then(|v| v.as_f64()).unwrap_or(0.0),
+                }))
+            }
+
+            "Exported" => self.parse_single_input(obj, path),
+
+            "Imported" => {
+                let name = obj.get("Name").and_then(|v| v.as_str()).unwrap_or("");
+                let Some(pointer) = self.exports.get(name).cloned() else {
+                    if self.strict {
+                        return Err(format!("Export '{}' is not defined in this graph", name));
+                    }
+                    // Exports from other files are not visible here; treat as zero
+                    return Ok(Box::new(super::nodes::ConstantNode { value: 0.0 }));
+                };
+                if self.importing.iter().any(|n| n == name) {
+                    return Err(format!("Import cycle through '{}'", name));
+                }
+                let graph = self.graph;
+                let target = graph
+                    .pointer(&pointer)
+                    .ok_or_else(|| format!("Export '{}' does not resolve to a node", name))?;
+
+                self.importing.push(name.to_string());
+                let node = self.parse_node(target, &pointer);
+                self.importing.pop();
+                node
+            }
+
+            _ if self.strict => Err(format!("Unknown density type '{}'", node_type)),
+            _ => {
+                // Unknown types evaluate as zero
+                Ok(Box::new(super::nodes::ConstantNode { value: 0.0 }))
+            }
+        }
+    }
+
+    /// Parse the "Inputs" array from a node object.
+    fn parse_inputs(
+        &mut self,
+        obj: &serde_json::Map<String, Value>,
+        path: &str,
+    ) -> Result<Vec<Box<dyn NodeEval>>, String> {
+        let inputs_arr = obj
+            .get("Inputs")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        let inputs_path = child_pointer(path, "Inputs");
+        let mut nodes = Vec::new();
+        for (i, input_json) in inputs_arr.iter().enumerate() {
+            nodes.push(self.parse_node(input_json, &child_pointer(&inputs_path, &i.to_string()))?);
+        }
+        Ok(nodes)
+    }
+
+    /// Parse the single "Input" of a node, falling back to the first element
+    /// of "Inputs" as the editor writes it.
+    fn parse_single_input(
+        &mut self,
+        obj: &serde_json::Map<String, Value>,
+        path: &str,
+    ) -> Result<Box<dyn NodeEval>, String> {
+        if let Some(input) = obj.get("Input").filter(|v| v.is_object()) {
+            return self.parse_node(input, &child_pointer(path, "Input"));
+        }
+        let inputs_arr = obj
+            .get("Inputs")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        if let Some(first) = inputs_arr.first() {
+            self.parse_node(first, &child_pointer(&child_pointer(path, "Inputs"), "0"))
+        } else {
+            Ok(Box::new(super::nodes::ConstantNode { value: 0.0 }))
+        }
     }
 }
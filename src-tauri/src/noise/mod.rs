@@ -1,2 +1,9 @@
+pub mod bands;
+pub mod cache;
 pub mod evaluator;
 pub mod nodes;
+pub mod profiler;
+pub mod ranges;
+pub mod trace;
+#[cfg(test)]
+mod tests;
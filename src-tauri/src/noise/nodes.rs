@@ -1,8 +1,19 @@
 use fastnoise_lite::FastNoiseLite;
+use std::sync::{Arc, Mutex};
 
 /// Trait for evaluable density function nodes.
 pub trait NodeEval: Send + Sync {
     fn eval(&self, x: f64, y: f64, z: f64) -> f64;
+
+    /// Evaluate a batch of positions, writing one value per lane into `out`.
+    ///
+    /// All slices have the same length. The default evaluates lane by lane;
+    /// nodes override it to work on whole lanes at once.
+    fn eval_batch(&self, xs: &[f64], ys: &[f64], zs: &[f64], out: &mut [f64]) {
+        for (i, value) in out.iter_mut().enumerate() {
+            *value = self.eval(xs[i], ys[i], zs[i]);
+        }
+    }
 }
 
 /// Constant value node.
@@ -14,6 +25,10 @@ impl NodeEval for ConstantNode {
     fn eval(&self, _x: f64, _y: f64, _z: f64) -> f64 {
         self.value
     }
+
+    fn eval_batch(&self, _xs: &[f64], _ys: &[f64], _zs: &[f64], out: &mut [f64]) {
+        out.fill(self.value);
+    }
 }
 
 /// SimplexNoise2D node using fastnoise-lite.
@@ -56,6 +71,32 @@ impl SimplexNoise2DNode {
 }
 
 impl NodeEval for SimplexNoise2DNode {
+    fn eval_batch(&self, xs: &[f64], _ys: &[f64], zs: &[f64], out: &mut [f64]) {
+        // Octave parameters are the same for every lane, so walk octaves in
+        // the outer loop and accumulate all lanes per octave.
+        out.fill(0.0);
+        let mut amplitude = 1.0;
+        let mut frequency = 1.0 / self.scale.max(0.001);
+        let mut max_amp = 0.0;
+
+        for _ in 0..self.octaves {
+            for (i, value) in out.iter_mut().enumerate() {
+                let nx = (xs[i] * frequency) as f32;
+                let nz = (zs[i] * frequency) as f32;
+                *value += self.noise.get_noise_2d(nx, nz) as f64 * amplitude;
+            }
+            max_amp += amplitude;
+            amplitude *= self.persistence;
+            frequency *= self.lacunarity;
+        }
+
+        if max_amp > 0.0 {
+            out.iter_mut().for_each(|v| *v /= max_amp);
+        } else {
+            out.fill(0.0);
+        }
+    }
+
     fn eval(&self, x: f64, _y: f64, z: f64) -> f64 {
         let mut value = 0.0;
         let mut amplitude = 1.0;
@@ -88,6 +129,52 @@ impl NodeEval for SumNode {
     fn eval(&self, x: f64, y: f64, z: f64) -> f64 {
         self.inputs.iter().map(|input| input.eval(x, y, z)).sum()
     }
+
+    fn eval_batch(&self, xs: &[f64], ys: &[f64], zs: &[f64], out: &mut [f64]) {
+        fold_inputs(&self.inputs, 0.0, xs, ys, zs, out, |acc, v| acc + v);
+    }
+}
+
+/// Product of multiple inputs.
+pub struct MultiplierNode {
+    pub inputs: Vec<Box<dyn NodeEval>>,
+}
+
+impl NodeEval for MultiplierNode {
+    fn eval(&self, x: f64, y: f64, z: f64) -> f64 {
+        self.inputs.iter().map(|input| input.eval(x, y, z)).product()
+    }
+
+    fn eval_batch(&self, xs: &[f64], ys: &[f64], zs: &[f64], out: &mut [f64]) {
+        fold_inputs(&self.inputs, 1.0, xs, ys, zs, out, |acc, v| acc * v);
+    }
+}
+
+/// Combine every input's batch into `out` lane-wise, starting from `identity`.
+fn fold_inputs(
+    inputs: &[Box<dyn NodeEval>],
+    identity: f64,
+    xs: &[f64],
+    ys: &[f64],
+    zs: &[f64],
+    out: &mut [f64],
+    op: impl Fn(f64, f64) -> f64,
+) {
+    let Some((first, rest)) = inputs.split_first() else {
+        out.fill(identity);
+        return;
+    };
+    first.eval_batch(xs, ys, zs, out);
+    if rest.is_empty() {
+        return;
+    }
+    let mut scratch = vec![0.0; out.len()];
+    for input in rest {
+        input.eval_batch(xs, ys, zs, &mut scratch);
+        for (acc, v) in out.iter_mut().zip(&scratch) {
+            *acc = op(*acc, *v);
+        }
+    }
 }
 
 /// Clamp node: clamps input between min and max.
@@ -101,6 +188,11 @@ impl NodeEval for ClampNode {
     fn eval(&self, x: f64, y: f64, z: f64) -> f64 {
         self.input.eval(x, y, z).clamp(self.min, self.max)
     }
+
+    fn eval_batch(&self, xs: &[f64], ys: &[f64], zs: &[f64], out: &mut [f64]) {
+        self.input.eval_batch(xs, ys, zs, out);
+        out.iter_mut().for_each(|v| *v = v.clamp(self.min, self.max));
+    }
 }
 
 /// Normalizer node: remaps input from source range to target range.
@@ -122,4 +214,204 @@ impl NodeEval for NormalizerNode {
         let normalized = (val - self.from_min) / from_range;
         self.to_min + normalized * (self.to_max - self.to_min)
     }
+
+    fn eval_batch(&self, xs: &[f64], ys: &[f64], zs: &[f64], out: &mut [f64]) {
+        self.input.eval_batch(xs, ys, zs, out);
+        let from_range = self.from_max - self.from_min;
+        if from_range.abs() < f64::EPSILON {
+            out.fill(self.to_min);
+            return;
+        }
+        for v in out.iter_mut() {
+            let normalized = (*v - self.from_min) / from_range;
+            *v = self.to_min + normalized * (self.to_max - self.to_min);
+        }
+    }
+}
+
+/// Scale node: stretches the input field by sampling it at divided coordinates.
+pub struct ScaleNode {
+    pub input: Box<dyn NodeEval>,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl NodeEval for ScaleNode {
+    fn eval(&self, x: f64, y: f64, z: f64) -> f64 {
+        self.input.eval(x / self.x, y / self.y, z / self.z)
+    }
+
+    fn eval_batch(&self, xs: &[f64], ys: &[f64], zs: &[f64], out: &mut [f64]) {
+        let sx: Vec<f64> = xs.iter().map(|x| x / self.x).collect();
+        let sy: Vec<f64> = ys.iter().map(|y| y / self.y).collect();
+        let sz: Vec<f64> = zs.iter().map(|z| z / self.z).collect();
+        self.input.eval_batch(&sx, &sy, &sz, out);
+    }
+}
+
+/// Slider node: translates the input field by a fixed vector.
+pub struct SliderNode {
+    pub input: Box<dyn NodeEval>,
+    pub slide_x: f64,
+    pub slide_y: f64,
+    pub slide_z: f64,
+}
+
+impl NodeEval for SliderNode {
+    fn eval(&self, x: f64, y: f64, z: f64) -> f64 {
+        self.input.eval(x - self.slide_x, y - self.slide_y, z - self.slide_z)
+    }
+
+    fn eval_batch(&self, xs: &[f64], ys: &[f64], zs: &[f64], out: &mut [f64]) {
+        let sx: Vec<f64> = xs.iter().map(|x| x - self.slide_x).collect();
+        let sy: Vec<f64> = ys.iter().map(|y| y - self.slide_y).collect();
+        let sz: Vec<f64> = zs.iter().map(|z| z - self.slide_z).collect();
+        self.input.eval_batch(&sx, &sy, &sz, out);
+    }
+}
+
+/// GradientWarp node: displaces the input along the gradient of a warp field.
+pub struct GradientWarpNode {
+    pub input: Box<dyn NodeEval>,
+    pub warp: Box<dyn NodeEval>,
+    pub sample_range: f64,
+    pub warp_factor: f64,
+    pub is_2d: bool,
+    pub y_for_2d: f64,
+}
+
+impl NodeEval for GradientWarpNode {
+    fn eval(&self, x: f64, y: f64, z: f64) -> f64 {
+        let eps = self.sample_range;
+        let inv_2e = 1.0 / (2.0 * eps);
+        let sample_y = if self.is_2d { self.y_for_2d } else { y };
+
+        let dfdx = (self.warp.eval(x + eps, sample_y, z) - self.warp.eval(x - eps, sample_y, z)) * inv_2e;
+        let dfdz = (self.warp.eval(x, sample_y, z + eps) - self.warp.eval(x, sample_y, z - eps)) * inv_2e;
+        let wy = if self.is_2d {
+            y
+        } else {
+            let dfdy = (self.warp.eval(x, sample_y + eps, z) - self.warp.eval(x, sample_y - eps, z)) * inv_2e;
+            y + self.warp_factor * dfdy
+        };
+
+        self.input.eval(x + self.warp_factor * dfdx, wy, z + self.warp_factor * dfdz)
+    }
+
+    fn eval_batch(&self, xs: &[f64], ys: &[f64], zs: &[f64], out: &mut [f64]) {
+        let len = out.len();
+        let eps = self.sample_range;
+        let inv_2e = 1.0 / (2.0 * eps);
+        let sample_ys: Vec<f64> = if self.is_2d {
+            vec![self.y_for_2d; len]
+        } else {
+            ys.to_vec()
+        };
+        let offset = |coords: &[f64], d: f64| -> Vec<f64> { coords.iter().map(|c| c + d).collect() };
+
+        // Central difference of the warp field along one axis, per lane.
+        let mut hi = vec![0.0; len];
+        let mut lo = vec![0.0; len];
+        let mut gradient = |axis: usize| -> Vec<f64> {
+            match axis {
+                0 => {
+                    self.warp.eval_batch(&offset(xs, eps), &sample_ys, zs, &mut hi);
+                    self.warp.eval_batch(&offset(xs, -eps), &sample_ys, zs, &mut lo);
+                }
+                1 => {
+                    self.warp.eval_batch(xs, &offset(&sample_ys, eps), zs, &mut hi);
+                    self.warp.eval_batch(xs, &offset(&sample_ys, -eps), zs, &mut lo);
+                }
+                _ => {
+                    self.warp.eval_batch(xs, &sample_ys, &offset(zs, eps), &mut hi);
+                    self.warp.eval_batch(xs, &sample_ys, &offset(zs, -eps), &mut lo);
+                }
+            }
+            hi.iter().zip(&lo).map(|(h, l)| (h - l) * inv_2e).collect()
+        };
+
+        let dfdx = gradient(0);
+        let dfdz = gradient(2);
+        let wys: Vec<f64> = if self.is_2d {
+            ys.to_vec()
+        } else {
+            let dfdy = gradient(1);
+            ys.iter().zip(&dfdy).map(|(y, d)| y + self.warp_factor * d).collect()
+        };
+        let wxs: Vec<f64> = xs.iter().zip(&dfdx).map(|(x, d)| x + self.warp_factor * d).collect();
+        let wzs: Vec<f64> = zs.iter().zip(&dfdz).map(|(z, d)| z + self.warp_factor * d).collect();
+
+        self.input.eval_batch(&wxs, &wys, &wzs, out);
+    }
+}
+
+/// Memoizes the most recent evaluation of a subtree.
+///
+/// Shared subtrees (exports and overlapping preview outputs) are reached from
+/// several parents at the same position; this computes them once per point.
+pub struct MemoNode {
+    inner: Box<dyn NodeEval>,
+    last: Mutex<Option<([f64; 3], f64)>>,
+    last_batch: Mutex<Option<BatchMemo>>,
+}
+
+/// Positions and results of the most recent batch.
+struct BatchMemo {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    zs: Vec<f64>,
+    out: Vec<f64>,
+}
+
+impl MemoNode {
+    pub fn new(inner: Box<dyn NodeEval>) -> Self {
+        MemoNode {
+            inner,
+            last: Mutex::new(None),
+            last_batch: Mutex::new(None),
+        }
+    }
+}
+
+impl NodeEval for MemoNode {
+    fn eval(&self, x: f64, y: f64, z: f64) -> f64 {
+        if let Some((pos, value)) = *self.last.lock().unwrap() {
+            if pos == [x, y, z] {
+                return value;
+            }
+        }
+        let value = self.inner.eval(x, y, z);
+        *self.last.lock().unwrap() = Some(([x, y, z], value));
+        value
+    }
+
+    fn eval_batch(&self, xs: &[f64], ys: &[f64], zs: &[f64], out: &mut [f64]) {
+        if let Some(memo) = &*self.last_batch.lock().unwrap() {
+            if memo.xs == xs && memo.ys == ys && memo.zs == zs {
+                out.copy_from_slice(&memo.out);
+                return;
+            }
+        }
+        self.inner.eval_batch(xs, ys, zs, out);
+        *self.last_batch.lock().unwrap() = Some(BatchMemo {
+            xs: xs.to_vec(),
+            ys: ys.to_vec(),
+            zs: zs.to_vec(),
+            out: out.to_vec(),
+        });
+    }
+}
+
+/// A reference to a shared, memoized subtree.
+pub struct SharedNode(pub Arc<MemoNode>);
+
+impl NodeEval for SharedNode {
+    fn eval(&self, x: f64, y: f64, z: f64) -> f64 {
+        self.0.eval(x, y, z)
+    }
+
+    fn eval_batch(&self, xs: &[f64], ys: &[f64], zs: &[f64], out: &mut [f64]) {
+        self.0.eval_batch(xs, ys, zs, out)
+    }
 }
@@ -0,0 +1,141 @@
+use serde::Serialize;
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use super::nodes::NodeEval;
+
+/// Call count and accumulated wall time for a single graph node.
+#[derive(Default)]
+pub struct NodeStats {
+    calls: AtomicU64,
+    nanos: AtomicU64,
+    /// Time spent in profiled nodes called from this one.
+    child_nanos: AtomicU64,
+}
+
+struct ProfileEntry {
+    id: String,
+    node_type: String,
+    stats: Arc<NodeStats>,
+}
+
+thread_local! {
+    /// Stats of the profiled nodes being evaluated on this thread, innermost last.
+    static ACTIVE: RefCell<Vec<Arc<NodeStats>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Collects per-node timings for one parsed density graph.
+///
+/// Nodes are registered while the graph is parsed; each one is identified by
+/// its JSON Pointer into the source graph so the editor can map results back
+/// onto the canvas.
+///
+/// Self time is measured at evaluation, as a node's time minus the time of
+/// the profiled nodes it actually called. A shared export is timed once
+/// per position, under whichever consumer evaluated it first; later
+/// consumers read the memoized value and are charged only the lookup. The
+/// export's `calls` therefore counts evaluations, not references.
+#[derive(Default)]
+pub struct Profiler {
+    entries: Mutex<Vec<ProfileEntry>>,
+}
+
+/// Profiling result for a single node.
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeProfile {
+    /// JSON Pointer of the node within the evaluated graph ("" is the root).
+    pub id: String,
+    pub node_type: String,
+    pub calls: u64,
+    /// Time spent in this node including its inputs, in milliseconds.
+    pub total_ms: f64,
+    /// Time spent in this node excluding its inputs, in milliseconds.
+    pub self_ms: f64,
+    /// Fraction (0..1) of the graph's total self time spent in this node.
+    pub share: f64,
+}
+
+impl Profiler {
+    /// Register a node and return the stats it should record into.
+    pub fn register(&self, id: &str, node_type: &str) -> Arc<NodeStats> {
+        let stats = Arc::new(NodeStats::default());
+        self.entries.lock().unwrap().push(ProfileEntry {
+            id: id.to_string(),
+            node_type: node_type.to_string(),
+            stats: stats.clone(),
+        });
+        stats
+    }
+
+    /// Snapshot the collected timings, in graph parse order.
+    pub fn report(&self) -> Vec<NodeProfile> {
+        let entries = self.entries.lock().unwrap();
+
+        let totals: Vec<u64> = entries
+            .iter()
+            .map(|e| e.stats.nanos.load(Ordering::Relaxed))
+            .collect();
+        let self_nanos: Vec<u64> = entries
+            .iter()
+            .zip(&totals)
+            .map(|(e, total)| total.saturating_sub(e.stats.child_nanos.load(Ordering::Relaxed)))
+            .collect();
+        let grand_total: u64 = self_nanos.iter().sum();
+
+        entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| NodeProfile {
+                id: entry.id.clone(),
+                node_type: entry.node_type.clone(),
+                calls: entry.stats.calls.load(Ordering::Relaxed),
+                total_ms: totals[i] as f64 / 1_000_000.0,
+                self_ms: self_nanos[i] as f64 / 1_000_000.0,
+                share: if grand_total > 0 {
+                    self_nanos[i] as f64 / grand_total as f64
+                } else {
+                    0.0
+                },
+            })
+            .collect()
+    }
+}
+
+/// Wraps a node and records every call into its `NodeStats`.
+pub struct ProfiledNode {
+    pub inner: Box<dyn NodeEval>,
+    pub stats: Arc<NodeStats>,
+}
+
+impl ProfiledNode {
+    /// Run `eval` as `calls` calls of this node, charging its time to the
+    /// calling profiled node as child time.
+    fn timed<T>(&self, calls: u64, eval: impl FnOnce() -> T) -> T {
+        ACTIVE.with(|active| active.borrow_mut().push(self.stats.clone()));
+        let start = Instant::now();
+        let value = eval();
+        let elapsed = start.elapsed().as_nanos() as u64;
+        ACTIVE.with(|active| {
+            let mut active = active.borrow_mut();
+            active.pop();
+            if let Some(caller) = active.last() {
+                caller.child_nanos.fetch_add(elapsed, Ordering::Relaxed);
+            }
+        });
+        self.stats.calls.fetch_add(calls, Ordering::Relaxed);
+        self.stats.nanos.fetch_add(elapsed, Ordering::Relaxed);
+        value
+    }
+}
+
+impl NodeEval for ProfiledNode {
+    fn eval(&self, x: f64, y: f64, z: f64) -> f64 {
+        self.timed(1, || self.inner.eval(x, y, z))
+    }
+
+    fn eval_batch(&self, xs: &[f64], ys: &[f64], zs: &[f64], out: &mut [f64]) {
+        self.timed(out.len() as u64, || self.inner.eval_batch(xs, ys, zs, out))
+    }
+}
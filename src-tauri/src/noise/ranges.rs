@@ -0,0 +1,454 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+use super::evaluator::{child_pointer, collect_exports};
+
+/// A closed interval of possible density values.
+///
+/// Unbounded ends are `±inf`, which serialize as `null`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Interval {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Interval {
+    pub const UNBOUNDED: Interval = Interval {
+        min: f64::NEG_INFINITY,
+        max: f64::INFINITY,
+    };
+
+    pub fn new(a: f64, b: f64) -> Self {
+        Interval {
+            min: a.min(b),
+            max: a.max(b),
+        }
+    }
+
+    pub fn point(v: f64) -> Self {
+        Interval { min: v, max: v }
+    }
+
+    pub fn is_bounded(&self) -> bool {
+        self.min.is_finite() && self.max.is_finite()
+    }
+
+    pub fn union(self, other: Interval) -> Self {
+        Interval {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    pub fn add(self, other: Interval) -> Self {
+        Interval::new(self.min + other.min, self.max + other.max)
+    }
+
+    pub fn mul(self, other: Interval) -> Self {
+        let products = [
+            self.min * other.min,
+            self.min * other.max,
+            self.max * other.min,
+            self.max * other.max,
+        ];
+        // 0 * inf is NaN; such a product can take any value
+        if products.iter().any(|p| p.is_nan()) {
+            return Interval::UNBOUNDED;
+        }
+        let min = products.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = products.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        Interval { min, max }
+    }
+
+    pub fn scale(self, factor: f64) -> Self {
+        // Exactly zero even for unbounded inputs, where `mul` sees 0 * inf
+        if factor == 0.0 {
+            return Interval::point(0.0);
+        }
+        self.mul(Interval::point(factor))
+    }
+
+    pub fn abs(self) -> Self {
+        if self.min >= 0.0 {
+            self
+        } else if self.max <= 0.0 {
+            Interval::new(-self.max, -self.min)
+        } else {
+            Interval::new(0.0, self.max.max(-self.min))
+        }
+    }
+
+    /// Image of a monotonically non-decreasing function.
+    pub fn map_monotonic(self, f: impl Fn(f64) -> f64) -> Self {
+        Interval::new(f(self.min), f(self.max))
+    }
+}
+
+/// Kind of issue found by range analysis.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum RangeFindingKind {
+    /// The node never changes its input (e.g. a Clamp whose walls are never reached).
+    NoEffect,
+    /// The node always produces the same value regardless of its input.
+    ConstantOutput,
+    /// An input can never influence the output.
+    DeadInput,
+    /// A Normalizer's FromMin/FromMax do not match its input's range.
+    NormalizerMismatch,
+}
+
+/// A single range analysis finding, anchored to a node.
+#[derive(Debug, Clone, Serialize)]
+pub struct RangeFinding {
+    /// JSON Pointer of the node the finding is about.
+    pub pointer: String,
+    pub kind: RangeFindingKind,
+    pub message: String,
+}
+
+/// Propagated ranges for one node.
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeRange {
+    /// JSON Pointer of the node within the analyzed graph ("" is the root).
+    pub pointer: String,
+    pub node_type: String,
+    /// Range of each input, in input order.
+    pub inputs: Vec<Interval>,
+    pub output: Interval,
+}
+
+/// Result of statically analyzing a density graph.
+#[derive(Debug, Clone, Serialize)]
+pub struct RangeAnalysis {
+    /// Output range of the whole graph.
+    pub output: Interval,
+    /// Every analyzed node, in depth-first order.
+    pub nodes: Vec<NodeRange>,
+    pub findings: Vec<RangeFinding>,
+}
+
+/// Statically propagate output intervals through a density graph.
+///
+/// Noise generators use their documented bounds and arithmetic, clamping and
+/// normalization nodes are treated exactly. Coordinate transforms, warps and
+/// caches pass their input range through. Anything whose range cannot be
+/// known statically (coordinates, curves, unknown types) is unbounded.
+pub fn analyze(graph: &Value) -> RangeAnalysis {
+    let mut exports = HashMap::new();
+    collect_exports(graph, "", &mut exports);
+
+    let mut analyzer = RangeAnalyzer {
+        graph,
+        exports,
+        import_ranges: HashMap::new(),
+        importing: Vec::new(),
+        nodes: Vec::new(),
+        findings: Vec::new(),
+    };
+    let output = analyzer.analyze_node(graph, "");
+
+    RangeAnalysis {
+        output,
+        nodes: analyzer.nodes,
+        findings: analyzer.findings,
+    }
+}
+
+/// Relative tolerance when comparing a Normalizer's source range to its input.
+const NORMALIZER_TOLERANCE: f64 = 1e-6;
+
+struct RangeAnalyzer<'a> {
+    graph: &'a Value,
+    exports: HashMap<String, String>,
+    import_ranges: HashMap<String, Interval>,
+    importing: Vec<String>,
+    nodes: Vec<NodeRange>,
+    findings: Vec<RangeFinding>,
+}
+
+impl RangeAnalyzer<'_> {
+    fn analyze_node(&mut self, json: &Value, path: &str) -> Interval {
+        let Some(obj) = json.as_object() else {
+            return Interval::UNBOUNDED;
+        };
+        let node_type = obj.get("Type").and_then(|v| v.as_str()).unwrap_or_default();
+
+        // Reserve our slot so nodes are listed parent-first
+        let index = self.nodes.len();
+        self.nodes.push(NodeRange {
+            pointer: path.to_string(),
+            node_type: node_type.to_string(),
+            inputs: Vec::new(),
+            output: Interval::UNBOUNDED,
+        });
+
+        let inputs = self.analyze_inputs(obj, path);
+        let output = self.node_output(obj, node_type, path, &inputs);
+
+        self.nodes[index].inputs = inputs;
+        self.nodes[index].output = output;
+        output
+    }
+
+    /// Analyze the node's density inputs ("Inputs" array, or a single "Input").
+    fn analyze_inputs(&mut self, obj: &Map<String, Value>, path: &str) -> Vec<Interval> {
+        if let Some(arr) = obj.get("Inputs").and_then(|v| v.as_array()) {
+            let inputs_path = child_pointer(path, "Inputs");
+            return arr
+                .iter()
+                .enumerate()
+                .map(|(i, input)| {
+                    self.analyze_node(input, &child_pointer(&inputs_path, &i.to_string()))
+                })
+                .collect();
+        }
+        if let Some(input) = obj.get("Input").filter(|v| v.is_object()) {
+            return vec![self.analyze_node(input, &child_pointer(path, "Input"))];
+        }
+        Vec::new()
+    }
+
+    fn node_output(
+        &mut self,
+        obj: &Map<String, Value>,
+        node_type: &str,
+        path: &str,
+        inputs: &[Interval],
+    ) -> Interval {
+        let num =
+            |key: &str, default: f64| obj.get(key).and_then(|v| v.as_f64()).unwrap_or(default);
+        // Missing inputs evaluate as zero
+        let first = inputs.first().copied().unwrap_or(Interval::point(0.0));
+
+        match node_type {
+            "Constant" => Interval::point(num("Value", 0.0)),
+
+            "SimplexNoise2D" | "SimplexNoise3D" => Interval::new(-1.0, 1.0),
+
+            "Sum" => inputs
+                .iter()
+                .fold(Interval::point(0.0), |acc, i| acc.add(*i)),
+
+            "Multiplier" => {
+                if inputs.iter().any(|i| *i == Interval::point(0.0)) {
+                    self.finding(
+                        path,
+                        RangeFindingKind::ConstantOutput,
+                        "An input is always zero, so the product is always zero".to_string(),
+                    );
+                    return Interval::point(0.0);
+                }
+                inputs
+                    .iter()
+                    .fold(Interval::point(1.0), |acc, i| acc.mul(*i))
+            }
+
+            "Abs" => first.abs(),
+            "Inverter" => first.scale(-1.0),
+            "Sqrt" => first.abs().map_monotonic(f64::sqrt),
+            "Pow" => {
+                let exponent = num("Exponent", 2.0);
+                if exponent > 0.0 {
+                    first.map_monotonic(|v| v.abs().powf(exponent) * v.signum())
+                } else {
+                    Interval::UNBOUNDED
+                }
+            }
+            "OffsetConstant" => first.add(Interval::point(num("Offset", 0.0))),
+            "AmplitudeConstant" => first.scale(num("Amplitude", 1.0)),
+
+            "Clamp" | "SmoothClamp" => {
+                let walls = Interval::new(num("WallA", 0.0), num("WallB", 1.0));
+                self.check_limits(path, first, Some(walls.min), Some(walls.max))
+            }
+            "Floor" | "SmoothFloor" => {
+                let floor = num("Floor", 0.0);
+                self.check_limits(path, first, Some(floor), None)
+            }
+            "Ceiling" | "SmoothCeiling" => {
+                let ceiling = num("Ceiling", 0.0);
+                self.check_limits(path, first, None, Some(ceiling))
+            }
+
+            "Min" | "SmoothMin" => {
+                self.check_dominated(path, inputs, true);
+                inputs
+                    .iter()
+                    .copied()
+                    .reduce(|a, b| Interval::new(a.min.min(b.min), a.max.min(b.max)))
+                    .unwrap_or(Interval::point(0.0))
+            }
+            "Max" | "SmoothMax" => {
+                self.check_dominated(path, inputs, false);
+                inputs
+                    .iter()
+                    .copied()
+                    .reduce(|a, b| Interval::new(a.min.max(b.min), a.max.max(b.max)))
+                    .unwrap_or(Interval::point(0.0))
+            }
+
+            "Normalizer" => {
+                let from_min = num("FromMin", -1.0);
+                let from_max = num("FromMax", 1.0);
+                let to_min = num("ToMin", 0.0);
+                let to_max = num("ToMax", 1.0);
+                self.check_normalizer(path, first, from_min, from_max);
+
+                let from_range = from_max - from_min;
+                if from_range.abs() < f64::EPSILON {
+                    return Interval::point(to_min);
+                }
+                let slope = (to_max - to_min) / from_range;
+                first
+                    .add(Interval::point(-from_min))
+                    .scale(slope)
+                    .add(Interval::point(to_min))
+            }
+
+            "Mix" => inputs
+                .iter()
+                .take(2)
+                .copied()
+                .reduce(Interval::union)
+                .unwrap_or(Interval::point(0.0)),
+
+            "Gradient" => Interval::new(num("From", 0.0), num("To", 1.0)),
+
+            "Scale" | "Slider" | "Rotator" | "Anchor" | "XOverride" | "YOverride" | "ZOverride"
+            | "GradientWarp" | "FastGradientWarp" | "VectorWarp" | "Cache" | "Cache2D"
+            | "YSampled" | "Exported" | "PositionsPinch" | "PositionsTwist" => first,
+
+            "Imported" => {
+                let name = obj.get("Name").and_then(|v| v.as_str()).unwrap_or("");
+                self.import_range(name)
+            }
+
+            _ => Interval::UNBOUNDED,
+        }
+    }
+
+    /// Range of an exported node, analyzed once per name.
+    fn import_range(&mut self, name: &str) -> Interval {
+        if let Some(range) = self.import_ranges.get(name) {
+            return *range;
+        }
+        let Some(pointer) = self.exports.get(name).cloned() else {
+            return Interval::UNBOUNDED;
+        };
+        if self.importing.iter().any(|n| n == name) {
+            return Interval::UNBOUNDED;
+        }
+        let Some(target) = self.graph.pointer(&pointer) else {
+            return Interval::UNBOUNDED;
+        };
+
+        // Analyze into scratch lists so the export's nodes and findings are
+        // reported once, at their own location in the graph
+        self.importing.push(name.to_string());
+        let saved_nodes = std::mem::take(&mut self.nodes);
+        let saved_findings = std::mem::take(&mut self.findings);
+        let range = self.analyze_node(target, &pointer);
+        self.nodes = saved_nodes;
+        self.findings = saved_findings;
+        self.importing.pop();
+
+        self.import_ranges.insert(name.to_string(), range);
+        range
+    }
+
+    /// Apply lower/upper limits to `input`, flagging limits that never or always apply.
+    fn check_limits(
+        &mut self,
+        path: &str,
+        input: Interval,
+        lower: Option<f64>,
+        upper: Option<f64>,
+    ) -> Interval {
+        let lo = lower.unwrap_or(f64::NEG_INFINITY);
+        let hi = upper.unwrap_or(f64::INFINITY);
+
+        if input.min >= lo && input.max <= hi {
+            self.finding(
+                path,
+                RangeFindingKind::NoEffect,
+                format!(
+                    "Input range [{}, {}] never reaches the limits [{}, {}]",
+                    input.min, input.max, lo, hi
+                ),
+            );
+        } else if input.max <= lo || input.min >= hi {
+            let value = if input.max <= lo { lo } else { hi };
+            self.finding(
+                path,
+                RangeFindingKind::ConstantOutput,
+                format!(
+                    "Input range [{}, {}] lies entirely past a limit; output is always {}",
+                    input.min, input.max, value
+                ),
+            );
+        }
+
+        Interval::new(input.min.clamp(lo, hi), input.max.clamp(lo, hi))
+    }
+
+    /// Flag Min/Max inputs that can never be selected.
+    fn check_dominated(&mut self, path: &str, inputs: &[Interval], is_min: bool) {
+        for (i, input) in inputs.iter().enumerate() {
+            let dominated = inputs.iter().enumerate().any(|(j, other)| {
+                i != j
+                    && if is_min {
+                        other.max < input.min
+                    } else {
+                        other.min > input.max
+                    }
+            });
+            if dominated {
+                self.finding(
+                    path,
+                    RangeFindingKind::DeadInput,
+                    format!(
+                        "Input {} (range [{}, {}]) is never the {}",
+                        i,
+                        input.min,
+                        input.max,
+                        if is_min { "minimum" } else { "maximum" }
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Compare a Normalizer's declared source range with its actual input range.
+    fn check_normalizer(&mut self, path: &str, input: Interval, from_min: f64, from_max: f64) {
+        if !input.is_bounded() {
+            return;
+        }
+        let (lo, hi) = (from_min.min(from_max), from_min.max(from_max));
+        let tolerance = NORMALIZER_TOLERANCE * (hi - lo).abs().max(1.0);
+        if (input.min - lo).abs() <= tolerance && (input.max - hi).abs() <= tolerance {
+            return;
+        }
+        let effect = if input.min < lo - tolerance || input.max > hi + tolerance {
+            "output overshoots ToMin/ToMax"
+        } else {
+            "part of the target range is never used"
+        };
+        self.finding(
+            path,
+            RangeFindingKind::NormalizerMismatch,
+            format!(
+                "FromMin/FromMax [{}, {}] do not match input range [{}, {}]; {}",
+                from_min, from_max, input.min, input.max, effect
+            ),
+        );
+    }
+
+    fn finding(&mut self, path: &str, kind: RangeFindingKind, message: String) {
+        self.findings.push(RangeFinding {
+            pointer: path.to_string(),
+            kind,
+            message,
+        });
+    }
+}
@@ -0,0 +1,655 @@
+#[cfg(test)]
+mod tests {
+    use crate::noise::evaluator::DensityEvaluator;
+    use serde_json::{json, Value};
+
+    fn parse(graph: &Value) -> DensityEvaluator {
+        DensityEvaluator::from_json(graph).expect("parse graph")
+    }
+
+    // ── Evaluation ────────────────────────────────────────────────────
+
+    #[test]
+    fn evaluates_sum_of_constants() {
+        let graph = json!({
+            "Type": "Sum",
+            "Inputs": [
+                { "Type": "Constant", "Value": 1.5 },
+                { "Type": "Constant", "Value": 2.0 }
+            ]
+        });
+        let evaluator = parse(&graph);
+        assert_eq!(evaluator.evaluate(0.0, 0.0, 0.0), 3.5);
+        assert!(evaluator.profile().is_none());
+    }
+
+    // ── Profiling ─────────────────────────────────────────────────────
+
+    #[test]
+    fn profile_reports_every_node_by_pointer() {
+        let graph = json!({
+            "Type": "Clamp",
+            "WallA": -1.0,
+            "WallB": 1.0,
+            "Inputs": [{
+                "Type": "Sum",
+                "Inputs": [
+                    { "Type": "SimplexNoise2D", "Scale": 32.0, "Octaves": 3 },
+                    { "Type": "Constant", "Value": 0.25 }
+                ]
+            }]
+        });
+        let evaluator = DensityEvaluator::from_json_pointers(&graph, &[String::new()], true)
+            .expect("parse graph");
+        for i in 0..100 {
+            evaluator.evaluate(i as f64, 64.0, -(i as f64));
+        }
+
+        let profile = evaluator.profile().expect("profiling enabled");
+        let ids: Vec<&str> = profile.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec!["", "/Inputs/0", "/Inputs/0/Inputs/0", "/Inputs/0/Inputs/1"]
+        );
+        assert_eq!(profile[2].node_type, "SimplexNoise2D");
+        assert!(profile.iter().all(|p| p.calls == 100));
+
+        let share_sum: f64 = profile.iter().map(|p| p.share).sum();
+        assert!((share_sum - 1.0).abs() < 1e-9, "shares sum to {}", share_sum);
+        for p in &profile {
+            assert!(p.self_ms <= p.total_ms);
+        }
+    }
+
+    #[test]
+    fn profile_escapes_pointer_tokens() {
+        assert_eq!(
+            crate::noise::evaluator::child_pointer("/a", "b/c~d"),
+            "/a/b~1c~0d"
+        );
+    }
+
+    // ── Probing ───────────────────────────────────────────────────────
+
+    #[test]
+    fn probe_mirrors_graph_with_transformed_coordinates() {
+        let graph = json!({
+            "Type": "Sum",
+            "Inputs": [
+                {
+                    "Type": "Slider",
+                    "SlideX": 10.0,
+                    "Input": {
+                        "Type": "Scale",
+                        "X": 2.0, "Y": 1.0, "Z": 4.0,
+                        "Input": { "Type": "Constant", "Value": 3.0 }
+                    }
+                },
+                { "Type": "Constant", "Value": 1.0 }
+            ]
+        });
+        let evaluator = DensityEvaluator::from_json_traced(&graph).expect("parse graph");
+        let trace = evaluator.probe(30.0, 5.0, 8.0).expect("trace");
+
+        assert_eq!(trace.pointer, "");
+        assert_eq!(trace.value, 4.0);
+        assert_eq!(trace.children.len(), 2);
+
+        let slider = &trace.children[0];
+        assert_eq!(slider.pointer, "/Inputs/0");
+        assert_eq!((slider.x, slider.y, slider.z), (30.0, 5.0, 8.0));
+
+        let scale = &slider.children[0];
+        assert_eq!(scale.node_type, "Scale");
+        assert_eq!((scale.x, scale.y, scale.z), (20.0, 5.0, 8.0));
+        assert_eq!(scale.offset, [-10.0, 0.0, 0.0]);
+
+        let leaf = &scale.children[0];
+        assert_eq!(leaf.pointer, "/Inputs/0/Input/Input");
+        assert_eq!((leaf.x, leaf.y, leaf.z), (10.0, 5.0, 2.0));
+        assert_eq!(leaf.value, 3.0);
+    }
+
+    #[test]
+    fn probe_records_every_warp_sample() {
+        let graph = json!({
+            "Type": "GradientWarp",
+            "WarpFactor": 2.0,
+            "SampleRange": 1.0,
+            "2D": true,
+            "Inputs": [
+                { "Type": "Constant", "Value": 0.5 },
+                { "Type": "SimplexNoise2D", "Scale": 16.0 }
+            ]
+        });
+        let evaluator = DensityEvaluator::from_json_traced(&graph).expect("parse graph");
+        let trace = evaluator.probe(4.0, 0.0, 4.0).expect("trace");
+
+        // Four finite-difference samples of the warp field, then the warped input.
+        assert_eq!(trace.children.len(), 5);
+        assert!(trace.children[..4].iter().all(|c| c.pointer == "/Inputs/1"));
+        assert_eq!(trace.children[4].pointer, "/Inputs/0");
+        assert_eq!(trace.value, 0.5);
+
+        let plain = parse(&graph);
+        assert!(plain.probe(4.0, 0.0, 4.0).is_none());
+    }
+
+    #[test]
+    fn probe_records_every_reference_to_a_shared_export() {
+        let graph = json!({
+            "Type": "Sum",
+            "Inputs": [
+                {
+                    "Type": "Multiplier",
+                    "ExportAs": "E",
+                    "Inputs": [
+                        { "Type": "Constant", "Value": 2.0 },
+                        { "Type": "Constant", "Value": 3.0 }
+                    ]
+                },
+                { "Type": "Imported", "Name": "E" }
+            ]
+        });
+        let evaluator = DensityEvaluator::from_json_traced(&graph).expect("parse graph");
+        let trace = evaluator.probe(1.0, 2.0, 3.0).expect("trace");
+
+        assert_eq!(trace.value, 12.0);
+        let export = &trace.children[0];
+        assert_eq!(export.pointer, "/Inputs/0");
+        assert_eq!(export.children.len(), 2);
+
+        // The import replays the export's first evaluation, so the export
+        // shows under it without children of its own
+        let import = &trace.children[1];
+        assert_eq!(import.node_type, "Imported");
+        assert_eq!(import.children.len(), 1);
+        let replayed = &import.children[0];
+        assert_eq!(replayed.pointer, "/Inputs/0");
+        assert_eq!(replayed.value, 6.0);
+        assert!(replayed.children.is_empty());
+    }
+
+    // ── Subgraph outputs ──────────────────────────────────────────────
+
+    #[test]
+    fn pointer_output_resolves_imports_from_full_graph() {
+        let graph = json!({
+            "Type": "Sum",
+            "Inputs": [
+                {
+                    "Type": "Exported",
+                    "ExportAs": "Base",
+                    "Inputs": [{ "Type": "Constant", "Value": 2.0 }]
+                },
+                {
+                    "Type": "Clamp",
+                    "WallA": 0.0,
+                    "WallB": 10.0,
+                    "Inputs": [{ "Type": "Imported", "Name": "Base" }]
+                }
+            ]
+        });
+        let pointers = vec![
+            String::new(),
+            "/Inputs/1".to_string(),
+            "/Inputs/0/Inputs/0".to_string(),
+        ];
+        let evaluator =
+            DensityEvaluator::from_json_pointers(&graph, &pointers, true).expect("parse graph");
+
+        assert_eq!(evaluator.output_count(), 3);
+        assert_eq!(evaluator.evaluate(1.0, 2.0, 3.0), 4.0);
+        assert_eq!(evaluator.evaluate_output(1, 1.0, 2.0, 3.0), 2.0);
+        assert_eq!(evaluator.evaluate_output(2, 1.0, 2.0, 3.0), 2.0);
+
+        // The exported constant is parsed once and memoized across outputs.
+        let profile = evaluator.profile().expect("profiling enabled");
+        let constant = profile
+            .iter()
+            .find(|p| p.id == "/Inputs/0/Inputs/0")
+            .expect("exported constant profiled");
+        assert_eq!(constant.calls, 1);
+        assert_eq!(profile.iter().filter(|p| p.id == "/Inputs/0/Inputs/0").count(), 1);
+    }
+
+    #[test]
+    fn shared_export_time_charged_to_evaluating_consumer() {
+        let graph = json!({
+            "Type": "Sum",
+            "Inputs": [
+                { "Type": "Clamp", "Inputs": [{ "Type": "Imported", "Name": "Noise" }] },
+                {
+                    "Type": "Exported",
+                    "ExportAs": "Noise",
+                    "Inputs": [{ "Type": "SimplexNoise2D", "Scale": 16.0, "Octaves": 8 }]
+                }
+            ]
+        });
+        // The clamp parsed the export, but the export output computes it first
+        // at every position, so the clamp only ever reads the memoized value.
+        let pointers = vec![String::new(), "/Inputs/1".to_string()];
+        let evaluator =
+            DensityEvaluator::from_json_pointers(&graph, &pointers, true).expect("parse graph");
+        for i in 0..2000 {
+            evaluator.evaluate_output(1, i as f64, 0.0, 0.0);
+            evaluator.evaluate(i as f64, 0.0, 0.0);
+        }
+
+        let profile = evaluator.profile().expect("profiling enabled");
+        let find = |id: &str| profile.iter().find(|p| p.id == id).unwrap();
+        let (clamp, noise) = (find("/Inputs/0"), find("/Inputs/1/Inputs/0"));
+        assert_eq!((clamp.calls, noise.calls), (2000, 2000));
+        assert!(clamp.self_ms > 0.0);
+        assert!(clamp.self_ms < noise.self_ms);
+    }
+
+    #[test]
+    fn unresolvable_pointer_is_an_error() {
+        let graph = json!({ "Type": "Constant", "Value": 1.0 });
+        let result = DensityEvaluator::from_json_pointers(&graph, &["/Inputs/3".to_string()], false);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn import_cycle_is_an_error() {
+        let graph = json!({
+            "Type": "Exported",
+            "ExportAs": "Loop",
+            "Inputs": [{ "Type": "Imported", "Name": "Loop" }]
+        });
+        let result = DensityEvaluator::from_json(&graph);
+        assert!(result.is_err());
+    }
+
+    // ── Range analysis ────────────────────────────────────────────────
+
+    #[test]
+    fn ranges_propagate_through_arithmetic() {
+        use crate::noise::ranges::{analyze, Interval};
+
+        let graph = json!({
+            "Type": "Pow",
+            "Exponent": 3.0,
+            "Inputs": [{
+                "Type": "Abs",
+                "Inputs": [{
+                    "Type": "Sum",
+                    "Inputs": [
+                        { "Type": "SimplexNoise2D", "Scale": 64.0 },
+                        { "Type": "Constant", "Value": -0.5 }
+                    ]
+                }]
+            }]
+        });
+        let analysis = analyze(&graph);
+
+        assert_eq!(analysis.nodes[2].output, Interval::new(-1.5, 0.5));
+        assert_eq!(analysis.nodes[1].output, Interval::new(0.0, 1.5));
+        assert_eq!(analysis.output, Interval::new(0.0, 3.375));
+        assert!(analysis.findings.is_empty());
+    }
+
+    #[test]
+    fn ranges_flag_dead_clamp_and_normalizer_mismatch() {
+        use crate::noise::ranges::{analyze, Interval, RangeFindingKind};
+
+        let graph = json!({
+            "Type": "Normalizer",
+            "FromMin": -1.0, "FromMax": 1.0, "ToMin": 0.0, "ToMax": 1.0,
+            "Inputs": [{
+                "Type": "Clamp",
+                "WallA": -2.0, "WallB": 2.0,
+                "Inputs": [{
+                    "Type": "AmplitudeConstant",
+                    "Amplitude": 0.5,
+                    "Inputs": [{ "Type": "SimplexNoise2D" }]
+                }]
+            }]
+        });
+        let analysis = analyze(&graph);
+
+        assert_eq!(analysis.output, Interval::new(0.25, 0.75));
+        let kinds: Vec<(&str, &RangeFindingKind)> = analysis
+            .findings
+            .iter()
+            .map(|f| (f.pointer.as_str(), &f.kind))
+            .collect();
+        assert!(kinds.contains(&("/Inputs/0", &RangeFindingKind::NoEffect)));
+        assert!(kinds.contains(&("", &RangeFindingKind::NormalizerMismatch)));
+    }
+
+    #[test]
+    fn ranges_scaled_by_zero_are_zero() {
+        use crate::noise::ranges::{analyze, Interval};
+
+        let graph = json!({
+            "Type": "AmplitudeConstant",
+            "Amplitude": 0.0,
+            "Inputs": [{ "Type": "SomeFutureNode" }]
+        });
+        let analysis = analyze(&graph);
+
+        assert_eq!(analysis.nodes[1].output, Interval::UNBOUNDED);
+        assert_eq!(analysis.output, Interval::point(0.0));
+        assert_eq!(Interval::new(-2.0, 3.0).scale(0.0), Interval::point(0.0));
+    }
+
+    #[test]
+    fn ranges_resolve_imports_and_dominated_inputs() {
+        use crate::noise::ranges::{analyze, Interval, RangeFindingKind};
+
+        let graph = json!({
+            "Type": "Max",
+            "Inputs": [
+                {
+                    "Type": "Exported",
+                    "ExportAs": "High",
+                    "Inputs": [{ "Type": "Constant", "Value": 5.0 }]
+                },
+                { "Type": "Imported", "Name": "High" },
+                { "Type": "SimplexNoise2D" }
+            ]
+        });
+        let analysis = analyze(&graph);
+
+        assert_eq!(analysis.output, Interval::point(5.0));
+        assert_eq!(analysis.nodes.len(), 5);
+        assert!(analysis
+            .findings
+            .iter()
+            .any(|f| f.kind == RangeFindingKind::DeadInput && f.message.starts_with("Input 2")));
+    }
+
+    // ── Subtree cache ─────────────────────────────────────────────────
+
+    fn eval_cached(
+        graph: &Value,
+        cache: &std::sync::Mutex<crate::noise::cache::SubtreeCache>,
+        grid: crate::noise::cache::GridKey,
+    ) -> (Vec<f64>, usize) {
+        let mut evaluator =
+            DensityEvaluator::from_json_cached(graph, &[String::new()], cache, grid)
+                .expect("parse graph");
+        let mut values = Vec::new();
+        for i in 0..grid.sample_count() {
+            evaluator.set_sample_index(i);
+            values.push(evaluator.evaluate(i as f64 * 3.0, 0.0, i as f64 * -2.0));
+        }
+        evaluator.store_grids(cache);
+        (values, evaluator.cache_hits())
+    }
+
+    #[test]
+    fn cached_evaluation_reuses_unchanged_subtrees() {
+        use crate::noise::cache::{GridKey, SubtreeCache};
+        use std::sync::Mutex;
+
+        let mut graph = json!({
+            "Type": "Sum",
+            "Inputs": [
+                { "Type": "SimplexNoise2D", "Scale": 32.0, "Seed": "a" },
+                {
+                    "Type": "Clamp",
+                    "WallA": -0.5, "WallB": 0.5,
+                    "Inputs": [{ "Type": "SimplexNoise2D", "Scale": 8.0, "Seed": "b" }]
+                }
+            ]
+        });
+        let grid = GridKey::new(4, 0.0, 64.0, 0.0);
+        let cache = Mutex::new(SubtreeCache::default());
+
+        let (first, hits) = eval_cached(&graph, &cache, grid);
+        assert_eq!(hits, 0);
+        let plain = parse(&graph);
+        for (i, value) in first.iter().enumerate() {
+            assert_eq!(*value, plain.evaluate(i as f64 * 3.0, 0.0, i as f64 * -2.0));
+        }
+
+        // Unchanged graph: the root replays directly.
+        let (again, hits) = eval_cached(&graph, &cache, grid);
+        assert_eq!((again.as_slice(), hits), (first.as_slice(), 1));
+
+        // Editing the clamped leaf reuses the untouched first input only.
+        graph["Inputs"][1]["Inputs"][0]["Scale"] = json!(16.0);
+        let (edited, hits) = eval_cached(&graph, &cache, grid);
+        assert_eq!(hits, 1);
+        let plain = parse(&graph);
+        for (i, value) in edited.iter().enumerate() {
+            assert_eq!(*value, plain.evaluate(i as f64 * 3.0, 0.0, i as f64 * -2.0));
+        }
+
+        // A different grid never reuses samples.
+        let (_, hits) = eval_cached(&graph, &cache, GridKey::new(4, 0.0, 32.0, 0.0));
+        assert_eq!(hits, 0);
+
+        // Subtrees that would not fit the budget are neither recorded nor replayed.
+        let small = Mutex::new(SubtreeCache::new(64));
+        eval_cached(&graph, &small, grid);
+        let (_, hits) = eval_cached(&graph, &small, grid);
+        assert_eq!(hits, 0);
+    }
+
+    #[test]
+    fn cached_export_imported_under_a_transform_matches_uncached() {
+        use crate::noise::cache::{GridKey, SubtreeCache};
+        use std::sync::Mutex;
+
+        // The export is first parsed where caching applies, then imported
+        // below a Scale that samples it at other positions.
+        let mut graph = json!({
+            "Type": "Sum",
+            "Inputs": [
+                {
+                    "Type": "Sum",
+                    "ExportAs": "E",
+                    "Inputs": [
+                        { "Type": "SimplexNoise2D", "Scale": 32.0, "Seed": "a" },
+                        { "Type": "SimplexNoise2D", "Scale": 8.0, "Seed": "b" }
+                    ]
+                },
+                {
+                    "Type": "Scale",
+                    "X": 2.0, "Y": 1.0, "Z": 2.0,
+                    "Input": { "Type": "Imported", "Name": "E" }
+                }
+            ]
+        });
+        let grid = GridKey::new(4, 0.0, 64.0, 0.0);
+        let cache = Mutex::new(SubtreeCache::default());
+
+        for edit in [None, Some(16.0)] {
+            if let Some(scale) = edit {
+                graph["Inputs"][0]["Inputs"][1]["Scale"] = json!(scale);
+            }
+            let (cached, _) = eval_cached(&graph, &cache, grid);
+            let plain = parse(&graph);
+            for (i, value) in cached.iter().enumerate() {
+                assert_eq!(*value, plain.evaluate(i as f64 * 3.0, 0.0, i as f64 * -2.0), "sample {}", i);
+            }
+        }
+    }
+
+    #[test]
+    fn subtree_cache_evicts_least_recently_used() {
+        use crate::noise::cache::{CacheKey, GridKey, SubtreeCache};
+        use std::sync::Arc;
+
+        let grid = GridKey::new(2, 0.0, 1.0, 0.0);
+        let key = |subtree| CacheKey { subtree, grid };
+        // Two entries of four samples (32 bytes) and one byte of subtree text
+        let mut cache = SubtreeCache::new(70);
+
+        cache.insert(key(1), "a".into(), Arc::new(vec![1.0; 4]));
+        cache.insert(key(2), "b".into(), Arc::new(vec![2.0; 4]));
+        assert!(cache.get(&key(1), "a").is_some());
+        cache.insert(key(3), "c".into(), Arc::new(vec![3.0; 4]));
+
+        assert!(cache.get(&key(1), "a").is_some());
+        assert!(cache.get(&key(2), "b").is_none());
+        assert!(cache.get(&key(3), "c").is_some());
+
+        assert!(cache.get(&key(3), "a").is_none(), "hash collision with another subtree");
+
+        cache.insert(key(4), "d".into(), Arc::new(vec![4.0; 9]));
+        assert!(cache.get(&key(4), "d").is_none(), "larger than the whole budget");
+    }
+
+    // ── Batch evaluation ──────────────────────────────────────────────
+
+    #[test]
+    fn batch_matches_scalar_evaluation() {
+        let graph = json!({
+            "Type": "Normalizer",
+            "FromMin": -2.0, "FromMax": 2.0, "ToMin": 0.0, "ToMax": 1.0,
+            "Inputs": [{
+                "Type": "Sum",
+                "Inputs": [
+                    {
+                        "Type": "Multiplier",
+                        "Inputs": [
+                            { "Type": "SimplexNoise2D", "Scale": 24.0, "Octaves": 4 },
+                            { "Type": "Constant", "Value": 1.5 }
+                        ]
+                    },
+                    {
+                        "Type": "GradientWarp",
+                        "WarpFactor": 4.0,
+                        "Inputs": [
+                            {
+                                "Type": "Clamp", "WallA": -0.3, "WallB": 0.3,
+                                "Input": {
+                                    "Type": "Scale", "X": 2.0, "Y": 1.0, "Z": 0.5,
+                                    "Input": { "Type": "SimplexNoise2D", "Scale": 16.0 }
+                                }
+                            },
+                            { "Type": "SimplexNoise2D", "Scale": 64.0, "Seed": "warp" }
+                        ]
+                    },
+                    { "Type": "Slider", "SlideX": 7.0, "Input": { "Type": "SimplexNoise2D" } }
+                ]
+            }]
+        });
+        let evaluator = parse(&graph);
+
+        let xs: Vec<f64> = (0..37).map(|i| i as f64 * 3.7 - 40.0).collect();
+        let ys = vec![64.0; xs.len()];
+        let zs: Vec<f64> = (0..37).map(|i| i as f64 * -1.3).collect();
+        let mut out = vec![0.0; xs.len()];
+        evaluator.evaluate_batch(&xs, &ys, &zs, &mut out);
+
+        for i in 0..xs.len() {
+            assert_eq!(out[i], evaluator.evaluate(xs[i], ys[i], zs[i]), "lane {}", i);
+        }
+    }
+
+    // ── Biome band analysis ───────────────────────────────────────────
+
+    fn band_kinds(structure: Value) -> Vec<(Option<usize>, crate::noise::bands::BandFindingKind)> {
+        let analysis = crate::noise::bands::analyze_bands(structure.as_object().unwrap()).unwrap();
+        analysis.findings.iter().map(|f| (f.band, f.kind)).collect()
+    }
+
+    #[test]
+    fn bands_flag_gaps_overlaps_inverted_and_unreachable() {
+        use crate::noise::bands::BandFindingKind::*;
+
+        let kinds = band_kinds(json!({
+            "Type": "NoiseRange",
+            "DefaultBiome": "Plains",
+            "Density": { "Type": "SimplexNoise2D", "Scale": 512.0 },
+            "Biomes": [
+                { "Biome": "Ocean", "Min": -1.0, "Max": -0.2 },
+                { "Biome": "Beach", "Min": -0.3, "Max": 0.2 },
+                { "Biome": "Broken", "Min": 0.5, "Max": 0.4 },
+                { "Biome": "Sky", "Min": 1.5, "Max": 2.0 }
+            ]
+        }));
+
+        assert_eq!(
+            kinds,
+            [(Some(2), Inverted), (Some(3), Unreachable), (Some(1), Overlap), (None, Gap)]
+        );
+    }
+
+    #[test]
+    fn bands_flag_transitions_wider_than_band() {
+        use crate::noise::bands::BandFindingKind::*;
+
+        let structure = |scale: f64| json!({
+            "Type": "NoiseRange",
+            "DefaultBiome": "Plains",
+            "DefaultTransitionDistance": 32,
+            "Density": { "Type": "SimplexNoise2D", "Scale": scale },
+            "Biomes": [
+                { "Biome": "Low", "Min": -1.0, "Max": 0.0 },
+                { "Biome": "High", "Min": 0.0, "Max": 1.0 }
+            ]
+        });
+
+        assert_eq!(band_kinds(structure(4.0)), [(Some(0), TransitionTooWide), (Some(1), TransitionTooWide)]);
+        assert!(band_kinds(structure(2048.0)).is_empty());
+    }
+
+    #[test]
+    fn bands_skip_selectors_with_unsupported_types() {
+        use crate::noise::bands::analyze_bands;
+
+        // XValue is unbounded statically and not implemented by the
+        // evaluator, so neither range source applies
+        let structure = json!({
+            "Type": "NoiseRange",
+            "DefaultBiome": "Plains",
+            "DefaultTransitionDistance": 32,
+            "Density": { "Type": "XValue" },
+            "Biomes": [
+                { "Biome": "West", "Min": -5000.0, "Max": 0.0 },
+                { "Biome": "East", "Min": 0.0, "Max": 5000.0 },
+                { "Biome": "FarEast", "Min": 9000.0, "Max": 10000.0 }
+            ]
+        });
+        let analysis = analyze_bands(structure.as_object().unwrap()).unwrap();
+
+        assert!(!analysis.sampled);
+        assert!(analysis.gradient.is_none());
+        assert!(analysis.findings.is_empty(), "{:?}", analysis.findings);
+    }
+
+    #[test]
+    fn bands_skip_selectors_importing_other_files() {
+        use crate::noise::bands::analyze_bands;
+
+        let structure = json!({
+            "Type": "NoiseRange",
+            "DefaultBiome": "P",
+            "Density": { "Type": "Imported", "Name": "Continents" },
+            "Biomes": [
+                { "Biome": "Ocean", "Min": -1, "Max": -0.2 },
+                { "Biome": "Land", "Min": -0.2, "Max": 1 }
+            ]
+        });
+        let analysis = analyze_bands(structure.as_object().unwrap()).unwrap();
+
+        // Evaluating the missing export as zero would make both bands unreachable
+        assert!(!analysis.sampled);
+        assert!(!analysis.selector.is_bounded());
+        assert!(analysis.findings.is_empty(), "{:?}", analysis.findings);
+        assert!(DensityEvaluator::from_json_strict(&structure["Density"]).is_err());
+    }
+
+    #[test]
+    fn bands_with_static_range_are_not_sampled() {
+        use crate::noise::bands::analyze_bands;
+
+        let structure = json!({
+            "Type": "NoiseRange",
+            "DefaultBiome": "Plains",
+            "Density": { "Type": "SimplexNoise2D", "Scale": 512.0 },
+            "Biomes": [{ "Biome": "All", "Min": -1.0, "Max": 1.0 }]
+        });
+        let analysis = analyze_bands(structure.as_object().unwrap()).unwrap();
+
+        assert!(!analysis.sampled);
+        assert!(analysis.gradient.is_none());
+    }
+}
@@ -0,0 +1,98 @@
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+
+use super::nodes::NodeEval;
+
+/// One node evaluation captured while probing a single point.
+///
+/// The tree mirrors the graph JSON: children are the evaluations the node
+/// requested from its inputs, in call order. A node that samples an input
+/// more than once (e.g. a warp taking finite differences) lists every call.
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceNode {
+    /// JSON Pointer of the node within the evaluated graph ("" is the root).
+    pub pointer: String,
+    pub node_type: String,
+    /// Output value of the node for this call.
+    pub value: f64,
+    /// Coordinates the node was evaluated at, after upstream transforms and warps.
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    /// Displacement of the evaluation point from the probed position.
+    pub offset: [f64; 3],
+    pub children: Vec<TraceNode>,
+}
+
+#[derive(Default)]
+struct TraceState {
+    origin: [f64; 3],
+    stack: Vec<TraceNode>,
+    root: Option<TraceNode>,
+}
+
+/// Records the evaluation tree for one probe at a time.
+#[derive(Default)]
+pub struct Tracer {
+    state: Mutex<TraceState>,
+}
+
+impl Tracer {
+    /// Clear any previous trace and set the probed position.
+    pub fn begin(&self, x: f64, y: f64, z: f64) {
+        let mut state = self.state.lock().unwrap();
+        *state = TraceState {
+            origin: [x, y, z],
+            ..Default::default()
+        };
+    }
+
+    /// Take the completed trace tree, if anything was evaluated.
+    pub fn finish(&self) -> Option<TraceNode> {
+        self.state.lock().unwrap().root.take()
+    }
+
+    fn enter(&self, pointer: &str, node_type: &str, x: f64, y: f64, z: f64) {
+        let mut state = self.state.lock().unwrap();
+        let [ox, oy, oz] = state.origin;
+        state.stack.push(TraceNode {
+            pointer: pointer.to_string(),
+            node_type: node_type.to_string(),
+            value: 0.0,
+            x,
+            y,
+            z,
+            offset: [x - ox, y - oy, z - oz],
+            children: Vec::new(),
+        });
+    }
+
+    fn exit(&self, value: f64) {
+        let mut state = self.state.lock().unwrap();
+        let Some(mut node) = state.stack.pop() else {
+            return;
+        };
+        node.value = value;
+        match state.stack.last_mut() {
+            Some(parent) => parent.children.push(node),
+            None => state.root = Some(node),
+        }
+    }
+}
+
+/// Wraps a node and reports each call to the shared `Tracer`.
+pub struct TracedNode {
+    pub inner: Box<dyn NodeEval>,
+    pub pointer: String,
+    pub node_type: String,
+    pub tracer: Arc<Tracer>,
+}
+
+impl NodeEval for TracedNode {
+    fn eval(&self, x: f64, y: f64, z: f64) -> f64 {
+        self.tracer.enter(&self.pointer, &self.node_type, x, y, z);
+        let value = self.inner.eval(x, y, z);
+        self.tracer.exit(value);
+        value
+    }
+}
@@ -0,0 +1,87 @@
+use serde::Deserialize;
+
+/// Identifies a preview grid buffer; the UI rejects anything else.
+pub const MAGIC: [u8; 4] = *b"TNGR";
+pub const VERSION: u8 = 1;
+/// Size of the fixed header. A multiple of 4 so the payload can be viewed
+/// in place as a `Float32Array` / `Uint16Array` on the JS side.
+pub const HEADER_LEN: usize = 24;
+
+/// Element type of the payload following the header.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GridDtype {
+    /// Raw `f32` values.
+    #[default]
+    F32,
+    /// Values quantized to `u16` over the header's min..max range.
+    U16,
+}
+
+impl GridDtype {
+    fn code(self) -> u8 {
+        match self {
+            GridDtype::F32 => 0,
+            GridDtype::U16 => 1,
+        }
+    }
+}
+
+/// Encode a row-major NxN grid as a little-endian binary buffer.
+///
+/// Layout:
+/// ```text
+/// 0   magic       [u8; 4]  "TNGR"
+/// 4   version     u8
+/// 5   dtype       u8       0 = f32, 1 = u16
+/// 6   reserved    u16
+/// 8   resolution  u32
+/// 12  count       u32      number of values
+/// 16  min_value   f32
+/// 20  max_value   f32
+/// 24  values      count * (4 | 2) bytes
+/// ```
+pub fn encode_grid(
+    values: &[f32],
+    resolution: u32,
+    min_value: f32,
+    max_value: f32,
+    dtype: GridDtype,
+) -> Vec<u8> {
+    let elem_size = match dtype {
+        GridDtype::F32 => 4,
+        GridDtype::U16 => 2,
+    };
+    let mut buf = Vec::with_capacity(HEADER_LEN + values.len() * elem_size);
+
+    buf.extend_from_slice(&MAGIC);
+    buf.push(VERSION);
+    buf.push(dtype.code());
+    buf.extend_from_slice(&0u16.to_le_bytes());
+    buf.extend_from_slice(&resolution.to_le_bytes());
+    buf.extend_from_slice(&(values.len() as u32).to_le_bytes());
+    buf.extend_from_slice(&min_value.to_le_bytes());
+    buf.extend_from_slice(&max_value.to_le_bytes());
+
+    match dtype {
+        GridDtype::F32 => {
+            for v in values {
+                buf.extend_from_slice(&v.to_le_bytes());
+            }
+        }
+        GridDtype::U16 => {
+            let range = max_value - min_value;
+            for v in values {
+                let t = if range > 0.0 {
+                    ((v - min_value) / range).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                let q = (t * u16::MAX as f32).round() as u16;
+                buf.extend_from_slice(&q.to_le_bytes());
+            }
+        }
+    }
+
+    buf
+}
@@ -0,0 +1,196 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Contour lines requested alongside a preview grid.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ContourOptions {
+    /// Iso-values to trace.
+    pub levels: Vec<f64>,
+    /// Also render the contours as a standalone SVG document.
+    #[serde(default)]
+    pub svg: bool,
+}
+
+/// All polylines traced at one iso-value.
+///
+/// Points are in grid-cell coordinates: `[x, z]` with `0..resolution-1` on
+/// each axis, matching `src/utils/contourLines.ts`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ContourLevel {
+    pub level: f64,
+    /// Closed loops repeat their first point at the end.
+    pub polylines: Vec<Vec<[f64; 2]>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Contours {
+    pub levels: Vec<ContourLevel>,
+    pub svg: Option<String>,
+}
+
+// Marching-squares edge table, identical to the TypeScript implementation.
+// For each 4-bit case (TL=8, TR=4, BR=2, BL=1), pairs of edges to connect.
+// Edge indices: 0=top, 1=right, 2=bottom, 3=left.
+const EDGE_TABLE: [&[u8]; 16] = [
+    &[],
+    &[3, 2],
+    &[2, 1],
+    &[3, 1],
+    &[1, 0],
+    &[3, 0, 1, 2],
+    &[2, 0],
+    &[3, 0],
+    &[0, 3],
+    &[0, 2],
+    &[0, 1, 2, 3],
+    &[0, 1],
+    &[1, 3],
+    &[1, 2],
+    &[2, 3],
+    &[],
+];
+
+fn interpolate(v1: f64, v2: f64, level: f64) -> f64 {
+    if (v2 - v1).abs() < 1e-10 {
+        return 0.5;
+    }
+    (level - v1) / (v2 - v1)
+}
+
+/// Trace contours for every level and optionally render them as SVG.
+pub fn extract(values: &[f32], resolution: u32, options: &ContourOptions) -> Contours {
+    let levels: Vec<ContourLevel> = options
+        .levels
+        .iter()
+        .map(|&level| ContourLevel {
+            level,
+            polylines: trace_level(values, resolution as usize, level),
+        })
+        .collect();
+    let svg = options.svg.then(|| to_svg(&levels, resolution));
+    Contours { levels, svg }
+}
+
+/// Run marching squares for one level and join the segments into polylines.
+fn trace_level(values: &[f32], n: usize, level: f64) -> Vec<Vec<[f64; 2]>> {
+    if n < 2 || values.len() < n * n {
+        return Vec::new();
+    }
+
+    // Grid edges are identified by the grid point they start at and their
+    // direction, so neighbouring cells agree on the shared crossing point.
+    let horizontal = |row: usize, col: usize| (row * n + col) * 2;
+    let vertical = |row: usize, col: usize| (row * n + col) * 2 + 1;
+
+    let mut points: HashMap<usize, [f64; 2]> = HashMap::new();
+    let mut segments: Vec<[usize; 2]> = Vec::new();
+
+    for row in 0..n - 1 {
+        for col in 0..n - 1 {
+            let tl = values[row * n + col] as f64;
+            let tr = values[row * n + col + 1] as f64;
+            let br = values[(row + 1) * n + col + 1] as f64;
+            let bl = values[(row + 1) * n + col] as f64;
+
+            let code = (if tl >= level { 8 } else { 0 })
+                | (if tr >= level { 4 } else { 0 })
+                | (if br >= level { 2 } else { 0 })
+                | (if bl >= level { 1 } else { 0 });
+
+            let (r, c) = (row as f64, col as f64);
+            let edge_ids: Vec<usize> = EDGE_TABLE[code]
+                .iter()
+                .map(|&edge| {
+                    let (id, point) = match edge {
+                        0 => (horizontal(row, col), [c + interpolate(tl, tr, level), r]),
+                        1 => (
+                            vertical(row, col + 1),
+                            [c + 1.0, r + interpolate(tr, br, level)],
+                        ),
+                        2 => (
+                            horizontal(row + 1, col),
+                            [c + interpolate(bl, br, level), r + 1.0],
+                        ),
+                        _ => (vertical(row, col), [c, r + interpolate(tl, bl, level)]),
+                    };
+                    points.entry(id).or_insert(point);
+                    id
+                })
+                .collect();
+            for pair in edge_ids.chunks_exact(2) {
+                segments.push([pair[0], pair[1]]);
+            }
+        }
+    }
+
+    join_segments(&segments, &points)
+}
+
+/// Chain segments sharing an edge crossing into polylines.
+///
+/// Open lines are walked from their loose ends first; whatever remains forms
+/// closed loops.
+fn join_segments(segments: &[[usize; 2]], points: &HashMap<usize, [f64; 2]>) -> Vec<Vec<[f64; 2]>> {
+    let mut by_edge: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (i, seg) in segments.iter().enumerate() {
+        by_edge.entry(seg[0]).or_default().push(i);
+        by_edge.entry(seg[1]).or_default().push(i);
+    }
+
+    let mut used = vec![false; segments.len()];
+    let walk = |start_edge: usize, start_seg: usize, used: &mut [bool]| {
+        let mut line = vec![points[&start_edge]];
+        let mut edge = start_edge;
+        let mut seg = Some(start_seg);
+        while let Some(s) = seg {
+            used[s] = true;
+            let [a, b] = segments[s];
+            edge = if a == edge { b } else { a };
+            line.push(points[&edge]);
+            seg = by_edge[&edge].iter().copied().find(|&next| !used[next]);
+        }
+        line
+    };
+
+    let mut polylines = Vec::new();
+    for i in 0..segments.len() {
+        if used[i] {
+            continue;
+        }
+        if let Some(&end) = segments[i].iter().find(|e| by_edge[*e].len() == 1) {
+            polylines.push(walk(end, i, &mut used));
+        }
+    }
+    for i in 0..segments.len() {
+        if !used[i] {
+            polylines.push(walk(segments[i][0], i, &mut used));
+        }
+    }
+    polylines
+}
+
+/// Render contour polylines as an SVG document in grid-cell coordinates.
+pub fn to_svg(levels: &[ContourLevel], resolution: u32) -> String {
+    let size = resolution.saturating_sub(1);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {size} {size}\" width=\"{resolution}\" height=\"{resolution}\">\n"
+    );
+    for level in levels {
+        let _ = writeln!(
+            svg,
+            "  <g data-level=\"{}\" fill=\"none\" stroke=\"#000000\">",
+            level.level
+        );
+        for line in &level.polylines {
+            let points: Vec<String> = line
+                .iter()
+                .map(|[x, z]| format!("{:.3},{:.3}", x, z))
+                .collect();
+            let _ = writeln!(svg, "    <polyline points=\"{}\" stroke-width=\"1\" vector-effect=\"non-scaling-stroke\"/>", points.join(" "));
+        }
+        svg.push_str("  </g>\n");
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
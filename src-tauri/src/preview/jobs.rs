@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::noise::evaluator::DensityEvaluator;
+
+use super::binary::{self, GridDtype};
+
+/// A running preview job and the view it renders into.
+struct Job {
+    view: String,
+    cancel: Arc<AtomicBool>,
+}
+
+#[derive(Default)]
+struct JobTable {
+    next_id: u64,
+    jobs: HashMap<u64, Job>,
+}
+
+/// Registry of in-flight preview jobs, managed as Tauri state.
+///
+/// Cloning shares the registry so worker threads can deregister themselves.
+#[derive(Clone, Default)]
+pub struct PreviewJobs {
+    table: Arc<Mutex<JobTable>>,
+}
+
+impl PreviewJobs {
+    /// Register a job for `view`, cancelling any older job still rendering it.
+    pub fn start(&self, view: &str) -> (u64, Arc<AtomicBool>) {
+        let mut table = self.table.lock().unwrap();
+        for job in table.jobs.values().filter(|job| job.view == view) {
+            job.cancel.store(true, Ordering::Relaxed);
+        }
+        table.jobs.retain(|_, job| job.view != view);
+
+        table.next_id += 1;
+        let id = table.next_id;
+        let cancel = Arc::new(AtomicBool::new(false));
+        table.jobs.insert(
+            id,
+            Job {
+                view: view.to_string(),
+                cancel: cancel.clone(),
+            },
+        );
+        (id, cancel)
+    }
+
+    /// Request cancellation. Returns false if the job already finished or is unknown.
+    pub fn cancel(&self, id: u64) -> bool {
+        match self.table.lock().unwrap().jobs.remove(&id) {
+            Some(job) => {
+                job.cancel.store(true, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Drop a job from the registry once its worker is done.
+    pub fn finish(&self, id: u64) {
+        self.table.lock().unwrap().jobs.remove(&id);
+    }
+}
+
+/// Sample area shared by every refinement level of a job.
+pub struct GridSpec {
+    pub range_min: f64,
+    pub range_max: f64,
+    pub y_level: f64,
+    pub dtype: GridDtype,
+}
+
+/// Evaluate `resolutions` in order, handing each encoded grid to `emit`.
+///
+/// Checks `cancel` between rows so a superseded job stops within one row of
+/// work. Stops early if cancelled or if `emit` returns false (e.g. the channel
+/// was closed). Returns the number of levels emitted.
+pub fn run_levels(
+    evaluator: &DensityEvaluator,
+    spec: &GridSpec,
+    resolutions: &[u32],
+    cancel: &AtomicBool,
+    mut emit: impl FnMut(Vec<u8>) -> bool,
+) -> usize {
+    let mut emitted = 0;
+    for &resolution in resolutions {
+        let Some(grid) = sample_level(evaluator, spec, resolution, cancel) else {
+            break;
+        };
+        if !emit(grid) {
+            break;
+        }
+        emitted += 1;
+    }
+    emitted
+}
+
+fn sample_level(
+    evaluator: &DensityEvaluator,
+    spec: &GridSpec,
+    resolution: u32,
+    cancel: &AtomicBool,
+) -> Option<Vec<u8>> {
+    let n = resolution as usize;
+    let step = (spec.range_max - spec.range_min) / n as f64;
+    let mut values = Vec::with_capacity(n * n);
+    let mut min_value = f32::MAX;
+    let mut max_value = f32::MIN;
+
+    let xs: Vec<f64> = (0..n)
+        .map(|x_idx| spec.range_min + (x_idx as f64 + 0.5) * step)
+        .collect();
+    let ys = vec![spec.y_level; n];
+    let mut zs = vec![0.0; n];
+    let mut row = vec![0.0; n];
+
+    for z_idx in 0..n {
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        zs.fill(spec.range_min + (z_idx as f64 + 0.5) * step);
+        evaluator.evaluate_batch(&xs, &ys, &zs, &mut row);
+        for &val in &row {
+            let val = val as f32;
+            min_value = min_value.min(val);
+            max_value = max_value.max(val);
+            values.push(val);
+        }
+    }
+
+    Some(binary::encode_grid(
+        &values, resolution, min_value, max_value, spec.dtype,
+    ))
+}
@@ -0,0 +1,7 @@
+pub mod binary;
+pub mod contours;
+pub mod jobs;
+pub mod stats;
+pub mod tiles;
+#[cfg(test)]
+mod tests;
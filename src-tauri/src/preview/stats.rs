@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+
+/// Which statistics to compute alongside a preview grid.
+#[derive(Debug, Clone, Deserialize)]
+pub struct StatsOptions {
+    /// Number of equal-width histogram buckets between the grid's min and max;
+    /// a flat grid gets a single bucket
+    #[serde(default = "default_histogram_bins")]
+    pub histogram_bins: usize,
+    /// Report the fraction of values above/below this threshold
+    #[serde(default)]
+    pub threshold: Option<f64>,
+}
+
+fn default_histogram_bins() -> usize {
+    32
+}
+
+/// Equal-width histogram; `bin_edges` has one more entry than `bins`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Histogram {
+    pub bins: Vec<u32>,
+    pub bin_edges: Vec<f64>,
+}
+
+/// Share of the grid on either side of a threshold.
+#[derive(Debug, Clone, Serialize)]
+pub struct ThresholdStats {
+    pub threshold: f64,
+    /// Fraction (0..1) of values strictly above the threshold
+    pub fraction_above: f64,
+    /// Fraction (0..1) of values strictly below the threshold
+    pub fraction_below: f64,
+}
+
+/// Descriptive statistics for a grid of density values.
+#[derive(Debug, Clone, Serialize)]
+pub struct GridStats {
+    pub mean: f64,
+    /// Population standard deviation
+    pub std_dev: f64,
+    pub p1: f64,
+    pub p5: f64,
+    pub p50: f64,
+    pub p95: f64,
+    pub p99: f64,
+    pub histogram: Histogram,
+    pub threshold: Option<ThresholdStats>,
+}
+
+/// Compute statistics over `values`, whose range is already known to be
+/// `[min, max]` from the evaluation pass.
+pub fn compute(values: &[f32], min: f32, max: f32, options: &StatsOptions) -> GridStats {
+    let n = values.len();
+    if n == 0 {
+        return GridStats {
+            mean: 0.0,
+            std_dev: 0.0,
+            p1: 0.0,
+            p5: 0.0,
+            p50: 0.0,
+            p95: 0.0,
+            p99: 0.0,
+            histogram: Histogram {
+                bins: Vec::new(),
+                bin_edges: Vec::new(),
+            },
+            threshold: None,
+        };
+    }
+
+    let mean = values.iter().map(|v| *v as f64).sum::<f64>() / n as f64;
+    let variance = values
+        .iter()
+        .map(|v| {
+            let d = *v as f64 - mean;
+            d * d
+        })
+        .sum::<f64>()
+        / n as f64;
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f32::total_cmp);
+    let percentile = |p: f64| {
+        let idx = p / 100.0 * (n - 1) as f64;
+        let lo = idx.floor() as usize;
+        let hi = idx.ceil() as usize;
+        let (a, b) = (sorted[lo] as f64, sorted[hi] as f64);
+        a + (b - a) * (idx - lo as f64)
+    };
+
+    let threshold = options.threshold.map(|t| {
+        let above = values.iter().filter(|v| **v as f64 > t).count();
+        let below = values.iter().filter(|v| (**v as f64) < t).count();
+        ThresholdStats {
+            threshold: t,
+            fraction_above: above as f64 / n as f64,
+            fraction_below: below as f64 / n as f64,
+        }
+    });
+
+    GridStats {
+        mean,
+        std_dev: variance.sqrt(),
+        p1: percentile(1.0),
+        p5: percentile(5.0),
+        p50: percentile(50.0),
+        p95: percentile(95.0),
+        p99: percentile(99.0),
+        histogram: histogram(values, min as f64, max as f64, options.histogram_bins),
+        threshold,
+    }
+}
+
+fn histogram(values: &[f32], min: f64, max: f64, bin_count: usize) -> Histogram {
+    if bin_count == 0 {
+        return Histogram {
+            bins: Vec::new(),
+            bin_edges: Vec::new(),
+        };
+    }
+
+    // Flat data: one bucket spanning the single value
+    if max <= min {
+        return Histogram {
+            bins: vec![values.len() as u32],
+            bin_edges: vec![min, max],
+        };
+    }
+
+    let mut bins = vec![0u32; bin_count];
+
+    let width = (max - min) / bin_count as f64;
+    for v in values {
+        // The max value goes in the last bucket
+        let idx = (((*v as f64 - min) / width) as usize).min(bin_count - 1);
+        bins[idx] += 1;
+    }
+    let bin_edges = (0..=bin_count).map(|i| min + i as f64 * width).collect();
+
+    Histogram { bins, bin_edges }
+}
@@ -0,0 +1,351 @@
+#[cfg(test)]
+mod tests {
+    use crate::preview::stats::{compute, StatsOptions};
+
+    fn options(bins: usize, threshold: Option<f64>) -> StatsOptions {
+        StatsOptions {
+            histogram_bins: bins,
+            threshold,
+        }
+    }
+
+    // ── Statistics ────────────────────────────────────────────────────
+
+    #[test]
+    fn stats_match_known_distribution() {
+        let values: Vec<f32> = (0..=100).map(|i| i as f32).collect();
+        let stats = compute(&values, 0.0, 100.0, &options(4, Some(75.0)));
+
+        assert_eq!(stats.mean, 50.0);
+        assert!((stats.std_dev - 29.1548).abs() < 1e-3, "std_dev {}", stats.std_dev);
+        assert_eq!(stats.p1, 1.0);
+        assert_eq!(stats.p50, 50.0);
+        assert_eq!(stats.p99, 99.0);
+
+        assert_eq!(stats.histogram.bins, vec![25, 25, 25, 26]);
+        assert_eq!(stats.histogram.bin_edges, vec![0.0, 25.0, 50.0, 75.0, 100.0]);
+
+        let threshold = stats.threshold.expect("threshold requested");
+        assert!((threshold.fraction_above - 25.0 / 101.0).abs() < 1e-12);
+        assert!((threshold.fraction_below - 75.0 / 101.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn stats_interpolate_percentiles() {
+        let values = [0.0f32, 1.0];
+        let stats = compute(&values, 0.0, 1.0, &options(2, None));
+        assert!((stats.p5 - 0.05).abs() < 1e-12, "p5 {}", stats.p5);
+        assert!((stats.p95 - 0.95).abs() < 1e-12, "p95 {}", stats.p95);
+        assert!(stats.threshold.is_none());
+    }
+
+    #[test]
+    fn stats_handle_flat_and_empty_grids() {
+        let flat = [0.5f32; 9];
+        let stats = compute(&flat, 0.5, 0.5, &options(3, None));
+        assert_eq!(stats.std_dev, 0.0);
+        assert_eq!(stats.histogram.bins, vec![9]);
+        assert_eq!(stats.histogram.bin_edges, vec![0.5, 0.5]);
+
+        let empty = compute(&[], 0.0, 0.0, &options(3, None));
+        assert!(empty.histogram.bins.is_empty());
+    }
+
+    // ── Binary encoding ───────────────────────────────────────────────
+
+    #[test]
+    fn binary_grid_has_header_and_le_payload() {
+        use crate::preview::binary::{encode_grid, GridDtype, HEADER_LEN};
+
+        let values = [0.5f32, -1.0, 2.0, 0.0];
+        let buf = encode_grid(&values, 2, -1.0, 2.0, GridDtype::F32);
+
+        assert_eq!(buf.len(), HEADER_LEN + 16);
+        assert_eq!(&buf[0..4], b"TNGR");
+        assert_eq!(buf[4], 1);
+        assert_eq!(buf[5], 0);
+        assert_eq!(u32::from_le_bytes(buf[8..12].try_into().unwrap()), 2);
+        assert_eq!(u32::from_le_bytes(buf[12..16].try_into().unwrap()), 4);
+        assert_eq!(f32::from_le_bytes(buf[16..20].try_into().unwrap()), -1.0);
+        assert_eq!(f32::from_le_bytes(buf[20..24].try_into().unwrap()), 2.0);
+
+        let decoded: Vec<f32> = buf[HEADER_LEN..]
+            .chunks_exact(4)
+            .map(|c| f32::from_le_bytes(c.try_into().unwrap()))
+            .collect();
+        assert_eq!(decoded, values);
+    }
+
+    #[test]
+    fn binary_u16_quantizes_over_min_max() {
+        use crate::preview::binary::{encode_grid, GridDtype, HEADER_LEN};
+
+        let buf = encode_grid(&[-1.0, 0.0, 1.0], 0, -1.0, 1.0, GridDtype::U16);
+        assert_eq!(buf[5], 1);
+        let decoded: Vec<u16> = buf[HEADER_LEN..]
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes(c.try_into().unwrap()))
+            .collect();
+        assert_eq!(decoded, vec![0, 32768, u16::MAX]);
+
+        let flat = encode_grid(&[3.0, 3.0], 0, 3.0, 3.0, GridDtype::U16);
+        assert_eq!(&flat[HEADER_LEN..], &[0, 0, 0, 0]);
+    }
+
+    // ── Preview jobs ──────────────────────────────────────────────────
+
+    #[test]
+    fn newer_job_supersedes_same_view() {
+        use crate::preview::jobs::PreviewJobs;
+        use std::sync::atomic::Ordering;
+
+        let jobs = PreviewJobs::default();
+        let (first, first_cancel) = jobs.start("main");
+        let (other, other_cancel) = jobs.start("side");
+        let (second, second_cancel) = jobs.start("main");
+
+        assert_ne!(first, second);
+        assert!(first_cancel.load(Ordering::Relaxed));
+        assert!(!second_cancel.load(Ordering::Relaxed));
+        assert!(!other_cancel.load(Ordering::Relaxed));
+        assert!(!jobs.cancel(first), "superseded job is deregistered");
+
+        assert!(jobs.cancel(other));
+        assert!(other_cancel.load(Ordering::Relaxed));
+        assert!(!jobs.cancel(other));
+
+        jobs.finish(second);
+        assert!(!jobs.cancel(second));
+    }
+
+    #[test]
+    fn job_levels_stream_coarse_to_fine_until_cancelled() {
+        use crate::noise::evaluator::DensityEvaluator;
+        use crate::preview::binary::{GridDtype, HEADER_LEN};
+        use crate::preview::jobs::{run_levels, GridSpec};
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let graph = serde_json::json!({ "Type": "SimplexNoise2D", "Scale": 16.0 });
+        let evaluator =
+            DensityEvaluator::from_json(&graph).expect("parse");
+        let spec = GridSpec {
+            range_min: -32.0,
+            range_max: 32.0,
+            y_level: 0.0,
+            dtype: GridDtype::F32,
+        };
+
+        let cancel = AtomicBool::new(false);
+        let mut sizes = Vec::new();
+        let emitted = run_levels(&evaluator, &spec, &[4, 8, 16], &cancel, |grid| {
+            sizes.push(grid.len());
+            true
+        });
+        assert_eq!(emitted, 3);
+        assert_eq!(sizes, vec![HEADER_LEN + 64, HEADER_LEN + 256, HEADER_LEN + 1024]);
+
+        // Cancelling after the first level stops before the next one is sent.
+        let emitted = run_levels(&evaluator, &spec, &[4, 8, 16], &cancel, |_| {
+            cancel.store(true, Ordering::Relaxed);
+            true
+        });
+        assert_eq!(emitted, 1);
+    }
+
+    // ── Contours ──────────────────────────────────────────────────────
+
+    #[test]
+    fn contour_around_peak_is_a_closed_loop() {
+        use crate::preview::contours::{extract, ContourOptions};
+
+        // 5x5 cone peaking in the centre.
+        let values: Vec<f32> = (0..25)
+            .map(|i| {
+                let (x, z) = ((i % 5) as f32 - 2.0, (i / 5) as f32 - 2.0);
+                2.0 - (x.abs() + z.abs())
+            })
+            .collect();
+        let options = ContourOptions {
+            levels: vec![0.5, 5.0],
+            svg: true,
+        };
+        let contours = extract(&values, 5, &options);
+
+        let loop_level = &contours.levels[0];
+        assert_eq!(loop_level.polylines.len(), 1);
+        let line = &loop_level.polylines[0];
+        assert_eq!(line.first(), line.last(), "closed loop repeats its start");
+        assert_eq!(line.len(), 13);
+        for [x, z] in line {
+            assert!(((x - 2.0).abs() + (z - 2.0).abs() - 1.5).abs() < 1e-9);
+        }
+
+        assert!(contours.levels[1].polylines.is_empty());
+        let svg = contours.svg.expect("svg requested");
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("<polyline").count(), 1);
+    }
+
+    #[test]
+    fn contour_crossing_the_border_stays_open() {
+        use crate::preview::contours::{extract, ContourOptions};
+
+        // Ramp along x: a single vertical line at x = 1.5.
+        let values: Vec<f32> = (0..16).map(|i| (i % 4) as f32).collect();
+        let options = ContourOptions {
+            levels: vec![1.5],
+            svg: false,
+        };
+        let contours = extract(&values, 4, &options);
+
+        let lines = &contours.levels[0].polylines;
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].len(), 4);
+        assert!(lines[0].iter().all(|[x, _]| *x == 1.5));
+        assert!(contours.svg.is_none());
+    }
+
+    // ── Tiles ─────────────────────────────────────────────────────────
+
+    fn tile_config(tile_size: u32) -> crate::preview::tiles::TileConfig {
+        crate::preview::tiles::TileConfig {
+            tile_size,
+            origin: [-32.0, -32.0],
+            extent: 64.0,
+            y_level: 0.0,
+        }
+    }
+
+    /// f32 payload of a binary grid.
+    fn grid_values(bytes: &[u8]) -> Vec<f32> {
+        bytes[crate::preview::binary::HEADER_LEN..]
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes(b.try_into().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn zoomed_tiles_match_quadrants_of_the_parent() {
+        use crate::preview::tiles::{TileLayer, TileMap};
+
+        let graph = serde_json::json!({ "Type": "SimplexNoise2D", "Scale": 16.0 });
+        let coarse = TileMap::new(&graph, TileLayer::Density, tile_config(16), None).unwrap();
+        let fine = TileMap::new(&graph, TileLayer::Density, tile_config(8), None).unwrap();
+        assert_ne!(coarse.id, fine.id);
+        assert_eq!(coarse.max_zoom(), 2);
+        assert_eq!(fine.max_zoom(), 3);
+
+        // Zoom 1 at 8 samples per tile has the same spacing as zoom 0 at 16.
+        let parent = grid_values(&coarse.tile(0, 0, 0).unwrap());
+        let child = grid_values(&fine.tile(1, 1, 0).unwrap());
+        for row in 0..8 {
+            assert_eq!(&child[row * 8..row * 8 + 8], &parent[row * 16 + 8..row * 16 + 16]);
+        }
+
+        assert!(fine.tile(4, 0, 0).is_err(), "beyond max zoom");
+        assert!(fine.tile(1, 2, 0).is_err(), "outside the map");
+    }
+
+    #[test]
+    fn biome_tiles_hold_range_indices() {
+        use crate::preview::tiles::{BiomeRange, TileLayer, TileMap};
+
+        let range = |biome: &str, min: f64, max: f64| BiomeRange {
+            biome: biome.to_string(),
+            min,
+            max,
+        };
+        let layer = TileLayer::Biomes {
+            ranges: vec![range("Desert", -1.0, 0.0), range("Forest", 0.0, 1.0)],
+        };
+        let graph = serde_json::json!({ "Type": "Constant", "Value": 0.5 });
+        let map = TileMap::new(&graph, layer, tile_config(4), None).unwrap();
+        assert_eq!(grid_values(&map.tile(0, 0, 0).unwrap()), vec![1.0; 16]);
+
+        let outside = serde_json::json!({ "Type": "Constant", "Value": 3.0 });
+        let layer = TileLayer::Biomes {
+            ranges: vec![range("Desert", -1.0, 0.0)],
+        };
+        let map = TileMap::new(&outside, layer, tile_config(4), None).unwrap();
+        assert_eq!(grid_values(&map.tile(0, 0, 0).unwrap()), vec![-1.0; 16]);
+    }
+
+    #[test]
+    fn tiles_are_cached_on_disk_per_graph_hash() {
+        use crate::preview::tiles::{TileLayer, TileMap, TileMaps};
+
+        let root = std::env::temp_dir().join(format!("terranova-tiles-{}", std::process::id()));
+        let graph = serde_json::json!({ "Type": "SimplexNoise2D", "Scale": 16.0 });
+        let map = TileMap::new(&graph, TileLayer::Density, tile_config(4), Some(&root)).unwrap();
+        let id = map.id.clone();
+
+        let rendered = map.tile(1, 0, 1).unwrap();
+        let path = root.join(&id).join("1/0/1.tngr");
+        assert_eq!(std::fs::read(&path).unwrap(), rendered);
+
+        // A reopened map with the same inputs serves the file from disk.
+        std::fs::write(&path, b"cached").unwrap();
+        let reopened =
+            TileMap::new(&graph, TileLayer::Density, tile_config(4), Some(&root)).unwrap();
+        assert_eq!(reopened.id, id);
+        assert_eq!(reopened.tile(1, 0, 1).unwrap(), b"cached");
+
+        let maps = TileMaps::default();
+        let opened = maps.open(reopened);
+        assert!(maps.get(&id).is_some_and(|m| std::sync::Arc::ptr_eq(&m, &opened)));
+        assert!(maps.get("missing").is_none());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn reading_a_map_keeps_it_open_over_newer_ones() {
+        use crate::preview::tiles::{TileLayer, TileMap, TileMaps};
+
+        let open = |maps: &TileMaps, scale: f64| {
+            let graph = serde_json::json!({ "Type": "SimplexNoise2D", "Scale": scale });
+            let map = TileMap::new(&graph, TileLayer::Density, tile_config(4), None).unwrap();
+            maps.open(map).id.clone()
+        };
+        let maps = TileMaps::default();
+        let first = open(&maps, 1.0);
+        let second = open(&maps, 2.0);
+        for scale in 3..=8 {
+            open(&maps, scale as f64);
+            assert!(maps.get(&first).is_some());
+        }
+        // Eight maps are open; a ninth evicts the least recently read one.
+        open(&maps, 9.0);
+        assert!(maps.get(&first).is_some());
+        assert!(maps.get(&second).is_none());
+    }
+
+    #[test]
+    fn disk_cache_drops_least_recently_opened_maps_over_budget() {
+        use crate::preview::tiles::prune_disk_cache;
+        use std::time::{Duration, SystemTime};
+
+        let root = std::env::temp_dir().join(format!("terranova-prune-{}", std::process::id()));
+        for (age, id) in [(30, "old"), (20, "mid"), (10, "new")] {
+            let dir = root.join(id).join("0/0");
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("0.tngr"), [0u8; 100]).unwrap();
+            let stamp = std::fs::File::create(root.join(id).join("last_used")).unwrap();
+            stamp
+                .set_modified(SystemTime::now() - Duration::from_secs(age))
+                .unwrap();
+        }
+
+        // The oldest map is kept when it is the one being opened.
+        prune_disk_cache(&root, "old", 200);
+        assert!(root.join("old").exists());
+        assert!(!root.join("mid").exists());
+        assert!(root.join("new").exists());
+
+        prune_disk_cache(&root, "new", 100);
+        assert!(!root.join("old").exists());
+        assert!(root.join("new").exists());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
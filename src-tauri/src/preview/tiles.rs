@@ -0,0 +1,311 @@
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::commands::io::write_atomic;
+use crate::noise::evaluator::DensityEvaluator;
+
+use super::binary::{self, GridDtype};
+
+/// Bumped whenever tile contents change for the same inputs, so stale disk
+/// caches are ignored rather than served.
+const TILE_FORMAT_VERSION: u32 = 1;
+/// Deepest zoom level accepted, whatever the extent.
+const MAX_ZOOM: u32 = 20;
+/// Tile maps kept parsed in memory; the least recently used are dropped
+/// (their disk tiles stay).
+const MAX_OPEN_MAPS: usize = 8;
+/// Disk budget for cached tiles across all maps. Opening a map deletes the
+/// least recently opened other maps' directories until the cache fits.
+const MAX_DISK_CACHE_BYTES: u64 = 512 * 1024 * 1024;
+/// File in a map's cache directory whose mtime records when it was last opened.
+const LAST_USED_FILE: &str = "last_used";
+
+/// One entry of a biome noise range, as stored in the asset pack.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct BiomeRange {
+    pub biome: String,
+    pub min: f64,
+    pub max: f64,
+}
+
+/// What a tile's samples hold.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum TileLayer {
+    /// Raw density values of the graph.
+    Density,
+    /// Index of the first range containing the graph's value, or -1 where
+    /// none does (the default biome).
+    Biomes { ranges: Vec<BiomeRange> },
+}
+
+/// Geometry shared by every zoom level of a map.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TileConfig {
+    /// Samples per tile edge
+    #[serde(default = "default_tile_size")]
+    pub tile_size: u32,
+    /// World x/z of the map's top-left corner
+    #[serde(default)]
+    pub origin: [f64; 2],
+    /// World width covered by the single zoom-0 tile
+    #[serde(default = "default_extent")]
+    pub extent: f64,
+    /// Y level for 2D evaluation
+    #[serde(default)]
+    pub y_level: f64,
+}
+
+fn default_tile_size() -> u32 {
+    256
+}
+
+fn default_extent() -> f64 {
+    10240.0
+}
+
+/// A parsed graph plus the pyramid it renders into.
+pub struct TileMap {
+    /// Stable hash of graph, layer and config; names the disk cache directory.
+    pub id: String,
+    evaluator: DensityEvaluator,
+    layer: TileLayer,
+    config: TileConfig,
+    cache_dir: Option<PathBuf>,
+}
+
+impl TileMap {
+    /// Parse `graph` for tiling. Tiles are cached under `cache_root/<id>` when
+    /// a root is given; the root is pruned to `MAX_DISK_CACHE_BYTES` first.
+    pub fn new(
+        graph: &Value,
+        layer: TileLayer,
+        config: TileConfig,
+        cache_root: Option<&Path>,
+    ) -> Result<Self, String> {
+        if config.tile_size == 0 || !config.extent.is_finite() || config.extent <= 0.0 {
+            return Err("Tile size and extent must be positive".to_string());
+        }
+        let evaluator =
+            DensityEvaluator::from_json(graph).map_err(|e| format!("Parse error: {}", e))?;
+
+        let id = format!("{:016x}", map_hash(graph, &layer, &config));
+        if let Some(root) = cache_root {
+            prune_disk_cache(root, &id, MAX_DISK_CACHE_BYTES);
+            let _ = write_atomic(&root.join(&id).join(LAST_USED_FILE), &[]);
+        }
+        Ok(TileMap {
+            cache_dir: cache_root.map(|root| root.join(&id)),
+            id,
+            evaluator,
+            layer,
+            config,
+        })
+    }
+
+    pub fn tile_size(&self) -> u32 {
+        self.config.tile_size
+    }
+
+    /// First zoom level where one sample covers at most one block.
+    pub fn max_zoom(&self) -> u32 {
+        let blocks_per_sample = self.config.extent / self.config.tile_size as f64;
+        (blocks_per_sample.log2().ceil().max(0.0) as u32).min(MAX_ZOOM)
+    }
+
+    /// Tile (x, y) at zoom `z` as a binary grid (see `preview::binary`).
+    ///
+    /// Served from the disk cache when present; freshly rendered tiles are
+    /// written back, but a failed cache write doesn't fail the request.
+    pub fn tile(&self, z: u32, x: u32, y: u32) -> Result<Vec<u8>, String> {
+        if z > self.max_zoom() {
+            return Err(format!(
+                "Zoom {} is beyond the maximum of {}",
+                z,
+                self.max_zoom()
+            ));
+        }
+        let tiles_per_side = 1u64 << z;
+        if x as u64 >= tiles_per_side || y as u64 >= tiles_per_side {
+            return Err(format!("Tile {}/{}/{} is outside the map", z, x, y));
+        }
+
+        let path = self
+            .cache_dir
+            .as_ref()
+            .map(|dir| dir.join(format!("{}/{}/{}.tngr", z, x, y)));
+        if let Some(bytes) = path.as_ref().and_then(|p| fs::read(p).ok()) {
+            return Ok(bytes);
+        }
+
+        let bytes = self.render(z, x, y);
+        if let Some(path) = path {
+            let _ = write_atomic(&path, &bytes);
+        }
+        Ok(bytes)
+    }
+
+    fn render(&self, z: u32, x: u32, y: u32) -> Vec<u8> {
+        let n = self.config.tile_size as usize;
+        let tile_world = self.config.extent / (1u64 << z) as f64;
+        let step = tile_world / n as f64;
+        let left = self.config.origin[0] + x as f64 * tile_world;
+        let top = self.config.origin[1] + y as f64 * tile_world;
+
+        let xs: Vec<f64> = (0..n).map(|i| left + (i as f64 + 0.5) * step).collect();
+        let ys = vec![self.config.y_level; n];
+        let mut zs = vec![0.0; n];
+        let mut row = vec![0.0; n];
+        let mut values = Vec::with_capacity(n * n);
+        let mut min_value = f32::MAX;
+        let mut max_value = f32::MIN;
+
+        for j in 0..n {
+            zs.fill(top + (j as f64 + 0.5) * step);
+            self.evaluator.evaluate_batch(&xs, &ys, &zs, &mut row);
+            for &val in &row {
+                let val = match &self.layer {
+                    TileLayer::Density => val as f32,
+                    TileLayer::Biomes { ranges } => ranges
+                        .iter()
+                        .position(|r| val >= r.min && val <= r.max)
+                        .map_or(-1.0, |i| i as f32),
+                };
+                min_value = min_value.min(val);
+                max_value = max_value.max(val);
+                values.push(val);
+            }
+        }
+
+        binary::encode_grid(
+            &values,
+            self.config.tile_size,
+            min_value,
+            max_value,
+            GridDtype::F32,
+        )
+    }
+}
+
+/// FNV-1a over the canonical JSON of the inputs. Unlike `DefaultHasher` it
+/// is stable across builds, which the disk cache relies on.
+fn map_hash(graph: &Value, layer: &TileLayer, config: &TileConfig) -> u64 {
+    let key = json!({
+        "version": TILE_FORMAT_VERSION,
+        "graph": graph,
+        "layer": layer,
+        "config": config,
+    });
+    serde_json::to_vec(&key)
+        .unwrap_or_default()
+        .iter()
+        .fold(0xcbf2_9ce4_8422_2325u64, |h, &b| {
+            (h ^ b as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        })
+}
+
+/// Delete map directories under `root`, least recently opened first, until
+/// the tiles there total at most `budget` bytes. The map `keep` is never
+/// deleted, even when it alone exceeds the budget. Errors are ignored: the
+/// cache is best effort.
+pub fn prune_disk_cache(root: &Path, keep: &str, budget: u64) {
+    let Ok(entries) = fs::read_dir(root) else {
+        return;
+    };
+    let mut maps: Vec<(SystemTime, u64, PathBuf)> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| {
+            let dir = entry.path();
+            let last_used = fs::metadata(dir.join(LAST_USED_FILE))
+                .or_else(|_| entry.metadata())
+                .and_then(|meta| meta.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH);
+            (last_used, dir_size(&dir), dir)
+        })
+        .collect();
+
+    let mut total: u64 = maps.iter().map(|(_, size, _)| size).sum();
+    maps.sort_by_key(|(last_used, _, _)| *last_used);
+    for (_, size, dir) in maps {
+        if total <= budget {
+            break;
+        }
+        if dir.file_name().is_some_and(|name| name == keep) {
+            continue;
+        }
+        if fs::remove_dir_all(&dir).is_ok() {
+            total -= size;
+        }
+    }
+}
+
+fn dir_size(dir: &Path) -> u64 {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(meta) if meta.is_dir() => dir_size(&entry.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+#[derive(Default)]
+struct MapTable {
+    maps: HashMap<String, Arc<TileMap>>,
+    order: VecDeque<String>,
+}
+
+/// Open tile maps by id, managed as Tauri state.
+#[derive(Default)]
+pub struct TileMaps(Mutex<MapTable>);
+
+impl MapTable {
+    /// Mark `id` as most recently used.
+    fn touch(&mut self, id: &str) {
+        if let Some(pos) = self.order.iter().position(|open| open == id) {
+            if let Some(id) = self.order.remove(pos) {
+                self.order.push_back(id);
+            }
+        }
+    }
+}
+
+impl TileMaps {
+    /// Register `map`, keeping an already open map with the same id.
+    pub fn open(&self, map: TileMap) -> Arc<TileMap> {
+        let mut table = self.0.lock().unwrap();
+        if let Some(existing) = table.maps.get(&map.id).cloned() {
+            table.touch(&map.id);
+            return existing;
+        }
+        while table.order.len() >= MAX_OPEN_MAPS {
+            if let Some(oldest) = table.order.pop_front() {
+                table.maps.remove(&oldest);
+            }
+        }
+        let map = Arc::new(map);
+        table.order.push_back(map.id.clone());
+        table.maps.insert(map.id.clone(), map.clone());
+        map
+    }
+
+    pub fn get(&self, id: &str) -> Option<Arc<TileMap>> {
+        let mut table = self.0.lock().unwrap();
+        let map = table.maps.get(id).cloned();
+        if map.is_some() {
+            table.touch(id);
+        }
+        map
+    }
+}
@@ -1,7 +1,8 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "Type")]
 pub enum AssignmentType {
     Constant {
@@ -21,7 +22,7 @@ pub enum AssignmentType {
     Weighted {
         #[serde(rename = "SkipChance", default)]
         skip_chance: f64,
-        #[serde(rename = "Seed", default)]
+        #[serde(rename = "Seed", default, deserialize_with = "super::seed")]
         seed: String,
         #[serde(rename = "WeightedAssignments", default)]
         weighted_assignments: Vec<Value>,
@@ -1,7 +1,8 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 #[serde(default, rename_all = "PascalCase")]
 pub struct BiomeAsset {
     pub name: String,
@@ -11,9 +12,12 @@ pub struct BiomeAsset {
     pub props: Vec<PropRuntimeAsset>,
     pub environment_provider: Option<Value>,
     pub tint_provider: Option<Value>,
+    // Editor form of a fluid layer, merged into the material provider on export
+    pub fluid_level: Option<f64>,
+    pub fluid_material: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 #[serde(default, rename_all = "PascalCase")]
 pub struct TerrainAsset {
     #[serde(rename = "Type")]
@@ -21,7 +25,7 @@ pub struct TerrainAsset {
     pub density: Option<Value>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 #[serde(default, rename_all = "PascalCase")]
 pub struct PropRuntimeAsset {
     pub runtime: i32,
@@ -1,8 +1,9 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 /// Block mask asset for controlling block placement.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 #[serde(default, rename_all = "PascalCase")]
 pub struct BlockMaskAsset {
     /// Materials that should not be placed
@@ -16,7 +17,7 @@ pub struct BlockMaskAsset {
 }
 
 /// Individual block mask entry with source→replacement mapping.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 #[serde(default, rename_all = "PascalCase")]
 pub struct BlockMaskEntryAsset {
     /// Source material set to match
@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -6,7 +7,7 @@ use serde_json::Value;
 /// Curves map decimal input values to decimal output values (f(x) = y),
 /// providing the basic mathematical building blocks for shaping terrain,
 /// controlling thickness, defining falloff, and more.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "Type")]
 pub enum CurveType {
     /// User-defined piecewise linear curve from plotted points.
@@ -18,6 +19,7 @@ pub enum CurveType {
 
     /// Exponential falloff from 1.0 at input 0 to 0.0 at the range.
     DistanceExponential {
+        #[schemars(default = "super::two")]
         #[serde(rename = "Exponent", default)]
         exponent: Option<f64>,
         #[serde(rename = "Range", default)]
@@ -154,6 +156,7 @@ pub enum CurveType {
 
     /// Imports a previously exported Curve by name.
     Imported {
+        #[schemars(default = "super::empty")]
         #[serde(rename = "Name", default)]
         name: Option<String>,
     },
@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -5,7 +6,7 @@ use serde_json::Value;
 ///
 /// Density functions define 3D decimal value fields used for terrain shaping,
 /// noise generation, mathematical operations, spatial transformations, and more.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "Type")]
 pub enum DensityType {
     // ── Noise Generators ──────────────────────────────────────────────
@@ -13,20 +14,35 @@ pub enum DensityType {
     /// 2D simplex noise in the x/z plane, outputs [-1, 1].
     /// Automatically caches per x/z column.
     SimplexNoise2D {
+        #[schemars(default = "super::two")]
         #[serde(rename = "Lacunarity", default)]
         lacunarity: Option<f64>,
         #[serde(rename = "Persistence", default)]
         persistence: Option<f64>,
         #[serde(rename = "Scale", default)]
         scale: Option<f64>,
+        #[schemars(default = "super::int::<1>")]
         #[serde(rename = "Octaves", default)]
         octaves: Option<i32>,
-        #[serde(rename = "Seed", default)]
+        #[schemars(default = "super::default_seed")]
+        #[serde(rename = "Seed", default, deserialize_with = "super::optional_seed")]
         seed: Option<String>,
+        // Editor form: Frequency becomes Scale, Gain Persistence, and
+        // Amplitude is dropped on export
+        #[schemars(default = "super::frequency")]
+        #[serde(rename = "Frequency", default)]
+        frequency: Option<f64>,
+        #[schemars(default = "super::half")]
+        #[serde(rename = "Gain", default)]
+        gain: Option<f64>,
+        #[schemars(default = "super::one")]
+        #[serde(rename = "Amplitude", default)]
+        amplitude: Option<f64>,
     },
 
     /// 3D simplex noise in x/y/z space, outputs [-1, 1].
     SimplexNoise3D {
+        #[schemars(default = "super::two")]
         #[serde(rename = "Lacunarity", default)]
         lacunarity: Option<f64>,
         #[serde(rename = "Persistence", default)]
@@ -35,17 +51,40 @@ pub enum DensityType {
         scale_xz: Option<f64>,
         #[serde(rename = "ScaleY", default)]
         scale_y: Option<f64>,
+        #[schemars(default = "super::int::<1>")]
         #[serde(rename = "Octaves", default)]
         octaves: Option<i32>,
-        #[serde(rename = "Seed", default)]
+        #[schemars(default = "super::default_seed")]
+        #[serde(rename = "Seed", default, deserialize_with = "super::optional_seed")]
         seed: Option<String>,
+        // Editor form: Frequency becomes ScaleXZ and ScaleY, Gain Persistence, and
+        // Amplitude is dropped on export
+        #[schemars(default = "super::frequency")]
+        #[serde(rename = "Frequency", default)]
+        frequency: Option<f64>,
+        #[schemars(default = "super::half")]
+        #[serde(rename = "Gain", default)]
+        gain: Option<f64>,
+        #[schemars(default = "super::one")]
+        #[serde(rename = "Amplitude", default)]
+        amplitude: Option<f64>,
     },
 
     /// 2D cell/Worley noise.
     CellNoise2D {
         #[serde(rename = "Scale", default)]
         scale: Option<f64>,
-        #[serde(rename = "Seed", default)]
+        #[serde(rename = "ScaleX", default)]
+        scale_x: Option<f64>,
+        #[serde(rename = "ScaleZ", default)]
+        scale_z: Option<f64>,
+        #[serde(rename = "CellType", default)]
+        cell_type: Option<String>,
+        #[serde(rename = "Jitter", default)]
+        jitter: Option<f64>,
+        #[serde(rename = "Octaves", default)]
+        octaves: Option<i32>,
+        #[serde(rename = "Seed", default, deserialize_with = "super::optional_seed")]
         seed: Option<String>,
         #[serde(rename = "ReturnType", default)]
         return_type: Option<String>,
@@ -57,7 +96,19 @@ pub enum DensityType {
     CellNoise3D {
         #[serde(rename = "Scale", default)]
         scale: Option<f64>,
-        #[serde(rename = "Seed", default)]
+        #[serde(rename = "ScaleX", default)]
+        scale_x: Option<f64>,
+        #[serde(rename = "ScaleY", default)]
+        scale_y: Option<f64>,
+        #[serde(rename = "ScaleZ", default)]
+        scale_z: Option<f64>,
+        #[serde(rename = "CellType", default)]
+        cell_type: Option<String>,
+        #[serde(rename = "Jitter", default)]
+        jitter: Option<f64>,
+        #[serde(rename = "Octaves", default)]
+        octaves: Option<i32>,
+        #[serde(rename = "Seed", default, deserialize_with = "super::optional_seed")]
         seed: Option<String>,
         #[serde(rename = "ReturnType", default)]
         return_type: Option<String>,
@@ -69,6 +120,7 @@ pub enum DensityType {
 
     /// Outputs a constant value.
     Constant {
+        #[schemars(default = "super::zero")]
         #[serde(rename = "Value", default)]
         value: Option<f64>,
     },
@@ -77,6 +129,11 @@ pub enum DensityType {
     Sum {
         #[serde(rename = "Inputs", default)]
         inputs: Vec<Value>,
+        // Editor form of a two-input sum, flattened into Inputs on export
+        #[serde(rename = "InputA", default)]
+        input_a: Option<Value>,
+        #[serde(rename = "InputB", default)]
+        input_b: Option<Value>,
     },
 
     /// Output is the product of all inputs. Short-circuits on zero.
@@ -105,6 +162,7 @@ pub enum DensityType {
 
     /// Input raised to the given exponent.
     Pow {
+        #[schemars(default = "super::two")]
         #[serde(rename = "Exponent", default)]
         exponent: Option<f64>,
         #[serde(rename = "Input", default)]
@@ -123,6 +181,9 @@ pub enum DensityType {
     AmplitudeConstant {
         #[serde(rename = "Amplitude", default)]
         amplitude: Option<f64>,
+        #[schemars(default = "super::one")]
+        #[serde(rename = "Value", default)]
+        value: Option<f64>,
         #[serde(rename = "Input", default)]
         input: Option<Value>,
     },
@@ -135,6 +196,13 @@ pub enum DensityType {
         wall_a: Option<f64>,
         #[serde(rename = "WallB", default)]
         wall_b: Option<f64>,
+        // Editor form of WallA and WallB
+        #[schemars(default = "super::zero")]
+        #[serde(rename = "Min", default)]
+        min: Option<f64>,
+        #[schemars(default = "super::one")]
+        #[serde(rename = "Max", default)]
+        max: Option<f64>,
         #[serde(rename = "Input", default)]
         input: Option<Value>,
     },
@@ -229,6 +297,13 @@ pub enum DensityType {
         to_min: Option<f64>,
         #[serde(rename = "ToMax", default)]
         to_max: Option<f64>,
+        // Editor form: {Min, Max} ranges flattened into From*/To* on export
+        #[schemars(default = "super::range::<{-1}, 1>")]
+        #[serde(rename = "SourceRange", default)]
+        source_range: Option<Value>,
+        #[schemars(default = "super::range::<0, 1>")]
+        #[serde(rename = "TargetRange", default)]
+        target_range: Option<Value>,
         #[serde(rename = "Input", default)]
         input: Option<Value>,
     },
@@ -267,6 +342,7 @@ pub enum DensityType {
 
     /// Mixes multiple density fields using keys and a gauge.
     MultiMix {
+        #[schemars(default = "mix_keys")]
         #[serde(rename = "Keys", default)]
         keys: Vec<Value>,
         #[serde(rename = "Inputs", default)]
@@ -329,6 +405,8 @@ pub enum DensityType {
         input: Option<Value>,
         #[serde(rename = "Override", default)]
         override_value: Option<Value>,
+        #[serde(rename = "Value", default)]
+        value: Option<f64>,
     },
 
     /// Overrides the Y coordinate the input sees.
@@ -337,6 +415,8 @@ pub enum DensityType {
         input: Option<Value>,
         #[serde(rename = "Override", default)]
         override_value: Option<Value>,
+        #[serde(rename = "Value", default)]
+        value: Option<f64>,
     },
 
     /// Overrides the Z coordinate the input sees.
@@ -345,18 +425,23 @@ pub enum DensityType {
         input: Option<Value>,
         #[serde(rename = "Override", default)]
         override_value: Option<Value>,
+        #[serde(rename = "Value", default)]
+        value: Option<f64>,
     },
 
     // ── Warping ──────────────────────────────────────────────────────
 
     /// Gradient-based warp using a second density field.
     GradientWarp {
+        #[schemars(default = "super::one")]
         #[serde(rename = "SampleRange", default)]
         sample_range: Option<f64>,
+        #[schemars(default = "super::one")]
         #[serde(rename = "WarpFactor", default)]
         warp_factor: Option<f64>,
         #[serde(rename = "2D", default)]
         is_2d: Option<bool>,
+        #[schemars(default = "super::zero")]
         #[serde(rename = "YFor2D", default)]
         y_for_2d: Option<f64>,
         #[serde(rename = "Inputs", default)]
@@ -365,17 +450,22 @@ pub enum DensityType {
 
     /// Fast gradient warp using an internal simplex noise generator.
     FastGradientWarp {
+        #[schemars(default = "super::frequency")]
         #[serde(rename = "WarpScale", default)]
         warp_scale: Option<f64>,
+        #[schemars(default = "super::two")]
         #[serde(rename = "WarpLacunarity", default)]
         warp_lacunarity: Option<f64>,
+        #[schemars(default = "super::half")]
         #[serde(rename = "WarpPersistence", default)]
         warp_persistence: Option<f64>,
+        #[schemars(default = "super::int::<3>")]
         #[serde(rename = "WarpOctaves", default)]
         warp_octaves: Option<i32>,
+        #[schemars(default = "super::one")]
         #[serde(rename = "WarpFactor", default)]
         warp_factor: Option<f64>,
-        #[serde(rename = "Seed", default)]
+        #[serde(rename = "Seed", default, deserialize_with = "super::optional_seed")]
         seed: Option<String>,
         #[serde(rename = "2D", default)]
         is_2d: Option<bool>,
@@ -385,6 +475,7 @@ pub enum DensityType {
 
     /// Warps input along a vector direction by the second density field.
     VectorWarp {
+        #[schemars(default = "super::one")]
         #[serde(rename = "WarpFactor", default)]
         warp_factor: Option<f64>,
         #[serde(rename = "WarpVector", default)]
@@ -417,6 +508,7 @@ pub enum DensityType {
     Ellipsoid {
         #[serde(rename = "Curve", default)]
         curve: Option<Value>,
+        #[schemars(default = "super::vector::<1, 1, 1>")]
         #[serde(rename = "Scale", default)]
         scale: Option<Value>,
         #[serde(rename = "X", default)]
@@ -433,6 +525,7 @@ pub enum DensityType {
     Cuboid {
         #[serde(rename = "Curve", default)]
         curve: Option<Value>,
+        #[schemars(default = "super::vector::<1, 1, 1>")]
         #[serde(rename = "Scale", default)]
         scale: Option<Value>,
         #[serde(rename = "X", default)]
@@ -443,6 +536,7 @@ pub enum DensityType {
         z: Option<f64>,
         #[serde(rename = "Spin", default)]
         spin: Option<f64>,
+        #[schemars(default = "super::vector::<0, 1, 0>")]
         #[serde(rename = "NewYAxis", default)]
         new_y_axis: Option<Value>,
     },
@@ -455,6 +549,7 @@ pub enum DensityType {
         radial_curve: Option<Value>,
         #[serde(rename = "Spin", default)]
         spin: Option<f64>,
+        #[schemars(default = "super::vector::<0, 1, 0>")]
         #[serde(rename = "NewYAxis", default)]
         new_y_axis: Option<Value>,
     },
@@ -475,6 +570,7 @@ pub enum DensityType {
 
     /// Density from distance to an axis line through the origin.
     Axis {
+        #[schemars(default = "super::vector::<0, 1, 0>")]
         #[serde(rename = "Axis", default)]
         axis: Option<Value>,
         #[serde(rename = "X", default)]
@@ -485,12 +581,14 @@ pub enum DensityType {
         z: Option<f64>,
         #[serde(rename = "Curve", default)]
         curve: Option<Value>,
+        #[schemars(default = "super::flag::<false>")]
         #[serde(rename = "IsAnchored", default)]
         is_anchored: Option<bool>,
     },
 
     /// Shell shape defined by angle and distance curves.
     Shell {
+        #[schemars(default = "super::vector::<0, 1, 0>")]
         #[serde(rename = "Axis", default)]
         axis: Option<Value>,
         #[serde(rename = "X", default)]
@@ -499,6 +597,7 @@ pub enum DensityType {
         y: Option<f64>,
         #[serde(rename = "Z", default)]
         z: Option<f64>,
+        #[schemars(default = "super::flag::<false>")]
         #[serde(rename = "Mirror", default)]
         mirror: Option<bool>,
         #[serde(rename = "AngleCurve", default)]
@@ -509,6 +608,7 @@ pub enum DensityType {
 
     /// Angle in degrees between two vectors.
     Angle {
+        #[schemars(default = "super::vector::<0, 1, 0>")]
         #[serde(rename = "Vector", default)]
         vector: Option<Value>,
         #[serde(rename = "VectorProvider", default)]
@@ -533,8 +633,10 @@ pub enum DensityType {
 
     /// Reference a BaseHeight from the WorldStructure.
     BaseHeight {
+        #[schemars(default = "base_height_name")]
         #[serde(rename = "BaseHeightName", default)]
         base_height_name: Option<String>,
+        #[schemars(default = "super::flag::<false>")]
         #[serde(rename = "Distance", default)]
         distance: Option<bool>,
     },
@@ -556,8 +658,10 @@ pub enum DensityType {
         from: Option<f64>,
         #[serde(rename = "To", default)]
         to: Option<f64>,
+        #[schemars(default = "super::zero")]
         #[serde(rename = "FromY", default)]
         from_y: Option<f64>,
+        #[schemars(default = "world_height")]
         #[serde(rename = "ToY", default)]
         to_y: Option<f64>,
     },
@@ -674,6 +778,7 @@ pub enum DensityType {
 
     /// Exports a density field for reuse (optionally as a single instance).
     Exported {
+        #[schemars(default = "super::flag::<false>")]
         #[serde(rename = "SingleInstance", default)]
         single_instance: Option<bool>,
         #[serde(rename = "Density", default)]
@@ -696,3 +801,15 @@ pub enum DensityType {
         input: Option<Value>,
     },
 }
+
+fn base_height_name() -> Option<String> {
+    Some("Base".to_string())
+}
+
+fn world_height() -> Option<f64> {
+    Some(256.0)
+}
+
+fn mix_keys() -> Vec<Value> {
+    vec![Value::from(0), Value::from(1)]
+}
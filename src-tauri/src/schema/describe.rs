@@ -0,0 +1,404 @@
+use schemars::schema::{InstanceType, RootSchema, Schema, SchemaObject, SingleOrVec};
+use schemars::{schema_for, JsonSchema, Map};
+use serde::Serialize;
+use serde_json::Value;
+
+use super::assignments::AssignmentType;
+use super::biome::{BiomeAsset, TerrainAsset};
+use super::block_masks::BlockMaskAsset;
+use super::curves::CurveType;
+use super::density::DensityType;
+use super::environment::{EnvironmentProviderType, TintProviderType};
+use super::framework::FrameworkType;
+use super::material::{ConditionType, LayerType, MaterialProviderType};
+use super::patterns::PatternType;
+use super::positions::PositionProviderType;
+use super::props::{DirectionalityType, PropType};
+use super::scanners::ScannerType;
+use super::settings::SettingsAsset;
+use super::slots::{child_slots, known_types, Slot};
+use super::validation::{field_rules, Rule};
+use super::vectors::VectorProviderType;
+use super::versions;
+use super::world_structure::WorldStructureAsset;
+use super::AssetCategory::{self, *};
+
+/// The schema the Rust definitions describe, for the frontend to build its
+/// node palette, inspectors and defaults from.
+#[derive(Debug, Clone, Serialize)]
+pub struct SchemaDescription {
+    /// Server version the schema was taken from.
+    pub version: &'static str,
+    pub categories: Vec<CategoryDescription>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CategoryDescription {
+    pub category: AssetCategory,
+    pub doc: Option<String>,
+    /// One entry per known type; a single unnamed one for untyped categories.
+    pub types: Vec<TypeDescription>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TypeDescription {
+    /// The `Type` discriminator, or `None` for untyped categories.
+    pub name: Option<String>,
+    pub doc: Option<String>,
+    pub fields: Vec<FieldDescription>,
+    /// Fields holding nested assets and the categories they expect.
+    pub slots: Vec<SlotDescription>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldDescription {
+    /// Name as written in JSON.
+    pub name: String,
+    pub doc: Option<String>,
+    #[serde(flatten)]
+    pub value: ValueKind,
+    pub required: bool,
+    /// Value a new node starts with, or that a missing field reads as.
+    pub default: Option<Value>,
+    pub minimum: Option<f64>,
+    pub exclusive_minimum: Option<f64>,
+    pub min_items: Option<usize>,
+}
+
+/// Kind of value a field holds.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum ValueKind {
+    Number,
+    Integer,
+    String,
+    Boolean,
+    /// Any JSON; nested assets are described by the type's slots.
+    Json,
+    Array {
+        items: Box<ValueKind>,
+    },
+    /// A plain object without a `Type`.
+    Object {
+        fields: Vec<FieldDescription>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SlotDescription {
+    pub field: &'static str,
+    #[serde(flatten)]
+    pub slot: SlotKind,
+}
+
+/// Serializable form of `Slot`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum SlotKind {
+    One { category: AssetCategory },
+    Many { category: AssetCategory },
+    Object { slots: Vec<SlotDescription> },
+    Objects { slots: Vec<SlotDescription> },
+}
+
+/// Each category and the JSON schema of the enum or struct declaring its types.
+const CATEGORIES: &[(AssetCategory, SchemaOf)] = &[
+    (Density, root::<DensityType>),
+    (Curve, root::<CurveType>),
+    (Pattern, root::<PatternType>),
+    (MaterialProvider, root::<MaterialProviderType>),
+    (Layer, root::<LayerType>),
+    (Condition, root::<ConditionType>),
+    (PositionProvider, root::<PositionProviderType>),
+    (Prop, root::<PropType>),
+    (Directionality, root::<DirectionalityType>),
+    (Scanner, root::<ScannerType>),
+    (Assignment, root::<AssignmentType>),
+    (VectorProvider, root::<VectorProviderType>),
+    (EnvironmentProvider, root::<EnvironmentProviderType>),
+    (TintProvider, root::<TintProviderType>),
+    (BlockMask, root::<BlockMaskAsset>),
+    (Framework, root::<FrameworkType>),
+    (WorldStructure, root::<WorldStructureAsset>),
+    (Biome, root::<BiomeAsset>),
+    (Settings, root::<SettingsAsset>),
+];
+
+/// Types of a struct category declared by a struct other than the category's.
+const STRUCT_TYPES: &[(&str, SchemaOf)] = &[("DAOTerrain", root::<TerrainAsset>)];
+
+/// Generates the JSON schema of the Rust type declaring a category.
+type SchemaOf = fn() -> RootSchema;
+
+fn root<T: JsonSchema>() -> RootSchema {
+    schema_for!(T)
+}
+
+/// Describe every category and type of the schema.
+///
+/// Fields, kinds, docs and defaults come from the types' `JsonSchema`
+/// derives, slots from `child_slots` and constraints from the validation
+/// rules.
+pub fn describe_schema() -> SchemaDescription {
+    let category_docs = root::<AssetCategory>();
+
+    let categories = CATEGORIES
+        .iter()
+        .map(|&(category, schema)| {
+            let schema = schema();
+            let name = format!("{:?}", category);
+            let doc = variants(&category_docs.schema)
+                .find(|variant| {
+                    variant
+                        .enum_values
+                        .iter()
+                        .flatten()
+                        .any(|value| *value == name)
+                })
+                .and_then(doc_of)
+                .or_else(|| doc_of(&schema.schema));
+
+            let types = if let Some(one_of) = one_of(&schema.schema) {
+                known_types(category)
+                    .iter()
+                    .map(|&type_name| {
+                        let variant = one_of
+                            .iter()
+                            .copied()
+                            .find(|variant| tag(variant) == Some(type_name));
+                        describe_type(
+                            category,
+                            Some(type_name),
+                            variant.and_then(doc_of),
+                            variant
+                                .map(|v| type_fields(&schema.definitions, v))
+                                .unwrap_or_default(),
+                        )
+                    })
+                    .collect()
+            } else if known_types(category).is_empty() {
+                let fields = fields_of(&schema.definitions, &schema.schema);
+                vec![describe_type(
+                    category,
+                    None,
+                    doc_of(&schema.schema),
+                    fields,
+                )]
+            } else {
+                // Typed structs, i.e. world structures, declare `Type` as a field
+                known_types(category)
+                    .iter()
+                    .map(|&type_name| {
+                        let schema = match STRUCT_TYPES.iter().find(|(name, _)| *name == type_name)
+                        {
+                            Some((_, schema)) => schema(),
+                            None => schema.clone(),
+                        };
+                        let fields = type_fields(&schema.definitions, &schema.schema);
+                        describe_type(category, Some(type_name), doc_of(&schema.schema), fields)
+                    })
+                    .collect()
+            };
+            CategoryDescription {
+                category,
+                doc,
+                types,
+            }
+        })
+        .collect();
+
+    SchemaDescription {
+        version: versions::CURRENT,
+        categories,
+    }
+}
+
+fn describe_type(
+    category: AssetCategory,
+    type_name: Option<&str>,
+    doc: Option<String>,
+    mut fields: Vec<FieldDescription>,
+) -> TypeDescription {
+    for (name, rule) in field_rules(category, type_name.unwrap_or(""), versions::CURRENT) {
+        let Some(field) = fields.iter_mut().find(|f| f.name == *name) else {
+            continue;
+        };
+        match *rule {
+            Rule::Required(default) => {
+                field.required = true;
+                // The declared default wins over the one fixes insert
+                if let (None, Some(default)) = (&field.default, default) {
+                    field.default = serde_json::from_str(default).ok();
+                }
+            }
+            Rule::Positive => field.exclusive_minimum = Some(0.0),
+            Rule::AtLeast(min) => field.minimum = Some(min as f64),
+            Rule::MinItems(min) => field.min_items = Some(min),
+        }
+    }
+
+    TypeDescription {
+        name: type_name.map(str::to_string),
+        doc,
+        fields,
+        slots: describe_slots(child_slots(category, type_name.unwrap_or(""))),
+    }
+}
+
+fn describe_slots(slots: &[(&'static str, Slot)]) -> Vec<SlotDescription> {
+    slots
+        .iter()
+        .map(|&(field, slot)| SlotDescription {
+            field,
+            slot: match slot {
+                Slot::One(category) => SlotKind::One { category },
+                Slot::Many(category) => SlotKind::Many { category },
+                Slot::Object(slots) => SlotKind::Object {
+                    slots: describe_slots(slots),
+                },
+                Slot::Objects(slots) => SlotKind::Objects {
+                    slots: describe_slots(slots),
+                },
+            },
+        })
+        .collect()
+}
+
+/// Fields of a typed asset, without its `Type`.
+fn type_fields(definitions: &Map<String, Schema>, object: &SchemaObject) -> Vec<FieldDescription> {
+    let mut fields = fields_of(definitions, object);
+    fields.retain(|field| field.name != "Type");
+    fields
+}
+
+/// Describe the properties of an object schema.
+fn fields_of(definitions: &Map<String, Schema>, object: &SchemaObject) -> Vec<FieldDescription> {
+    let Some(validation) = &object.object else {
+        return Vec::new();
+    };
+    validation
+        .properties
+        .iter()
+        .map(|(name, schema)| FieldDescription {
+            name: name.clone(),
+            doc: match schema {
+                Schema::Object(object) => doc_of(object),
+                Schema::Bool(_) => None,
+            },
+            value: value_kind(definitions, schema),
+            required: validation.required.contains(name),
+            default: default_of(schema),
+            minimum: None,
+            exclusive_minimum: None,
+            min_items: None,
+        })
+        .collect()
+}
+
+fn value_kind(definitions: &Map<String, Schema>, schema: &Schema) -> ValueKind {
+    let Schema::Object(object) = schema else {
+        return ValueKind::Json;
+    };
+    if let Some(reference) = &object.reference {
+        let name = reference.trim_start_matches("#/definitions/");
+        return match definitions.get(name) {
+            Some(schema) => value_kind(definitions, schema),
+            None => ValueKind::Json,
+        };
+    }
+    // `Option` of a referenced type is `anyOf` the type and null
+    if let Some(any_of) = object.subschemas.as_ref().and_then(|s| s.any_of.as_ref()) {
+        let mut inner = any_of.iter().filter(|schema| !is_null(schema));
+        return match (inner.next(), inner.next()) {
+            (Some(schema), None) => value_kind(definitions, schema),
+            _ => ValueKind::Json,
+        };
+    }
+    match instance_type(object) {
+        Some(InstanceType::Number) => ValueKind::Number,
+        Some(InstanceType::Integer) => ValueKind::Integer,
+        Some(InstanceType::String) => ValueKind::String,
+        Some(InstanceType::Boolean) => ValueKind::Boolean,
+        Some(InstanceType::Array) => ValueKind::Array {
+            items: Box::new(match object.array.as_ref().and_then(|a| a.items.as_ref()) {
+                Some(SingleOrVec::Single(items)) => value_kind(definitions, items),
+                _ => ValueKind::Json,
+            }),
+        },
+        // Maps have no declared properties and stay plain JSON
+        Some(InstanceType::Object)
+            if object
+                .object
+                .as_ref()
+                .is_some_and(|o| !o.properties.is_empty()) =>
+        {
+            ValueKind::Object {
+                fields: fields_of(definitions, object),
+            }
+        }
+        _ => ValueKind::Json,
+    }
+}
+
+/// The one non-null type of `object`, unwrapping `Option` primitives.
+fn instance_type(object: &SchemaObject) -> Option<InstanceType> {
+    match object.instance_type.as_ref()? {
+        SingleOrVec::Single(instance) => Some(**instance),
+        SingleOrVec::Vec(instances) => {
+            let mut types = instances.iter().filter(|t| **t != InstanceType::Null);
+            match (types.next(), types.next()) {
+                (Some(instance), None) => Some(*instance),
+                _ => None,
+            }
+        }
+    }
+}
+
+fn is_null(schema: &Schema) -> bool {
+    matches!(schema, Schema::Object(object) if instance_type(object) == Some(InstanceType::Null))
+}
+
+/// Variants of an enum schema: its `oneOf` alternatives.
+fn one_of(schema: &SchemaObject) -> Option<Vec<&SchemaObject>> {
+    let one_of = schema.subschemas.as_ref()?.one_of.as_ref()?;
+    Some(
+        one_of
+            .iter()
+            .filter_map(|variant| match variant {
+                Schema::Object(object) => Some(object),
+                Schema::Bool(_) => None,
+            })
+            .collect(),
+    )
+}
+
+fn variants(schema: &SchemaObject) -> impl Iterator<Item = &SchemaObject> {
+    one_of(schema).into_iter().flatten()
+}
+
+/// The `Type` an internally tagged variant is written with.
+fn tag(variant: &SchemaObject) -> Option<&str> {
+    match variant.object.as_ref()?.properties.get("Type")? {
+        Schema::Object(tag) => tag.enum_values.as_ref()?.first()?.as_str(),
+        Schema::Bool(_) => None,
+    }
+}
+
+/// Declared default of a field. Unset optional fields report `null`, which
+/// isn't a default.
+fn default_of(schema: &Schema) -> Option<Value> {
+    match schema {
+        Schema::Object(object) => object
+            .metadata
+            .as_ref()?
+            .default
+            .clone()
+            .filter(|default| !default.is_null()),
+        Schema::Bool(_) => None,
+    }
+}
+
+/// Doc comment of a type, variant or field.
+fn doc_of(schema: &SchemaObject) -> Option<String> {
+    schema.metadata.as_ref()?.description.clone()
+}
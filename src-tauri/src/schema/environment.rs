@@ -1,8 +1,9 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 /// Environment provider types (2 types).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "Type")]
 pub enum EnvironmentProviderType {
     Constant {
@@ -18,10 +19,11 @@ pub enum EnvironmentProviderType {
 }
 
 /// Tint provider types (2 types).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "Type")]
 pub enum TintProviderType {
     Constant {
+        #[schemars(default = "white")]
         #[serde(rename = "Color", default)]
         color: String,
     },
@@ -32,3 +34,7 @@ pub enum TintProviderType {
         delimiters: Vec<Value>,
     },
 }
+
+fn white() -> String {
+    "#ffffff".to_string()
+}
@@ -1,8 +1,9 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 /// Framework types (2 types).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "Type")]
 pub enum FrameworkType {
     DecimalConstants {
@@ -16,7 +17,7 @@ pub enum FrameworkType {
 }
 
 /// Named decimal constant entry.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 #[serde(default, rename_all = "PascalCase")]
 pub struct DecimalConstantEntry {
     pub name: String,
@@ -24,7 +25,7 @@ pub struct DecimalConstantEntry {
 }
 
 /// Named position provider entry.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 #[serde(default, rename_all = "PascalCase")]
 pub struct PositionEntry {
     pub name: String,
@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::validation::{DiagnosticCode, Severity, ValidationError};
+use super::versions;
+
+/// Lint config file name, looked up next to the project's `manifest.json`.
+pub const CONFIG_FILE: &str = "terranova.lint.json";
+
+/// Node key listing codes suppressed on the node and everything inside it,
+/// e.g. `"$Suppress": ["UNKNOWN_TYPE"]`. Like `$NodeId`, the game ignores it.
+pub const SUPPRESS_KEY: &str = "$Suppress";
+
+/// Configured level of a rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleLevel {
+    Error,
+    Warning,
+    Info,
+    Off,
+}
+
+/// Rules disabled for a set of files.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Ignore {
+    /// Glob patterns relative to the config's directory. `*` and `?` stay
+    /// within a path segment, `**` spans segments, and a plain directory
+    /// matches everything under it.
+    pub paths: Vec<String>,
+    /// Codes to disable; all of them when empty.
+    #[serde(default)]
+    pub codes: Vec<DiagnosticCode>,
+}
+
+/// Per-project lint settings from `terranova.lint.json`:
+///
+/// ```json
+/// {
+///   "rules": { "UNRESOLVED_IMPORT": "error", "BAND_GAP": "off" },
+///   "ignore": [{ "paths": ["HytaleGenerator/Density/Drafts"], "codes": ["UNKNOWN_TYPE"] }]
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintConfig {
+    /// Severity override, or `off`, per code.
+    #[serde(default)]
+    pub rules: HashMap<DiagnosticCode, RuleLevel>,
+    #[serde(default)]
+    pub ignore: Vec<Ignore>,
+    /// The validated pack's path relative to the config's directory, so
+    /// `ignore` paths can be matched against pack-relative files.
+    #[serde(skip)]
+    base: String,
+}
+
+impl LintConfig {
+    /// Load the config of the project containing `pack`, i.e. the nearest
+    /// directory at or above it with a `manifest.json`. `Ok(None)` when
+    /// there is no such project or it has no config.
+    pub fn load(pack: &Path) -> Result<Option<LintConfig>, String> {
+        let Some(root) = versions::project_root(pack) else {
+            return Ok(None);
+        };
+        let path = root.join(CONFIG_FILE);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let content =
+            fs::read_to_string(&path).map_err(|e| format!("Cannot read {}: {}", CONFIG_FILE, e))?;
+        let mut config: LintConfig = serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid {}: {}", CONFIG_FILE, e))?;
+        config.base = pack
+            .strip_prefix(root)
+            .unwrap_or(Path::new(""))
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        Ok(Some(config))
+    }
+
+    /// True if an `ignore` entry disables `code` for the pack-relative `file`.
+    fn ignores(&self, code: DiagnosticCode, file: &str) -> bool {
+        let file = file.replace('\\', "/");
+        let file = if self.base.is_empty() {
+            file
+        } else {
+            format!("{}/{}", self.base, file)
+        };
+        self.ignore.iter().any(|ignore| {
+            (ignore.codes.is_empty() || ignore.codes.contains(&code))
+                && ignore
+                    .paths
+                    .iter()
+                    .any(|pattern| path_matches(pattern, &file))
+        })
+    }
+}
+
+/// Apply `config` and inline `$Suppress` keys to the errors of a pack.
+///
+/// `files` holds every parsed JSON file of the pack with its relative path.
+pub fn apply_lint(
+    errors: Vec<ValidationError>,
+    config: &LintConfig,
+    files: &[(String, Value)],
+) -> Vec<ValidationError> {
+    let files: HashMap<&str, &Value> = files.iter().map(|(f, v)| (f.as_str(), v)).collect();
+    errors
+        .into_iter()
+        .filter_map(|mut error| {
+            let (file, pointer) = error.path.split_once('#').unwrap_or((&error.path, ""));
+            if config.ignores(error.code, file) {
+                return None;
+            }
+            if let Some(value) = files.get(file) {
+                if suppressed(value, pointer, error.code) {
+                    return None;
+                }
+            }
+            match config.rules.get(&error.code) {
+                Some(RuleLevel::Off) => return None,
+                Some(RuleLevel::Error) => error.severity = Severity::Error,
+                Some(RuleLevel::Warning) => error.severity = Severity::Warning,
+                Some(RuleLevel::Info) => error.severity = Severity::Info,
+                None => {}
+            }
+            Some(error)
+        })
+        .collect()
+}
+
+/// True if the value at `pointer` or any object containing it suppresses `code`.
+fn suppressed(root: &Value, pointer: &str, code: DiagnosticCode) -> bool {
+    let Ok(Value::String(code)) = serde_json::to_value(code) else {
+        return false;
+    };
+    let suppresses = |value: &Value| {
+        value
+            .get(SUPPRESS_KEY)
+            .and_then(|v| v.as_array())
+            .is_some_and(|codes| codes.iter().any(|c| c.as_str() == Some(&code)))
+    };
+
+    let mut value = root;
+    if suppresses(value) {
+        return true;
+    }
+    for token in pointer.split('/').skip(1) {
+        let token = token.replace("~1", "/").replace("~0", "~");
+        let next = match value {
+            Value::Object(obj) => obj.get(&token),
+            Value::Array(arr) => token.parse::<usize>().ok().and_then(|i| arr.get(i)),
+            _ => None,
+        };
+        match next {
+            Some(next) => value = next,
+            None => return false,
+        }
+        if suppresses(value) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Match a pack-relative `path` against an `ignore` pattern.
+fn path_matches(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<char> = pattern.trim_end_matches('/').chars().collect();
+    let path: Vec<char> = path.chars().collect();
+    // A pattern naming a directory covers everything under it
+    let under: Vec<char> = pattern.iter().copied().chain("/**".chars()).collect();
+    glob(&pattern, &path) || glob(&under, &path)
+}
+
+fn glob(pattern: &[char], path: &[char]) -> bool {
+    match pattern {
+        [] => path.is_empty(),
+        ['*', '*', rest @ ..] => {
+            // `**/` also matches no directories at all
+            let rest = rest.strip_prefix(&['/']).unwrap_or(rest);
+            if rest.is_empty() {
+                return true;
+            }
+            (0..=path.len())
+                .filter(|&i| i == 0 || path[i - 1] == '/')
+                .any(|i| glob(rest, &path[i..]))
+        }
+        ['*', rest @ ..] => (0..=path.len())
+            .take_while(|&i| i == 0 || path[i - 1] != '/')
+            .any(|i| glob(rest, &path[i..])),
+        ['?', rest @ ..] => path.first().is_some_and(|&c| c != '/') && glob(rest, &path[1..]),
+        [c, rest @ ..] => path.first() == Some(c) && glob(rest, &path[1..]),
+    }
+}
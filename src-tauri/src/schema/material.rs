@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -6,13 +7,16 @@ use serde_json::Value;
 /// Material providers determine which block material to use at each position
 /// in the world, supporting layering, conditions, weighting, and noise-driven
 /// selection.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "Type")]
 pub enum MaterialProviderType {
     /// Provides one constant block type.
     Constant {
         #[serde(rename = "BlockType", default)]
         block_type: Option<String>,
+        #[schemars(default = "super::default_material")]
+        #[serde(rename = "Material", default)]
+        material: Option<Value>,
     },
 
     /// Separates into Solid and Empty terrain and provides a provider for each.
@@ -53,7 +57,7 @@ pub enum MaterialProviderType {
 
     /// Picks a provider from a weighted list with optional skip chance.
     Weighted {
-        #[serde(rename = "Seed", default)]
+        #[serde(rename = "Seed", default, deserialize_with = "super::optional_seed")]
         seed: Option<String>,
         #[serde(rename = "SkipChance", default)]
         skip_chance: Option<f64>,
@@ -71,18 +75,28 @@ pub enum MaterialProviderType {
 
     /// Places layers of blocks on terrain floor or ceiling surfaces.
     SpaceAndDepth {
+        #[schemars(default = "layer_context")]
         #[serde(rename = "LayerContext", default)]
         layer_context: Option<String>,
+        #[schemars(default = "super::int::<16>")]
         #[serde(rename = "MaxExpectedDepth", default)]
         max_expected_depth: Option<i32>,
         #[serde(rename = "Condition", default)]
         condition: Option<Value>,
         #[serde(rename = "Layers", default)]
         layers: Vec<Value>,
+        // Editor form: two layers split at DepthThreshold, built on export
+        #[serde(rename = "DepthThreshold", default)]
+        depth_threshold: Option<i32>,
+        #[serde(rename = "Solid", default)]
+        solid: Option<Value>,
+        #[serde(rename = "Empty", default)]
+        empty: Option<Value>,
     },
 
     /// Imports an exported MaterialProvider by name.
     Imported {
+        #[schemars(default = "super::empty")]
         #[serde(rename = "Name", default)]
         name: Option<String>,
     },
@@ -128,13 +142,14 @@ pub enum MaterialProviderType {
 ///
 /// Layers are stacked into the depth of floor or ceiling surfaces,
 /// each with a configurable thickness strategy.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "Type")]
 pub enum LayerType {
     /// Fixed thickness everywhere.
     ConstantThickness {
         #[serde(rename = "Material", default)]
         material: Option<Value>,
+        #[schemars(default = "super::int::<3>")]
         #[serde(rename = "Thickness", default)]
         thickness: Option<i32>,
     },
@@ -143,11 +158,14 @@ pub enum LayerType {
     RangeThickness {
         #[serde(rename = "Material", default)]
         material: Option<Value>,
+        #[schemars(default = "super::int::<1>")]
         #[serde(rename = "RangeMin", default)]
         range_min: Option<i32>,
+        #[schemars(default = "super::int::<5>")]
         #[serde(rename = "RangeMax", default)]
         range_max: Option<i32>,
-        #[serde(rename = "Seed", default)]
+        #[schemars(default = "super::empty")]
+        #[serde(rename = "Seed", default, deserialize_with = "super::optional_seed")]
         seed: Option<String>,
     },
 
@@ -155,9 +173,11 @@ pub enum LayerType {
     WeightedThickness {
         #[serde(rename = "Material", default)]
         material: Option<Value>,
+        #[schemars(default = "possible_thicknesses")]
         #[serde(rename = "PossibleThicknesses", default)]
         possible_thicknesses: Vec<Value>,
-        #[serde(rename = "Seed", default)]
+        #[schemars(default = "super::empty")]
+        #[serde(rename = "Seed", default, deserialize_with = "super::optional_seed")]
         seed: Option<String>,
     },
 
@@ -174,29 +194,35 @@ pub enum LayerType {
 ///
 /// Conditions check environmental context (space above floor, space below
 /// ceiling) to determine whether a material provider should be applied.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "Type")]
 pub enum ConditionType {
     /// Validates if a context value equals the configured value.
     EqualsCondition {
+        #[schemars(default = "context_to_check")]
         #[serde(rename = "ContextToCheck", default)]
         context_to_check: Option<String>,
+        #[schemars(default = "super::int::<0>")]
         #[serde(rename = "Value", default)]
         value: Option<i32>,
     },
 
     /// Validates if a context value is greater than the threshold.
     GreaterThanCondition {
+        #[schemars(default = "context_to_check")]
         #[serde(rename = "ContextToCheck", default)]
         context_to_check: Option<String>,
+        #[schemars(default = "super::int::<0>")]
         #[serde(rename = "Threshold", default)]
         threshold: Option<i32>,
     },
 
     /// Validates if a context value is smaller than the threshold.
     SmallerThanCondition {
+        #[schemars(default = "context_to_check")]
         #[serde(rename = "ContextToCheck", default)]
         context_to_check: Option<String>,
+        #[schemars(default = "super::int::<0>")]
         #[serde(rename = "Threshold", default)]
         threshold: Option<i32>,
     },
@@ -222,3 +248,15 @@ pub enum ConditionType {
     /// Always validates (no parameters).
     AlwaysTrueCondition {},
 }
+
+fn layer_context() -> Option<String> {
+    Some("DEPTH_INTO_FLOOR".to_string())
+}
+
+fn context_to_check() -> Option<String> {
+    Some("SPACE_ABOVE_FLOOR".to_string())
+}
+
+fn possible_thicknesses() -> Vec<Value> {
+    vec![serde_json::json!({"Weight": 1, "Thickness": 3})]
+}
@@ -0,0 +1,201 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use super::patch::{self, PatchOp};
+use super::slots::{file_category, walk};
+use super::validation::{Fix, Loc};
+use super::versions::{position, MigrationStep, SchemaVersion, Transform};
+use super::AssetCategory;
+
+/// Outcome of migrating a pack, or of a dry run.
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationReport {
+    pub from: String,
+    pub to: String,
+    /// One entry per file and step, in application order. Files are
+    /// relative to the project directory.
+    pub changes: Vec<Fix>,
+    /// False for a dry run.
+    pub applied: bool,
+}
+
+/// Plan the upgrade of a pack from version `from` to `to`.
+///
+/// `files` holds every parsed JSON file of the pack with its relative path.
+/// Returns one fix per file and step; applying them in order performs the
+/// migration. Each step sees the result of the steps before it.
+pub fn plan(
+    versions: &[SchemaVersion],
+    from: &str,
+    to: &str,
+    files: &[(String, Value)],
+) -> Result<Vec<Fix>, String> {
+    let start =
+        position(versions, from).ok_or_else(|| format!("Unknown server version '{}'", from))?;
+    let end = position(versions, to).ok_or_else(|| format!("Unknown server version '{}'", to))?;
+    if end < start {
+        return Err(format!("Cannot migrate from {} back to {}", from, to));
+    }
+
+    let mut files = files.to_vec();
+    let mut fixes = Vec::new();
+    for version in &versions[start + 1..=end] {
+        for step in version.steps {
+            for (file, value) in &mut files {
+                let edits = step_edits(step, file, value);
+                if edits.is_empty() {
+                    continue;
+                }
+                let title = format!("{}: {}", version.id, describe(step));
+                patch::apply(value, &edits).map_err(|e| format!("{} in {}: {}", title, file, e))?;
+                fixes.push(Fix {
+                    title,
+                    file: file.clone(),
+                    edits,
+                });
+            }
+        }
+    }
+    Ok(fixes)
+}
+
+/// Short description of a step for reports.
+fn describe(step: &MigrationStep) -> String {
+    let node = |category: &AssetCategory, node_type: &Option<&str>| match node_type {
+        Some(node_type) => format!("{:?} {}", category, node_type),
+        None => format!("{:?}", category),
+    };
+    match step {
+        MigrationStep::RenameType { category, from, to } => {
+            format!("Rename {:?} type {} to {}", category, from, to)
+        }
+        MigrationStep::RenameField {
+            category,
+            node_type,
+            from,
+            to,
+        } => format!(
+            "Rename {} field {} to {}",
+            node(category, node_type),
+            from,
+            to
+        ),
+        MigrationStep::MoveField {
+            category,
+            node_type,
+            from,
+            to,
+        } => format!("Move {} {} to {}", node(category, node_type), from, to),
+        MigrationStep::Transform {
+            category,
+            node_type,
+            field,
+            transform,
+        } => format!(
+            "Rewrite {} {} ({:?})",
+            node(category, node_type),
+            field,
+            transform
+        ),
+    }
+}
+
+/// Patch operations performing `step` on one file.
+fn step_edits(step: &MigrationStep, file: &str, value: &Value) -> Vec<PatchOp> {
+    let (category, node_type) = match step {
+        MigrationStep::RenameType { category, from, .. } => (*category, Some(*from)),
+        MigrationStep::RenameField {
+            category,
+            node_type,
+            ..
+        }
+        | MigrationStep::MoveField {
+            category,
+            node_type,
+            ..
+        }
+        | MigrationStep::Transform {
+            category,
+            node_type,
+            ..
+        } => (*category, *node_type),
+    };
+    let Some(root) = file_category(file, value) else {
+        return Vec::new();
+    };
+
+    let mut pointers = Vec::new();
+    walk(root, value, &mut |segments, node_category, node| {
+        let type_name = node.get("Type").and_then(|v| v.as_str());
+        if node_category == category && (node_type.is_none() || type_name == node_type) {
+            pointers.push(Loc::at(file, segments).pointer);
+        }
+    });
+
+    // Deepest nodes first, so moves in a parent don't strand its children's paths
+    let mut edits = Vec::new();
+    for pointer in pointers.into_iter().rev() {
+        let Some(node) = value.pointer(&pointer) else {
+            continue;
+        };
+        match step {
+            MigrationStep::RenameType { to, .. } => edits.push(PatchOp::Replace {
+                path: format!("{}/Type", pointer),
+                value: json!(to),
+            }),
+            MigrationStep::RenameField { from, to, .. } => {
+                let (from, to) = (format!("/{}", escape(from)), format!("/{}", escape(to)));
+                move_edits(&pointer, node, &from, &to, &mut edits);
+            }
+            MigrationStep::MoveField { from, to, .. } => {
+                move_edits(&pointer, node, from, to, &mut edits)
+            }
+            MigrationStep::Transform {
+                field, transform, ..
+            } => {
+                let Some(n) = node.get(*field).and_then(|v| v.as_f64()) else {
+                    continue;
+                };
+                let value = match transform {
+                    Transform::Multiply(k) => n * k,
+                    Transform::Add(k) => n + k,
+                    Transform::Reciprocal if n != 0.0 => 1.0 / n,
+                    Transform::Reciprocal => continue,
+                };
+                edits.push(PatchOp::Replace {
+                    path: format!("{}/{}", pointer, escape(field)),
+                    value: json!(value),
+                });
+            }
+        }
+    }
+    edits
+}
+
+/// Move the value at node-relative `from` to `to`, adding any missing
+/// parent objects of `to`. A value already at `to` is replaced.
+fn move_edits(pointer: &str, node: &Value, from: &str, to: &str, edits: &mut Vec<PatchOp>) {
+    if node.pointer(from).is_none() {
+        return;
+    }
+    let mut parent = String::new();
+    let tokens: Vec<&str> = to.split('/').skip(1).collect();
+    for token in &tokens[..tokens.len().saturating_sub(1)] {
+        parent.push('/');
+        parent.push_str(token);
+        if node.pointer(&parent).is_none() {
+            edits.push(PatchOp::Add {
+                path: format!("{}{}", pointer, parent),
+                value: json!({}),
+            });
+        }
+    }
+    edits.push(PatchOp::Move {
+        from: format!("{}{}", pointer, from),
+        path: format!("{}{}", pointer, to),
+    });
+}
+
+fn escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
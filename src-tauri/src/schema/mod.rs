@@ -15,14 +15,103 @@ pub mod settings;
 pub mod world_structure;
 pub mod framework;
 pub mod block_masks;
+pub mod describe;
+pub mod lint;
+pub mod migration;
+pub mod patch;
+pub mod references;
+pub mod slots;
+pub mod spans;
 pub mod validation;
+pub mod versions;
 #[cfg(test)]
 mod tests;
 
-use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+/// Deserialize a `Seed`. Seeds are names, but packs often write them as
+/// numbers, which the server accepts too.
+pub(crate) fn seed<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    seed_from_value(Value::deserialize(deserializer)?)
+}
+
+/// Deserialize an optional `Seed`, where `null` means no seed.
+pub(crate) fn optional_seed<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    match Value::deserialize(deserializer)? {
+        Value::Null => Ok(None),
+        value => seed_from_value(value).map(Some),
+    }
+}
+
+fn seed_from_value<E: serde::de::Error>(value: Value) -> Result<String, E> {
+    match value {
+        Value::String(s) => Ok(s),
+        Value::Number(n) => Ok(n.to_string()),
+        other => Err(E::invalid_type(
+            serde::de::Unexpected::Other(&other.to_string()),
+            &"a string or number",
+        )),
+    }
+}
+
+// Defaults the editor starts new nodes with, declared on fields with
+// `#[schemars(default = "...")]` for `get_schema` to report. Deserializing
+// ignores them: a missing field stays unset.
+
+pub(crate) fn zero() -> Option<f64> {
+    Some(0.0)
+}
+
+pub(crate) fn half() -> Option<f64> {
+    Some(0.5)
+}
+
+pub(crate) fn one() -> Option<f64> {
+    Some(1.0)
+}
+
+pub(crate) fn two() -> Option<f64> {
+    Some(2.0)
+}
+
+pub(crate) fn frequency() -> Option<f64> {
+    Some(0.01)
+}
+
+pub(crate) fn int<const N: i32>() -> Option<i32> {
+    Some(N)
+}
+
+pub(crate) fn flag<const B: bool>() -> Option<bool> {
+    Some(B)
+}
+
+pub(crate) fn default_seed() -> Option<String> {
+    Some("A".to_string())
+}
+
+pub(crate) fn empty() -> Option<String> {
+    Some(String::new())
+}
+
+pub(crate) fn default_material() -> Option<Value> {
+    Some(Value::from("Rock_Lime_Cobble"))
+}
+
+pub(crate) fn vector<const X: i32, const Y: i32, const Z: i32>() -> Option<Value> {
+    Some(serde_json::json!({"x": X, "y": Y, "z": Z}))
+}
+
+pub(crate) fn range<const MIN: i32, const MAX: i32>() -> Option<Value> {
+    Some(serde_json::json!({"Min": MIN, "Max": MAX}))
+}
 
 /// Common base fields shared by most V2 asset types.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 #[serde(default)]
 pub struct BaseFields {
     /// The polymorphic type discriminator.
@@ -39,7 +128,7 @@ pub struct BaseFields {
 }
 
 /// A 3D vector with double precision.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 pub struct Vector3d {
     pub x: f64,
     pub y: f64,
@@ -47,7 +136,7 @@ pub struct Vector3d {
 }
 
 /// A 3D vector with integer components.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 pub struct Vector3i {
     pub x: i32,
     pub y: i32,
@@ -55,7 +144,7 @@ pub struct Vector3i {
 }
 
 /// A material definition (solid/fluid pair).
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 #[serde(default)]
 pub struct MaterialAsset {
     #[serde(rename = "Solid")]
@@ -67,7 +156,7 @@ pub struct MaterialAsset {
 }
 
 /// A range with min/max double values.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RangeDouble {
     #[serde(rename = "Min")]
     pub min: f64,
@@ -75,13 +164,17 @@ pub struct RangeDouble {
     pub max: f64,
 }
 
-/// Category of a V2 asset type (for UI organization).
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+/// Category of a V2 asset type (for UI organization and validation).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, JsonSchema)]
 pub enum AssetCategory {
     Density,
     Curve,
     Pattern,
     MaterialProvider,
+    /// SpaceAndDepth layer inside a material provider.
+    Layer,
+    /// Context condition inside a material provider.
+    Condition,
     PositionProvider,
     Prop,
     Scanner,
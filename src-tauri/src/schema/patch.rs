@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// One JSON Patch (RFC 6902) operation. Paths are JSON pointers into the
+/// file the patch applies to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOp {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+    Move { from: String, path: String },
+    Copy { from: String, path: String },
+    Test { path: String, value: Value },
+}
+
+/// Apply `ops` to `doc` in order. On error `doc` may be partly patched, so
+/// callers apply to a copy.
+pub fn apply(doc: &mut Value, ops: &[PatchOp]) -> Result<(), String> {
+    for op in ops {
+        match op {
+            PatchOp::Add { path, value } => add(doc, path, value.clone())?,
+            PatchOp::Remove { path } => {
+                remove(doc, path)?;
+            }
+            PatchOp::Replace { path, value } => {
+                let target = doc
+                    .pointer_mut(path)
+                    .ok_or_else(|| format!("No value at '{}'", path))?;
+                *target = value.clone();
+            }
+            PatchOp::Move { from, path } => {
+                if path.starts_with(&format!("{}/", from)) {
+                    return Err(format!("Cannot move '{}' into itself", from));
+                }
+                let value = remove(doc, from)?;
+                add(doc, path, value)?;
+            }
+            PatchOp::Copy { from, path } => {
+                let value = doc
+                    .pointer(from)
+                    .cloned()
+                    .ok_or_else(|| format!("No value at '{}'", from))?;
+                add(doc, path, value)?;
+            }
+            PatchOp::Test { path, value } => {
+                if doc.pointer(path) != Some(value) {
+                    return Err(format!("Test failed at '{}'", path));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Split a pointer into its parent pointer and unescaped last token.
+fn split(path: &str) -> Result<(&str, String), String> {
+    let slash = path
+        .rfind('/')
+        .ok_or_else(|| format!("Invalid JSON pointer '{}'", path))?;
+    let token = path[slash + 1..].replace("~1", "/").replace("~0", "~");
+    Ok((&path[..slash], token))
+}
+
+fn add(doc: &mut Value, path: &str, value: Value) -> Result<(), String> {
+    if path.is_empty() {
+        *doc = value;
+        return Ok(());
+    }
+    let (parent, token) = split(path)?;
+    match doc.pointer_mut(parent) {
+        Some(Value::Object(obj)) => {
+            obj.insert(token, value);
+            Ok(())
+        }
+        Some(Value::Array(arr)) => {
+            let index = if token == "-" {
+                arr.len()
+            } else {
+                token
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|&i| i <= arr.len())
+                    .ok_or_else(|| format!("Invalid array index in '{}'", path))?
+            };
+            arr.insert(index, value);
+            Ok(())
+        }
+        _ => Err(format!("No container at '{}'", parent)),
+    }
+}
+
+fn remove(doc: &mut Value, path: &str) -> Result<Value, String> {
+    let (parent, token) = split(path)?;
+    let removed = match doc.pointer_mut(parent) {
+        Some(Value::Object(obj)) => obj.shift_remove(&token),
+        Some(Value::Array(arr)) => token
+            .parse::<usize>()
+            .ok()
+            .filter(|&i| i < arr.len())
+            .map(|i| arr.remove(i)),
+        _ => None,
+    };
+    removed.ok_or_else(|| format!("No value at '{}'", path))
+}
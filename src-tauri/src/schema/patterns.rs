@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -7,25 +8,29 @@ use serde_json::Value;
 /// relationships, surface detection, and logical operations. They are used
 /// to determine valid placement positions for props, materials, and other
 /// world generation elements.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "Type")]
 pub enum PatternType {
-    /// Checks against a specific block material.
+    /// Checks against a specific block material: a block name or a
+    /// material object (`{"Solid": ...}`).
     BlockType {
+        #[schemars(default = "super::default_material")]
         #[serde(rename = "Material", default)]
-        material: Option<String>,
+        material: Option<Value>,
     },
 
-    /// Checks if the block's material belongs to a named BlockSet.
+    /// Checks if the block's material belongs to a BlockSet, given by name
+    /// or inline.
     BlockSet {
         #[serde(rename = "BlockSet", default)]
-        block_set: Option<String>,
+        block_set: Option<Value>,
     },
 
     /// Offsets the child pattern by an integer vector.
     Offset {
         #[serde(rename = "Pattern", default)]
         pattern: Option<Value>,
+        #[schemars(default = "super::vector::<0, 0, 0>")]
         #[serde(rename = "Offset", default)]
         offset: Option<Value>,
     },
@@ -103,8 +108,10 @@ pub enum PatternType {
     /// Defines a cuboid region; validates if all positions inside
     /// match the SubPattern.
     Cuboid {
+        #[schemars(default = "super::vector::<0, 0, 0>")]
         #[serde(rename = "Min", default)]
         min: Option<Value>,
+        #[schemars(default = "super::vector::<1, 1, 1>")]
         #[serde(rename = "Max", default)]
         max: Option<Value>,
         #[serde(rename = "SubPattern", default)]
@@ -139,6 +146,7 @@ pub enum PatternType {
 
     /// Imports a previously exported Pattern by name.
     Imported {
+        #[schemars(default = "super::empty")]
         #[serde(rename = "Name", default)]
         name: Option<String>,
     },
@@ -153,6 +161,7 @@ pub enum PatternType {
 
     /// Always outputs a constant boolean value (true/false).
     Constant {
+        #[schemars(default = "super::flag::<true>")]
         #[serde(rename = "Value", default)]
         value: Option<bool>,
     },
@@ -1,7 +1,8 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "Type")]
 pub enum PositionProviderType {
     List {
@@ -13,6 +14,13 @@ pub enum PositionProviderType {
         point_generator: Option<PointGenerator>,
         #[serde(rename = "PointsY", default)]
         points_y: i32,
+        // Editor form of the PointGenerator's scale and jitter
+        #[schemars(default = "mesh_resolution")]
+        #[serde(rename = "Resolution")]
+        resolution: Option<f64>,
+        #[schemars(default = "super::zero")]
+        #[serde(rename = "Jitter")]
+        jitter: Option<f64>,
     },
     Mesh3D {
         #[serde(rename = "PointGenerator")]
@@ -25,9 +33,15 @@ pub enum PositionProviderType {
         positions: Option<Value>,
         #[serde(rename = "Delimiters", default)]
         delimiters: Vec<Value>,
+        // Editor form of Positions and of a single open-ended delimiter
+        #[serde(rename = "PositionProvider")]
+        position_provider: Option<Value>,
+        #[schemars(default = "super::half")]
+        #[serde(rename = "Threshold")]
+        threshold: Option<f64>,
     },
     Occurrence {
-        #[serde(rename = "Seed", default)]
+        #[serde(rename = "Seed", default, deserialize_with = "super::seed")]
         seed: String,
         #[serde(rename = "FieldFunction")]
         field_function: Option<Value>,
@@ -94,12 +108,20 @@ pub enum PositionProviderType {
     },
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 #[serde(default, rename_all = "PascalCase")]
 pub struct PointGenerator {
     #[serde(rename = "Type")]
     pub generator_type: String,
     pub spacing: i32,
+    pub scale_x: Option<f64>,
+    pub scale_y: Option<f64>,
+    pub scale_z: Option<f64>,
     pub jitter: f64,
+    #[serde(deserialize_with = "super::seed")]
     pub seed: String,
 }
+
+fn mesh_resolution() -> Option<f64> {
+    Some(16.0)
+}
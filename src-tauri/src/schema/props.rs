@@ -1,22 +1,27 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "Type")]
 pub enum PropType {
     Box {
         #[serde(rename = "Range")]
         range: Option<Value>,
+        #[schemars(default = "super::default_material")]
         #[serde(rename = "Material")]
         material: Option<Value>,
         #[serde(rename = "Pattern")]
         pattern: Option<Value>,
         #[serde(rename = "Scanner")]
         scanner: Option<Value>,
+        #[serde(rename = "BoxBlockType")]
+        box_block_type: Option<String>,
     },
     Column {
         #[serde(rename = "Range")]
         range: Option<Value>,
+        #[schemars(default = "super::default_material")]
         #[serde(rename = "Material")]
         material: Option<Value>,
         #[serde(rename = "Pattern")]
@@ -29,10 +34,13 @@ pub enum PropType {
         range: i32,
         #[serde(rename = "DistanceCurve")]
         distance_curve: Option<Value>,
-        #[serde(rename = "Seed", default)]
+        #[serde(rename = "Seed", default, deserialize_with = "super::seed")]
         seed: String,
         #[serde(rename = "WeightedProps")]
         weighted_props: Option<Value>,
+        // Editor form of WeightedProps, each prop weighted 1
+        #[serde(rename = "Props", default)]
+        props: Vec<Value>,
         #[serde(rename = "Pattern")]
         pattern: Option<Value>,
         #[serde(rename = "Scanner")]
@@ -53,8 +61,12 @@ pub enum PropType {
         material: Option<Value>,
     },
     Prefab {
+        #[schemars(default = "prefab_paths")]
         #[serde(rename = "WeightedPrefabPaths")]
         weighted_prefab_paths: Option<Value>,
+        // Editor form of a single weighted path
+        #[serde(rename = "Path")]
+        path: Option<String>,
         #[serde(rename = "LegacyPath", default)]
         legacy_path: bool,
         #[serde(rename = "Directionality")]
@@ -63,6 +75,7 @@ pub enum PropType {
         scanner: Option<Value>,
         #[serde(rename = "BlockMask")]
         block_mask: Option<Value>,
+        #[schemars(default = "molding_direction")]
         #[serde(rename = "MoldingDirection", default)]
         molding_direction: String,
         #[serde(rename = "MoldingPattern")]
@@ -71,6 +84,7 @@ pub enum PropType {
         molding_scanner: Option<Value>,
         #[serde(rename = "MoldingChildren", default)]
         molding_children: bool,
+        #[schemars(default = "load_entities")]
         #[serde(rename = "LoadEntities", default)]
         load_entities: bool,
     },
@@ -79,6 +93,7 @@ pub enum PropType {
         scanner: Option<Value>,
         #[serde(rename = "Pattern")]
         pattern: Option<Value>,
+        #[schemars(default = "water")]
         #[serde(rename = "Material")]
         material: Option<Value>,
     },
@@ -103,7 +118,7 @@ pub enum PropType {
     Weighted {
         #[serde(rename = "Entries")]
         entries: Option<Value>,
-        #[serde(rename = "Seed", default)]
+        #[serde(rename = "Seed", default, deserialize_with = "super::seed")]
         seed: String,
     },
     Imported {
@@ -112,7 +127,10 @@ pub enum PropType {
     },
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// Only built when reading a file, so the size of the Pattern variant's
+// four pattern values doesn't matter
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "Type")]
 pub enum DirectionalityType {
     Static {
@@ -122,15 +140,18 @@ pub enum DirectionalityType {
         pattern: Option<Value>,
     },
     Random {
-        #[serde(rename = "Seed", default)]
+        #[schemars(default = "seed")]
+        #[serde(rename = "Seed", default, deserialize_with = "super::seed")]
         seed: String,
         #[serde(rename = "Pattern")]
         pattern: Option<Value>,
     },
     Pattern {
+        #[schemars(default = "initial_direction")]
         #[serde(rename = "InitialDirection", default)]
         initial_direction: String,
-        #[serde(rename = "Seed", default)]
+        #[schemars(default = "seed")]
+        #[serde(rename = "Seed", default, deserialize_with = "super::seed")]
         seed: String,
         #[serde(rename = "NorthPattern")]
         north_pattern: Option<Value>,
@@ -146,3 +167,27 @@ pub enum DirectionalityType {
         name: String,
     },
 }
+
+fn water() -> Option<Value> {
+    Some(Value::from("Water"))
+}
+
+fn prefab_paths() -> Option<Value> {
+    Some(serde_json::json!([{"Path": "", "Weight": 1}]))
+}
+
+fn load_entities() -> bool {
+    true
+}
+
+fn molding_direction() -> String {
+    "NONE".to_string()
+}
+
+fn seed() -> String {
+    "A".to_string()
+}
+
+fn initial_direction() -> String {
+    "NORTH".to_string()
+}
@@ -0,0 +1,237 @@
+use std::collections::{HashMap, HashSet};
+
+use serde_json::Value;
+
+use super::slots::{file_category, walk};
+use super::validation::{DiagnosticCode, Loc, Severity, ValidationError};
+use super::AssetCategory;
+
+type ExportKey = (AssetCategory, String);
+
+/// True if `inner` lies inside the value at `outer` in the same file.
+fn is_within(inner: &Loc, outer: &Loc) -> bool {
+    inner.file == outer.file
+        && inner
+            .pointer
+            .strip_prefix(outer.pointer.as_str())
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// Names declared and referenced across a pack, gathered in one pass.
+#[derive(Default)]
+struct PackIndex<'a> {
+    biomes: HashSet<String>,
+    /// `ExportAs` declarations by category and name.
+    exports: HashMap<ExportKey, Vec<Loc<'a>>>,
+    /// `Imported` nodes by the export they ask for.
+    imports: Vec<(ExportKey, Loc<'a>)>,
+    /// Entry names of `Positions` frameworks.
+    framework_positions: HashSet<String>,
+    /// `Framework` position providers by framework entry name.
+    framework_refs: Vec<(String, Loc<'a>)>,
+    /// Biome names used by world structures, with the referring field.
+    biome_refs: Vec<(String, Loc<'a>, &'static str)>,
+}
+
+impl<'a> PackIndex<'a> {
+    fn add_file(&mut self, file: &'a str, value: &Value) {
+        let Some(category) = file_category(file, value) else {
+            return;
+        };
+        if category == AssetCategory::Biome {
+            if let Some(name) = value.get("Name").and_then(|v| v.as_str()) {
+                self.biomes.insert(name.to_string());
+            }
+        }
+
+        walk(category, value, &mut |segments, category, node| {
+            let loc = Loc::at(file, segments);
+            let text = |key: &str| node.get(key).and_then(|v| v.as_str()).map(str::to_string);
+
+            if let Some(export) = text("ExportAs").filter(|name| !name.is_empty()) {
+                self.exports
+                    .entry((category, export))
+                    .or_default()
+                    .push(loc.clone());
+            }
+            match (category, node.get("Type").and_then(|v| v.as_str())) {
+                (_, Some("Imported")) => {
+                    if let Some(name) = text("Name") {
+                        self.imports.push(((category, name), loc));
+                    }
+                }
+                (AssetCategory::PositionProvider, Some("Framework")) => {
+                    if let Some(name) = text("Name") {
+                        self.framework_refs.push((name, loc));
+                    }
+                }
+                (AssetCategory::Framework, Some("Positions")) => {
+                    let entries = node.get("Entries").and_then(|v| v.as_array());
+                    for entry in entries.into_iter().flatten() {
+                        if let Some(name) = entry.get("Name").and_then(|v| v.as_str()) {
+                            self.framework_positions.insert(name.to_string());
+                        }
+                    }
+                }
+                (AssetCategory::WorldStructure, Some("NoiseRange")) => {
+                    if let Some(name) = text("DefaultBiome") {
+                        self.biome_refs.push((name, loc, "DefaultBiome"));
+                    }
+                    let ranges = node.get("Biomes").and_then(|v| v.as_array());
+                    for (i, range) in ranges.into_iter().flatten().enumerate() {
+                        if let Some(name) = range.get("Biome").and_then(|v| v.as_str()) {
+                            let mut entry = segments.to_vec();
+                            entry.extend(["Biomes".to_string(), i.to_string()]);
+                            self.biome_refs.push((
+                                name.to_string(),
+                                Loc::at(file, &entry),
+                                "Biome",
+                            ));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        });
+    }
+
+    /// Declarations of an export, dropping nodes that repeat the `ExportAs`
+    /// of an enclosing export (the editor writes the name on both).
+    fn declarations(&self, key: &ExportKey) -> Vec<&Loc<'a>> {
+        let locs = self.exports.get(key).map(Vec::as_slice).unwrap_or_default();
+        locs.iter()
+            .filter(|loc| !locs.iter().any(|outer| is_within(loc, outer)))
+            .collect()
+    }
+}
+
+/// Check references that span files: biome names, imports, framework
+/// positions, unique exports and import cycles.
+///
+/// `files` holds every parsed JSON file of the pack with its relative path.
+pub fn validate_references(files: &[(String, Value)]) -> Vec<ValidationError> {
+    let mut index = PackIndex::default();
+    for (file, value) in files {
+        index.add_file(file, value);
+    }
+    let mut errors = Vec::new();
+
+    for (name, loc, field) in &index.biome_refs {
+        if !index.biomes.contains(name) {
+            errors.push(loc.error(
+                DiagnosticCode::UnresolvedBiome,
+                field,
+                format!("Biome '{}' is not the Name of any biome in the pack", name),
+                Severity::Error,
+            ));
+        }
+    }
+
+    for (name, loc) in &index.framework_refs {
+        if !index.framework_positions.contains(name) {
+            errors.push(loc.error(
+                DiagnosticCode::UnresolvedFramework,
+                "Name",
+                format!("Framework '{}' matches no Positions framework entry", name),
+                Severity::Error,
+            ));
+        }
+    }
+
+    let mut keys: Vec<&ExportKey> = index.exports.keys().collect();
+    keys.sort_by(|a, b| a.1.cmp(&b.1));
+    for key in keys {
+        let locs = index.declarations(key);
+        if locs.len() > 1 {
+            for loc in &locs {
+                errors.push(loc.error(
+                    DiagnosticCode::DuplicateExport,
+                    "ExportAs",
+                    format!("ExportAs '{}' is declared {} times", key.1, locs.len()),
+                    Severity::Error,
+                ));
+            }
+        }
+    }
+
+    for ((category, name), loc) in &index.imports {
+        if index.exports.contains_key(&(*category, name.clone())) {
+            continue;
+        }
+        let other = index
+            .exports
+            .keys()
+            .find(|(_, export)| export == name)
+            .map(|(other, _)| *other);
+        errors.push(match other {
+            Some(other) => loc.error(
+                DiagnosticCode::TypeMismatch,
+                "Name",
+                format!(
+                    "Imported '{}' is a {:?} export but a {:?} is expected here",
+                    name, other, category
+                ),
+                Severity::Error,
+            ),
+            None => loc.error(
+                DiagnosticCode::UnresolvedImport,
+                "Name",
+                format!("Imported '{}' is not exported anywhere in the pack", name),
+                Severity::Warning,
+            ),
+        });
+    }
+
+    errors.extend(import_cycles(&index));
+    errors
+}
+
+/// Report every import that closes a cycle of exports importing each other.
+fn import_cycles(index: &PackIndex) -> Vec<ValidationError> {
+    // Export -> the imports made inside its value.
+    let mut edges: HashMap<&ExportKey, Vec<(&ExportKey, &Loc)>> = HashMap::new();
+    for (key, locs) in &index.exports {
+        for (import, loc) in &index.imports {
+            if locs.iter().any(|export| is_within(loc, export)) {
+                edges.entry(key).or_default().push((import, loc));
+            }
+        }
+    }
+
+    // Chain of export names leading from `from` to `to`, if any.
+    let path_between = |from: &ExportKey, to: &ExportKey| -> Option<Vec<String>> {
+        let mut stack = vec![(from, vec![from.1.clone()])];
+        let mut seen = HashSet::new();
+        while let Some((key, chain)) = stack.pop() {
+            if key == to {
+                return Some(chain);
+            }
+            if !seen.insert(key) {
+                continue;
+            }
+            for (next, _) in edges.get(key).into_iter().flatten() {
+                let mut chain = chain.clone();
+                chain.push(next.1.clone());
+                stack.push((next, chain));
+            }
+        }
+        None
+    };
+
+    let mut sources: Vec<&ExportKey> = edges.keys().copied().collect();
+    sources.sort_by(|a, b| a.1.cmp(&b.1));
+    let mut errors = Vec::new();
+    for source in sources {
+        for (target, loc) in &edges[source] {
+            if let Some(chain) = path_between(target, source) {
+                errors.push(loc.error(
+                    DiagnosticCode::CircularReference,
+                    "Name",
+                    format!("Import cycle: {} -> {}", source.1, chain.join(" -> ")),
+                    Severity::Error,
+                ));
+            }
+        }
+    }
+    errors
+}
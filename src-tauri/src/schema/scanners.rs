@@ -1,7 +1,8 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "Type")]
 pub enum ScannerType {
     Origin,
@@ -18,6 +19,14 @@ pub enum ScannerType {
         relative_to_position: bool,
         #[serde(rename = "BaseHeightName", default)]
         base_height_name: String,
+        // Editor form: Range is {Min, Max} of MinY and MaxY; StepSize is
+        // dropped on export
+        #[schemars(default = "super::range::<0, 256>")]
+        #[serde(rename = "Range")]
+        range: Option<Value>,
+        #[schemars(default = "super::int::<1>")]
+        #[serde(rename = "StepSize")]
+        step_size: Option<i32>,
     },
     ColumnRandom {
         #[serde(rename = "MinY", default)]
@@ -26,7 +35,7 @@ pub enum ScannerType {
         max_y: i32,
         #[serde(rename = "ResultCap", default)]
         result_cap: i32,
-        #[serde(rename = "Seed", default)]
+        #[serde(rename = "Seed", default, deserialize_with = "super::seed")]
         seed: String,
         #[serde(rename = "Strategy", default)]
         strategy: String,
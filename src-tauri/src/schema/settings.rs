@@ -1,8 +1,10 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Settings asset loaded from HytaleGenerator/Settings/.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 #[serde(default, rename_all = "PascalCase")]
+#[schemars(default = "SettingsAsset::new")]
 pub struct SettingsAsset {
     pub custom_concurrency: i32,
     pub buffer_capacity_factor: f64,
@@ -0,0 +1,412 @@
+use super::AssetCategory;
+use serde_json::Value;
+use AssetCategory::*;
+
+/// How a field holds nested assets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slot {
+    /// A single nested asset of the category.
+    One(AssetCategory),
+    /// An array of nested assets of the category.
+    Many(AssetCategory),
+    /// A plain object (no `Type`) whose listed fields hold assets.
+    Object(&'static [(&'static str, Slot)]),
+    /// An array of plain objects whose listed fields hold assets.
+    Objects(&'static [(&'static str, Slot)]),
+}
+
+/// All known V2 type names of a category. Empty for categories without a
+/// `Type` discriminator (biomes, settings, block masks).
+pub fn known_types(category: AssetCategory) -> &'static [&'static str] {
+    match category {
+        Density => &[
+            "SimplexNoise2D", "SimplexNoise3D", "CellNoise2D", "CellNoise3D",
+            "Constant", "Sum", "Multiplier", "Abs", "Inverter", "Sqrt", "Pow",
+            "OffsetConstant", "AmplitudeConstant",
+            "Clamp", "SmoothClamp", "Floor", "SmoothFloor", "Ceiling", "SmoothCeiling",
+            "Min", "SmoothMin", "Max", "SmoothMax",
+            "Normalizer", "CurveMapper", "Offset", "Amplitude",
+            "Mix", "MultiMix",
+            "Scale", "Slider", "Rotator", "Anchor", "XOverride", "YOverride", "ZOverride",
+            "GradientWarp", "FastGradientWarp", "VectorWarp",
+            "Distance", "Cube", "Ellipsoid", "Cuboid", "Cylinder", "Plane", "Axis", "Shell", "Angle",
+            "XValue", "YValue", "ZValue",
+            "Terrain", "BaseHeight", "CellWallDistance", "DistanceToBiomeEdge",
+            "Gradient", "Cache", "Cache2D", "YSampled",
+            "Switch", "SwitchState",
+            "PositionsCellNoise", "Positions3D", "PositionsPinch", "PositionsTwist",
+            "Exported", "Imported", "Pipeline",
+        ],
+        Curve => &[
+            "Manual", "DistanceExponential", "DistanceS",
+            "Ceiling", "Floor", "SmoothCeiling", "SmoothFloor", "SmoothClamp",
+            "SmoothMax", "SmoothMin", "Clamp", "Inverter",
+            "Max", "Min", "Multiplier", "Not", "Sum", "Imported", "Exported",
+        ],
+        Pattern => &[
+            "BlockType", "BlockSet", "Offset", "Floor", "Ceiling", "Wall", "Surface", "Gap",
+            "Cuboid", "And", "Or", "Not", "FieldFunction", "Imported", "Exported", "Constant",
+        ],
+        MaterialProvider => &[
+            "Constant", "Solidity", "Queue", "SimpleHorizontal", "Striped", "Weighted",
+            "FieldFunction", "SpaceAndDepth", "Imported", "Exported", "Switch",
+            "DepthBased", "GradientBased", "Pipeline",
+        ],
+        Layer => &["ConstantThickness", "RangeThickness", "WeightedThickness", "NoiseThickness"],
+        Condition => &[
+            "EqualsCondition", "GreaterThanCondition", "SmallerThanCondition",
+            "AndCondition", "OrCondition", "NotCondition", "AlwaysTrueCondition",
+        ],
+        PositionProvider => &[
+            "List", "Mesh2D", "Mesh3D", "FieldFunction", "Occurrence", "Offset", "Union",
+            "SimpleHorizontal", "Cache", "BaseHeight", "Anchor", "Bound", "Framework", "Imported",
+        ],
+        Prop => &[
+            "Box", "Column", "Cluster", "Density", "Prefab", "PondFiller",
+            "Queue", "Union", "Offset", "Weighted", "Imported",
+        ],
+        Directionality => &["Static", "Random", "Pattern", "Imported"],
+        Scanner => &["Origin", "ColumnLinear", "ColumnRandom", "Area", "Imported"],
+        Assignment => &["Constant", "FieldFunction", "Sandwich", "Weighted", "Imported"],
+        VectorProvider => &["Constant", "DensityGradient", "Cache", "Exported", "Imported"],
+        EnvironmentProvider => &["Constant", "DensityDelimited"],
+        TintProvider => &["Constant", "DensityDelimited"],
+        Framework => &["DecimalConstants", "Positions"],
+        // A biome's terrain, also accepted as a standalone root file
+        WorldStructure => &["NoiseRange", "DAOTerrain"],
+        BlockMask | Biome | Settings => &[],
+    }
+}
+
+/// Fields of a `category` asset of type `type_name` that hold nested assets,
+/// and the category each expects. `type_name` is ignored for untyped categories.
+pub fn child_slots(category: AssetCategory, type_name: &str) -> &'static [(&'static str, Slot)] {
+    const INPUT: (&str, Slot) = ("Input", Slot::One(Density));
+    const INPUTS: (&str, Slot) = ("Inputs", Slot::Many(Density));
+    const CURVE: (&str, Slot) = ("Curve", Slot::One(Curve));
+    const POSITIONS: (&str, Slot) = ("Positions", Slot::One(PositionProvider));
+    const FIELD_FUNCTION: (&str, Slot) = ("FieldFunction", Slot::One(Density));
+    const PATTERN: (&str, Slot) = ("Pattern", Slot::One(Pattern));
+    const MATERIAL: (&str, Slot) = ("Material", Slot::One(MaterialProvider));
+    const SCANNER: (&str, Slot) = ("Scanner", Slot::One(Scanner));
+    const PROP: (&str, Slot) = ("Prop", Slot::One(Prop));
+    const ASSIGNMENTS: (&str, Slot) = ("Assignments", Slot::One(Assignment));
+    const VECTOR_PROVIDER: (&str, Slot) = ("VectorProvider", Slot::One(VectorProvider));
+
+    match (category, type_name) {
+        (Density, "Sum" | "Multiplier" | "Min" | "Max" | "SmoothMin" | "SmoothMax" | "Mix")
+        | (Density, "MultiMix" | "GradientWarp" | "VectorWarp") => &[INPUTS],
+        (
+            Density,
+            "Abs" | "Inverter" | "Sqrt" | "Pow" | "OffsetConstant" | "AmplitudeConstant" | "Clamp"
+            | "SmoothClamp" | "Floor" | "SmoothFloor" | "Ceiling" | "SmoothCeiling" | "Normalizer"
+            | "Scale" | "Slider" | "Rotator" | "Anchor" | "FastGradientWarp" | "Cache" | "Cache2D"
+            | "YSampled" | "Switch" | "SwitchState",
+        ) => &[INPUT],
+        (Density, "CurveMapper") => &[CURVE, INPUT],
+        (Density, "Offset") => &[("Offset", Slot::One(Density)), INPUT],
+        (Density, "Amplitude") => &[("Amplitude", Slot::One(Density)), INPUT],
+        (Density, "XOverride" | "YOverride" | "ZOverride") => {
+            &[INPUT, ("Override", Slot::One(Density))]
+        }
+        (Density, "Distance" | "Cube" | "Ellipsoid" | "Cuboid" | "Plane" | "Axis") => &[CURVE],
+        (Density, "Cylinder") => &[
+            ("AxialCurve", Slot::One(Curve)),
+            ("RadialCurve", Slot::One(Curve)),
+        ],
+        (Density, "Shell") => &[
+            ("AngleCurve", Slot::One(Curve)),
+            ("DistanceCurve", Slot::One(Curve)),
+        ],
+        (Density, "Angle") => &[VECTOR_PROVIDER],
+        (Density, "CellWallDistance" | "PositionsCellNoise") => &[POSITIONS],
+        (Density, "Positions3D") => &[POSITIONS, ("Density", Slot::One(Density))],
+        (Density, "PositionsPinch") => &[POSITIONS, ("PinchCurve", Slot::One(Curve)), INPUT],
+        (Density, "PositionsTwist") => &[POSITIONS, ("TwistCurve", Slot::One(Curve)), INPUT],
+        (Density, "Exported") => &[("Density", Slot::One(Density)), INPUT],
+        (Density, "Pipeline") => &[("Steps", Slot::Many(Density)), INPUT],
+
+        (
+            Curve,
+            "Ceiling" | "Floor" | "SmoothCeiling" | "SmoothFloor" | "SmoothClamp" | "Clamp"
+            | "Inverter" | "Not" | "Exported",
+        ) => &[CURVE],
+        (Curve, "SmoothMax" | "SmoothMin") => &[
+            ("CurveA", Slot::One(Curve)),
+            ("CurveB", Slot::One(Curve)),
+        ],
+        (Curve, "Max" | "Min" | "Multiplier" | "Sum") => &[("Curves", Slot::Many(Curve))],
+
+        (Pattern, "Offset" | "Not" | "Exported") => &[PATTERN],
+        (Pattern, "Floor") => &[("Floor", Slot::One(Pattern)), ("Origin", Slot::One(Pattern))],
+        (Pattern, "Ceiling") => &[
+            ("Ceiling", Slot::One(Pattern)),
+            ("Origin", Slot::One(Pattern)),
+        ],
+        (Pattern, "Wall") => &[("Wall", Slot::One(Pattern)), ("Origin", Slot::One(Pattern))],
+        (Pattern, "Surface") => &[
+            ("Surface", Slot::One(Pattern)),
+            ("Medium", Slot::One(Pattern)),
+        ],
+        (Pattern, "Gap") => &[
+            ("GapPattern", Slot::One(Pattern)),
+            ("AnchorPattern", Slot::One(Pattern)),
+        ],
+        (Pattern, "Cuboid") => &[("SubPattern", Slot::One(Pattern))],
+        (Pattern, "And" | "Or") => &[("Patterns", Slot::Many(Pattern))],
+        (Pattern, "FieldFunction") => &[FIELD_FUNCTION],
+
+        (MaterialProvider, "Solidity") => &[
+            ("Solid", Slot::One(MaterialProvider)),
+            ("Empty", Slot::One(MaterialProvider)),
+        ],
+        (MaterialProvider, "Queue") => &[("Queue", Slot::Many(MaterialProvider))],
+        (MaterialProvider, "SimpleHorizontal" | "Striped" | "Exported") => &[MATERIAL],
+        (MaterialProvider, "Weighted") => &[("WeightedMaterials", Slot::Objects(&[MATERIAL]))],
+        (MaterialProvider, "FieldFunction") => {
+            &[FIELD_FUNCTION, ("Delimiters", Slot::Objects(&[MATERIAL]))]
+        }
+        (MaterialProvider, "SpaceAndDepth") => &[
+            ("Condition", Slot::One(Condition)),
+            ("Layers", Slot::Many(Layer)),
+        ],
+        (MaterialProvider, "DepthBased") => &[
+            ("DepthCurve", Slot::One(Curve)),
+            ("Materials", Slot::Many(MaterialProvider)),
+        ],
+        (MaterialProvider, "GradientBased") => &[
+            ("Gradient", Slot::One(Density)),
+            ("Materials", Slot::Many(MaterialProvider)),
+        ],
+        (MaterialProvider, "Pipeline") => &[("Steps", Slot::Many(MaterialProvider))],
+
+        (Layer, "NoiseThickness") => &[MATERIAL, ("ThicknessFunctionXZ", Slot::One(Density))],
+        (Layer, _) => &[MATERIAL],
+        (Condition, "AndCondition" | "OrCondition") => &[("Conditions", Slot::Many(Condition))],
+        (Condition, "NotCondition") => &[("Condition", Slot::One(Condition))],
+
+        (PositionProvider, "Mesh2D" | "Mesh3D") => &[("PointGenerator", Slot::Object(&[]))],
+        (PositionProvider, "FieldFunction" | "Occurrence") => &[FIELD_FUNCTION, POSITIONS],
+        (
+            PositionProvider,
+            "Offset" | "SimpleHorizontal" | "Cache" | "BaseHeight" | "Anchor" | "Bound",
+        ) => &[POSITIONS],
+        (PositionProvider, "Union") => &[("Positions", Slot::Many(PositionProvider))],
+
+        (Prop, "Box" | "Column" | "PondFiller") => &[MATERIAL, PATTERN, SCANNER],
+        (Prop, "Cluster") => &[
+            ("DistanceCurve", Slot::One(Curve)),
+            ("WeightedProps", Slot::Objects(&[PROP])),
+            PATTERN,
+            SCANNER,
+        ],
+        (Prop, "Density") => &[
+            ("Density", Slot::One(Density)),
+            MATERIAL,
+            PATTERN,
+            SCANNER,
+            ("PlacementMask", Slot::Object(&[])),
+        ],
+        (Prop, "Prefab") => &[
+            ("Directionality", Slot::One(Directionality)),
+            SCANNER,
+            ("BlockMask", Slot::Object(&[])),
+            ("MoldingPattern", Slot::One(Pattern)),
+            ("MoldingScanner", Slot::One(Scanner)),
+        ],
+        (Prop, "Queue" | "Union") => &[("Props", Slot::Many(Prop))],
+        (Prop, "Offset") => &[PROP],
+        (Prop, "Weighted") => &[("Entries", Slot::Objects(&[PROP]))],
+
+        (Directionality, "Static" | "Random") => &[PATTERN],
+        (Directionality, "Pattern") => &[
+            ("NorthPattern", Slot::One(Pattern)),
+            ("SouthPattern", Slot::One(Pattern)),
+            ("EastPattern", Slot::One(Pattern)),
+            ("WestPattern", Slot::One(Pattern)),
+        ],
+
+        (Scanner, "Area") => &[("ChildScanner", Slot::One(Scanner))],
+
+        (Assignment, "Constant") => &[PROP],
+        (Assignment, "FieldFunction") => {
+            &[FIELD_FUNCTION, ("Delimiters", Slot::Objects(&[ASSIGNMENTS]))]
+        }
+        (Assignment, "Sandwich") => &[("Delimiters", Slot::Objects(&[ASSIGNMENTS]))],
+        (Assignment, "Weighted") => &[("WeightedAssignments", Slot::Objects(&[ASSIGNMENTS]))],
+
+        (VectorProvider, "DensityGradient") => &[("Density", Slot::One(Density))],
+        (VectorProvider, "Cache" | "Exported") => &[VECTOR_PROVIDER],
+
+        (EnvironmentProvider | TintProvider, "DensityDelimited") => {
+            &[("Density", Slot::One(Density))]
+        }
+
+        (Framework, "Positions") => &[("Entries", Slot::Objects(&[POSITIONS]))],
+
+        (WorldStructure, "DAOTerrain") => &[("Density", Slot::One(Density))],
+        (WorldStructure, _) => &[
+            ("Density", Slot::One(Density)),
+            ("Framework", Slot::One(Framework)),
+            ("SpawnPositions", Slot::One(PositionProvider)),
+        ],
+        (Biome, _) => &[
+            ("Terrain", Slot::Object(&[("Density", Slot::One(Density))])),
+            ("FloatingFunctionNodes", Slot::Many(Density)),
+            ("MaterialProvider", Slot::One(MaterialProvider)),
+            (
+                "Props",
+                Slot::Objects(&[POSITIONS, ("Assignments", Slot::One(Assignment))]),
+            ),
+            ("EnvironmentProvider", Slot::One(EnvironmentProvider)),
+            ("TintProvider", Slot::One(TintProvider)),
+        ],
+
+        _ => &[],
+    }
+}
+
+/// Pack folders and the category of the assets stored in them.
+const FOLDER_CATEGORIES: &[(&str, AssetCategory)] = &[
+    ("WorldStructures", WorldStructure),
+    ("Biomes", Biome),
+    ("Settings", Settings),
+    ("Density", Density),
+    ("Curves", Curve),
+    ("Patterns", Pattern),
+    ("MaterialProviders", MaterialProvider),
+    ("PositionProviders", PositionProvider),
+    ("Props", Prop),
+    ("Scanners", Scanner),
+    ("Assignments", Assignment),
+    ("VectorProviders", VectorProvider),
+    ("EnvironmentProviders", EnvironmentProvider),
+    ("TintProviders", TintProvider),
+    ("BlockMasks", BlockMask),
+    ("Frameworks", Framework),
+];
+
+/// Category of a pack file's root asset: from its folder when that is a
+/// known category folder, otherwise from its `Type`, preferring density.
+pub fn root_category(file_path: &str, type_name: Option<&str>) -> Option<AssetCategory> {
+    let by_folder = file_path
+        .split(['/', '\\'])
+        .rev()
+        .skip(1)
+        .find_map(|dir| {
+            FOLDER_CATEGORIES
+                .iter()
+                .find(|(folder, _)| *folder == dir)
+                .map(|(_, category)| *category)
+        });
+    if by_folder.is_some() {
+        return by_folder;
+    }
+
+    let type_name = type_name?;
+    const BY_TYPE: &[AssetCategory] = &[
+        Density, WorldStructure, Curve, Pattern, MaterialProvider, PositionProvider, Prop,
+        Scanner, Assignment, VectorProvider, Framework,
+    ];
+    BY_TYPE
+        .iter()
+        .copied()
+        .find(|&category| known_types(category).contains(&type_name))
+}
+
+/// Category to walk a parsed pack file as. Typed roots that no folder or
+/// type places are treated as density, like `validate_asset` does.
+pub fn file_category(file_path: &str, value: &Value) -> Option<AssetCategory> {
+    let type_name = value.get("Type").and_then(|v| v.as_str());
+    match root_category(file_path, type_name) {
+        Some(category) => Some(category),
+        None if type_name.is_some() => Some(Density),
+        None => None,
+    }
+}
+
+/// Visit `value` (an asset of `category`) and every asset nested in it with
+/// its JSON pointer segments. Untyped values, such as biome roots, are not
+/// visited themselves but their slots are followed.
+///
+/// Typed values in fields without a slot are assumed to share their
+/// parent's category.
+pub fn walk(
+    category: AssetCategory,
+    value: &Value,
+    visit: &mut dyn FnMut(&[String], AssetCategory, &Value),
+) {
+    walk_node(&mut Vec::new(), category, value, visit);
+}
+
+fn is_typed(value: &Value) -> bool {
+    value.as_object().is_some_and(|obj| obj.contains_key("Type"))
+}
+
+fn walk_node(
+    segments: &mut Vec<String>,
+    category: AssetCategory,
+    value: &Value,
+    visit: &mut dyn FnMut(&[String], AssetCategory, &Value),
+) {
+    let Some(obj) = value.as_object() else { return };
+    let type_name = obj.get("Type").and_then(|v| v.as_str());
+    if type_name.is_some() {
+        visit(segments, category, value);
+    }
+
+    let slots = child_slots(category, type_name.unwrap_or_default());
+    for (key, val) in obj {
+        if key == "Type" {
+            continue;
+        }
+        let slot = match slots.iter().find(|(field, _)| field == key) {
+            Some((_, slot)) => *slot,
+            None if known_types(category).is_empty() => continue,
+            None if val.is_array() => Slot::Many(category),
+            None => Slot::One(category),
+        };
+        segments.push(key.clone());
+        walk_slot(segments, slot, val, visit);
+        segments.pop();
+    }
+}
+
+fn walk_slot(
+    segments: &mut Vec<String>,
+    slot: Slot,
+    value: &Value,
+    visit: &mut dyn FnMut(&[String], AssetCategory, &Value),
+) {
+    let items: Vec<(Option<usize>, &Value)> = match (slot, value) {
+        (Slot::Many(_) | Slot::Objects(_), Value::Array(arr)) => {
+            arr.iter().enumerate().map(|(i, v)| (Some(i), v)).collect()
+        }
+        (Slot::One(_) | Slot::Object(_), _) => vec![(None, value)],
+        _ => Vec::new(),
+    };
+    for (i, item) in items {
+        if let Some(i) = i {
+            segments.push(i.to_string());
+        }
+        match slot {
+            Slot::One(category) | Slot::Many(category) => {
+                if is_typed(item) {
+                    walk_node(segments, category, item, visit);
+                }
+            }
+            Slot::Object(fields) | Slot::Objects(fields) => {
+                for (field, slot) in fields {
+                    if let Some(val) = item.get(*field) {
+                        segments.push(field.to_string());
+                        walk_slot(segments, *slot, val, visit);
+                        segments.pop();
+                    }
+                }
+            }
+        }
+        if i.is_some() {
+            segments.pop();
+        }
+    }
+}
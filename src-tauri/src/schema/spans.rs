@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// A 1-based line and column; columns count characters, not bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Source range of a JSON value. For object members it starts at the key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Span {
+    pub start: Position,
+    /// Just past the last character.
+    pub end: Position,
+}
+
+/// Byte ranges of every value in a JSON document, by JSON pointer.
+pub struct SpanIndex<'a> {
+    source: &'a str,
+    line_starts: Vec<usize>,
+    ranges: HashMap<String, (usize, usize)>,
+}
+
+impl<'a> SpanIndex<'a> {
+    /// Scan `source` for value positions. Malformed input is indexed up to
+    /// the first error.
+    pub fn new(source: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        let mut scanner = Scanner {
+            source,
+            bytes: source.as_bytes(),
+            pos: 0,
+            ranges: HashMap::new(),
+        };
+        scanner.skip_whitespace();
+        let start = scanner.pos;
+        if let Some(end) = scanner.value(String::new()) {
+            scanner.ranges.insert(String::new(), (start, end));
+        }
+        SpanIndex {
+            source,
+            line_starts,
+            ranges: scanner.ranges,
+        }
+    }
+
+    /// Span of the value at `pointer`, or of its nearest existing ancestor
+    /// (so a missing field points at the object that lacks it).
+    pub fn locate(&self, pointer: &str) -> Option<Span> {
+        let mut pointer = pointer;
+        loop {
+            if let Some(&(start, end)) = self.ranges.get(pointer) {
+                return Some(Span {
+                    start: self.position(start),
+                    end: self.position(end),
+                });
+            }
+            pointer = &pointer[..pointer.rfind('/')?];
+        }
+    }
+
+    /// Position of a byte offset.
+    pub fn position(&self, offset: usize) -> Position {
+        let line = self.line_starts.partition_point(|&s| s <= offset) - 1;
+        let start = self.line_starts[line];
+        Position {
+            line: line + 1,
+            column: self.source[start..offset].chars().count() + 1,
+        }
+    }
+}
+
+struct Scanner<'a> {
+    source: &'a str,
+    bytes: &'a [u8],
+    pos: usize,
+    ranges: HashMap<String, (usize, usize)>,
+}
+
+impl Scanner<'_> {
+    fn skip_whitespace(&mut self) {
+        while self
+            .bytes
+            .get(self.pos)
+            .is_some_and(|b| b.is_ascii_whitespace())
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        self.skip_whitespace();
+        if self.bytes.get(self.pos) == Some(&byte) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Scan the value at the cursor, recording nested values under
+    /// `pointer`. Returns the offset just past it.
+    fn value(&mut self, pointer: String) -> Option<usize> {
+        self.skip_whitespace();
+        match *self.bytes.get(self.pos)? {
+            b'{' => self.object(pointer),
+            b'[' => self.array(pointer),
+            b'"' => self.string().map(|_| self.pos),
+            _ => {
+                // Numbers and literals run until the next delimiter
+                let start = self.pos;
+                while self
+                    .bytes
+                    .get(self.pos)
+                    .is_some_and(|b| !b.is_ascii_whitespace() && !matches!(b, b',' | b']' | b'}'))
+                {
+                    self.pos += 1;
+                }
+                (self.pos > start).then_some(self.pos)
+            }
+        }
+    }
+
+    fn object(&mut self, pointer: String) -> Option<usize> {
+        self.pos += 1;
+        if self.eat(b'}') {
+            return Some(self.pos);
+        }
+        loop {
+            self.skip_whitespace();
+            let key_start = self.pos;
+            let key = self.string()?;
+            if !self.eat(b':') {
+                return None;
+            }
+            let child = format!("{}/{}", pointer, key.replace('~', "~0").replace('/', "~1"));
+            let end = self.value(child.clone())?;
+            self.ranges.insert(child, (key_start, end));
+            if self.eat(b'}') {
+                return Some(self.pos);
+            }
+            if !self.eat(b',') {
+                return None;
+            }
+        }
+    }
+
+    fn array(&mut self, pointer: String) -> Option<usize> {
+        self.pos += 1;
+        if self.eat(b']') {
+            return Some(self.pos);
+        }
+        let mut i = 0;
+        loop {
+            self.skip_whitespace();
+            let start = self.pos;
+            let child = format!("{}/{}", pointer, i);
+            let end = self.value(child.clone())?;
+            self.ranges.insert(child, (start, end));
+            if self.eat(b']') {
+                return Some(self.pos);
+            }
+            if !self.eat(b',') {
+                return None;
+            }
+            i += 1;
+        }
+    }
+
+    /// Scan a string literal at the cursor and return its decoded text.
+    fn string(&mut self) -> Option<String> {
+        let start = self.pos;
+        if self.bytes.get(self.pos) != Some(&b'"') {
+            return None;
+        }
+        self.pos += 1;
+        loop {
+            match *self.bytes.get(self.pos)? {
+                b'\\' => self.pos += 2,
+                b'"' => break,
+                _ => self.pos += 1,
+            }
+        }
+        self.pos += 1;
+        serde_json::from_str(&self.source[start..self.pos]).ok()
+    }
+}
@@ -2,7 +2,9 @@
 mod tests {
     use crate::schema::biome::BiomeAsset;
     use crate::schema::density::DensityType;
+    use crate::schema::references::validate_references;
     use crate::schema::settings::SettingsAsset;
+    use crate::schema::validation::Severity;
     use crate::schema::world_structure::WorldStructureAsset;
 
     /// Helper: deserialize JSON string, reserialize, and compare parsed values.
@@ -110,6 +112,27 @@ mod tests {
         assert!(reserialized.contains("42.0"));
     }
 
+    #[test]
+    fn optional_seed_accepts_null_numbers_and_names() {
+        use crate::schema::material::MaterialProviderType;
+
+        let seed_of = |seed: serde_json::Value| {
+            let json = serde_json::json!({ "Type": "Weighted", "Seed": seed });
+            match serde_json::from_value::<MaterialProviderType>(json) {
+                Ok(MaterialProviderType::Weighted { seed, .. }) => Ok(seed),
+                Ok(other) => panic!("expected Weighted, got {:?}", other),
+                Err(e) => Err(e.to_string()),
+            }
+        };
+        assert_eq!(seed_of(serde_json::Value::Null), Ok(None));
+        assert_eq!(seed_of(serde_json::json!(42)), Ok(Some("42".to_string())));
+        assert_eq!(
+            seed_of(serde_json::json!("ore")),
+            Ok(Some("ore".to_string()))
+        );
+        assert!(seed_of(serde_json::json!(true)).is_err());
+    }
+
     #[test]
     fn density_simplex_noise_2d_round_trip() {
         let json = r#"{
@@ -128,6 +151,7 @@ mod tests {
                 scale,
                 octaves,
                 seed,
+                ..
             } => {
                 assert_eq!(*lacunarity, Some(2.0));
                 assert_eq!(*persistence, Some(0.5));
@@ -154,6 +178,7 @@ mod tests {
                 wall_a,
                 wall_b,
                 input,
+                ..
             } => {
                 assert_eq!(*wall_a, Some(-1.0));
                 assert_eq!(*wall_b, Some(1.0));
@@ -201,7 +226,7 @@ mod tests {
         }"#;
         let density: DensityType = serde_json::from_str(json).expect("deserialize Sum");
         match &density {
-            DensityType::Sum { inputs } => {
+            DensityType::Sum { inputs, .. } => {
                 assert_eq!(inputs.len(), 2);
             }
             other => panic!("expected Sum, got {:?}", other),
@@ -434,7 +459,7 @@ mod tests {
         let json = r#"{"Type": "CellNoise2D", "Scale": 128.0, "Seed": "biome", "ReturnType": "Distance", "DistanceFunction": "Euclidean"}"#;
         let density: DensityType = serde_json::from_str(json).expect("deserialize CellNoise2D");
         match &density {
-            DensityType::CellNoise2D { scale, seed, return_type, distance_function } => {
+            DensityType::CellNoise2D { scale, seed, return_type, distance_function, .. } => {
                 assert_eq!(*scale, Some(128.0));
                 assert_eq!(seed.as_deref(), Some("biome"));
                 assert_eq!(return_type.as_deref(), Some("Distance"));
@@ -475,7 +500,7 @@ mod tests {
         let json = r#"{"Type": "AmplitudeConstant", "Amplitude": 2.5, "Input": {"Type": "Constant", "Value": 1.0}}"#;
         let density: DensityType = serde_json::from_str(json).expect("deserialize AmplitudeConstant");
         match &density {
-            DensityType::AmplitudeConstant { amplitude, input } => {
+            DensityType::AmplitudeConstant { amplitude, input, .. } => {
                 assert_eq!(*amplitude, Some(2.5));
                 assert!(input.is_some());
             }
@@ -497,4 +522,435 @@ mod tests {
             other => panic!("expected SmoothClamp, got {:?}", other),
         }
     }
+
+    fn pack(files: &[(&str, &str)]) -> Vec<(String, serde_json::Value)> {
+        files
+            .iter()
+            .map(|(path, json)| (path.to_string(), serde_json::from_str(json).expect("parse pack file")))
+            .collect()
+    }
+
+    #[test]
+    fn references_resolve_across_files() {
+        let files = pack(&[
+            ("Biomes/Forest.json", r#"{"Name": "Forest", "Terrain": {"Type": "DAOTerrain", "Density": {"Type": "Imported", "Name": "Hills"}}}"#),
+            ("Density/Hills.json", r#"{"Type": "Exported", "ExportAs": "Hills", "Density": {"Type": "Constant", "Value": 1.0}}"#),
+            ("WorldStructures/Main.json", r#"{"Type": "NoiseRange", "DefaultBiome": "Forest", "Biomes": [{"Biome": "Forest", "Min": -1.0, "Max": 1.0}],
+                "Framework": {"Type": "Positions", "Entries": [{"Name": "Spawn"}]},
+                "SpawnPositions": {"Type": "Framework", "Name": "Spawn"}}"#),
+        ]);
+        let errors = validate_references(&files);
+        assert!(errors.is_empty(), "expected no errors, got: {:?}", errors);
+    }
+
+    #[test]
+    fn references_report_unresolved_names() {
+        let files = pack(&[
+            ("Biomes/Forest.json", r#"{"Name": "Forest", "Terrain": {"Type": "DAOTerrain", "Density": {"Type": "Imported", "Name": "Missing"}}}"#),
+            ("WorldStructures/Main.json", r#"{"Type": "NoiseRange", "DefaultBiome": "Forest", "Biomes": [{"Biome": "Desert", "Min": -1.0, "Max": 1.0}],
+                "SpawnPositions": {"Type": "Framework", "Name": "Spawn"}}"#),
+        ]);
+        let errors = validate_references(&files);
+        let mut paths: Vec<(&str, &Severity)> = errors.iter().map(|e| (e.path.as_str(), &e.severity)).collect();
+        paths.sort_by_key(|(path, _)| *path);
+        assert_eq!(
+            paths,
+            [
+                ("Biomes/Forest.json#/Terrain/Density/Name", &Severity::Warning),
+                ("WorldStructures/Main.json#/Biomes/0/Biome", &Severity::Error),
+                ("WorldStructures/Main.json#/SpawnPositions/Name", &Severity::Error),
+            ]
+        );
+    }
+
+    #[test]
+    fn references_require_matching_category() {
+        let files = pack(&[
+            ("Curves/Falloff.json", r#"{"Type": "Exported", "ExportAs": "Falloff", "Curve": {"Type": "Manual"}}"#),
+            ("Density/Use.json", r#"{"Type": "Abs", "Input": {"Type": "Imported", "Name": "Falloff"}}"#),
+        ]);
+        let errors = validate_references(&files);
+        assert_eq!(errors.len(), 1, "got: {:?}", errors);
+        assert_eq!(errors[0].severity, Severity::Error);
+        assert!(errors[0].message.contains("Curve"));
+    }
+
+    #[test]
+    fn references_flag_duplicate_exports() {
+        let files = pack(&[
+            ("Density/A.json", r#"{"Type": "Exported", "ExportAs": "Base", "Density": {"Type": "Constant", "Value": 1.0, "ExportAs": "Base"}}"#),
+            ("Density/B.json", r#"{"Type": "Exported", "ExportAs": "Base", "Density": {"Type": "Constant", "Value": 2.0}}"#),
+        ]);
+        let errors = validate_references(&files);
+        // The inner node repeating its enclosing export's name is not a duplicate.
+        let paths: Vec<&str> = errors.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, ["Density/A.json#/ExportAs", "Density/B.json#/ExportAs"]);
+    }
+
+    #[test]
+    fn references_detect_import_cycles() {
+        let files = pack(&[
+            ("Density/A.json", r#"{"Type": "Exported", "ExportAs": "A", "Density": {"Type": "Imported", "Name": "B"}}"#),
+            ("Density/B.json", r#"{"Type": "Exported", "ExportAs": "B", "Density": {"Type": "Abs", "Input": {"Type": "Imported", "Name": "A"}}}"#),
+            ("Density/C.json", r#"{"Type": "Exported", "ExportAs": "C", "Density": {"Type": "Imported", "Name": "A"}}"#),
+        ]);
+        let errors = validate_references(&files);
+        let paths: Vec<&str> = errors.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, ["Density/A.json#/Density/Name", "Density/B.json#/Density/Input/Name"]);
+        assert!(errors[0].message.contains("A -> B -> A"));
+    }
+
+    #[test]
+    fn spans_locate_values_by_pointer() {
+        use crate::schema::spans::{Position, SpanIndex};
+
+        let source = "{\n  \"Type\": \"Sum\",\n  \"Inputs\": [\n    {\"Type\": \"Constant\", \"Value\": 1.5},\n    {\"Type\": \"Imported\", \"Name\": \"Über\"}\n  ]\n}";
+        let index = SpanIndex::new(source);
+
+        // Object members span from the key to the end of the value
+        let value = index.locate("/Inputs/0/Value").unwrap();
+        assert_eq!(value.start, Position { line: 4, column: 26 });
+        assert_eq!(value.end, Position { line: 4, column: 38 });
+
+        let element = index.locate("/Inputs/1").unwrap();
+        assert_eq!(element.start, Position { line: 5, column: 5 });
+
+        // Columns count characters, so the multi-byte 'Ü' is one column
+        let name = index.locate("/Inputs/1/Name").unwrap();
+        assert_eq!(name.end, Position { line: 5, column: 40 });
+
+        // A missing field falls back to the object that lacks it
+        assert_eq!(index.locate("/Inputs/0/Missing"), index.locate("/Inputs/0"));
+        assert_eq!(index.locate("").unwrap().start, Position { line: 1, column: 1 });
+    }
+
+    #[test]
+    fn patch_applies_rfc6902_operations() {
+        use crate::schema::patch::{apply, PatchOp};
+        use serde_json::json;
+
+        let mut doc = json!({"Type": "Abs", "Input": {"Type": "Constant"}, "Tags": ["a"]});
+        let ops: Vec<PatchOp> = serde_json::from_value(json!([
+            {"op": "test", "path": "/Type", "value": "Abs"},
+            {"op": "add", "path": "/Inputs", "value": []},
+            {"op": "move", "from": "/Input", "path": "/Inputs/0"},
+            {"op": "add", "path": "/Tags/-", "value": "b"},
+            {"op": "copy", "from": "/Tags/0", "path": "/Tags/0"},
+            {"op": "replace", "path": "/Type", "value": "Sum"},
+            {"op": "remove", "path": "/Tags/1"}
+        ]))
+        .unwrap();
+        apply(&mut doc, &ops).unwrap();
+        assert_eq!(
+            doc,
+            json!({"Type": "Sum", "Inputs": [{"Type": "Constant"}], "Tags": ["a", "b"]})
+        );
+
+        // Failures name the pointer that didn't resolve
+        let err = apply(&mut doc, &[PatchOp::Remove { path: "/Missing".into() }]).unwrap_err();
+        assert!(err.contains("/Missing"));
+        let err = apply(
+            &mut doc,
+            &[PatchOp::Test { path: "/Type".into(), value: json!("Abs") }],
+        )
+        .unwrap_err();
+        assert!(err.contains("Test failed"));
+    }
+
+    #[test]
+    fn lint_config_overrides_ignores_and_suppresses() {
+        use crate::schema::lint::{apply_lint, LintConfig};
+        use crate::schema::validation::{validate_asset, DiagnosticCode};
+        use crate::schema::versions::CURRENT;
+
+        let files = pack(&[
+            ("Density/A.json", r#"{"Type": "Sum", "Inputs": [{"Type": "Imported", "Name": "Missing"}, {"Type": "Mystery"}]}"#),
+            ("Density/Drafts/B.json", r#"{"Type": "Mystery"}"#),
+            ("Density/C.json", r#"{"Type": "Sum", "Inputs": [{"Type": "Mystery", "$Suppress": ["UNKNOWN_TYPE"]}, {"Type": "Constant", "Value": 1.0}]}"#),
+        ]);
+        let mut errors: Vec<_> = files.iter().flat_map(|(file, value)| validate_asset(file, value, CURRENT)).collect();
+        errors.extend(validate_references(&files));
+        assert_eq!(errors.iter().filter(|e| e.code == DiagnosticCode::UnknownType).count(), 3);
+
+        let config: LintConfig = serde_json::from_str(
+            r#"{"rules": {"UNRESOLVED_IMPORT": "error"}, "ignore": [{"paths": ["Density/Drafts"], "codes": ["UNKNOWN_TYPE"]}]}"#,
+        )
+        .unwrap();
+        let errors = apply_lint(errors, &config, &files);
+        let found: Vec<(&str, DiagnosticCode, &Severity)> =
+            errors.iter().map(|e| (e.path.as_str(), e.code, &e.severity)).collect();
+        assert_eq!(
+            found,
+            [
+                ("Density/A.json#/Inputs/1/Type", DiagnosticCode::UnknownType, &Severity::Warning),
+                ("Density/A.json#/Inputs/0/Name", DiagnosticCode::UnresolvedImport, &Severity::Error),
+            ]
+        );
+
+        // Unknown keys in the config are rejected rather than ignored
+        assert!(serde_json::from_str::<LintConfig>(r#"{"rule": {}}"#).is_err());
+    }
+
+    #[test]
+    fn lint_config_found_next_to_manifest() {
+        use crate::schema::lint::{apply_lint, LintConfig, CONFIG_FILE};
+        use crate::schema::validation::validate_asset;
+        use crate::schema::versions::CURRENT;
+
+        let root = std::env::temp_dir().join(format!("terranova-lint-{}", std::process::id()));
+        let pack_dir = root.join("HytaleGenerator");
+        std::fs::create_dir_all(&pack_dir).unwrap();
+        std::fs::write(root.join("manifest.json"), "{}").unwrap();
+        assert!(LintConfig::load(&pack_dir).unwrap().is_none());
+
+        // Ignore paths are relative to the config, not the validated pack
+        std::fs::write(
+            root.join(CONFIG_FILE),
+            r#"{"ignore": [{"paths": ["HytaleGenerator/**/*.json"]}]}"#,
+        )
+        .unwrap();
+        let config = LintConfig::load(&pack_dir).unwrap().unwrap();
+        let files = pack(&[("Density/A.json", r#"{"Type": "Mystery"}"#)]);
+        let errors = validate_asset(&files[0].0, &files[0].1, CURRENT);
+        assert!(!errors.is_empty());
+        assert!(apply_lint(errors, &config, &files).is_empty());
+
+        std::fs::write(root.join(CONFIG_FILE), "{").unwrap();
+        assert!(LintConfig::load(&pack_dir).is_err());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn applied_fixes_keep_key_order() {
+        use crate::commands::validate::apply_fixes;
+        use crate::schema::patch::PatchOp;
+        use crate::schema::validation::Fix;
+        use serde_json::json;
+
+        let root = std::env::temp_dir().join(format!("terranova-fix-order-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("Density")).unwrap();
+        std::fs::write(
+            root.join("Density/A.json"),
+            r#"{"Type": "Clamp", "WallB": 1.0, "Input": {"Type": "Constant", "Value": 0.5}}"#,
+        )
+        .unwrap();
+        let fix = Fix {
+            title: "Add 'WallA'".to_string(),
+            file: "Density/A.json".to_string(),
+            edits: vec![PatchOp::Add { path: "/WallA".to_string(), value: json!(0.0) }],
+        };
+        apply_fixes(root.to_string_lossy().to_string(), vec![fix]).unwrap();
+
+        let written = std::fs::read_to_string(root.join("Density/A.json")).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&written).unwrap();
+        let keys: Vec<_> = value.as_object().unwrap().keys().collect();
+        assert_eq!(keys, ["Type", "WallB", "Input", "WallA"]);
+        let keys: Vec<_> = value["Input"].as_object().unwrap().keys().collect();
+        assert_eq!(keys, ["Type", "Value"]);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn migration_plan_chains_versions() {
+        use crate::schema::migration::plan;
+        use crate::schema::patch::apply;
+        use crate::schema::validation::current_field_rules;
+        use crate::schema::versions::{MigrationStep, SchemaVersion, Transform};
+        use crate::schema::AssetCategory::{Density, Scanner};
+        use serde_json::json;
+
+        const V2: &[MigrationStep] = &[
+            MigrationStep::RenameType { category: Density, from: "Noise2D", to: "SimplexNoise2D" },
+            MigrationStep::RenameField { category: Density, node_type: None, from: "Source", to: "Input" },
+        ];
+        const V3: &[MigrationStep] = &[
+            // Sees the type renamed by the previous version
+            MigrationStep::RenameField { category: Density, node_type: Some("SimplexNoise2D"), from: "Frequency", to: "Scale" },
+            MigrationStep::Transform { category: Density, node_type: Some("SimplexNoise2D"), field: "Scale", transform: Transform::Reciprocal },
+            MigrationStep::MoveField { category: Scanner, node_type: Some("ColumnLinear"), from: "/Range/Min", to: "/Bounds/MinY" },
+        ];
+        let versions = [
+            SchemaVersion { id: "1", build: "1-a", steps: &[], rules: current_field_rules },
+            SchemaVersion { id: "2", build: "2-b", steps: V2, rules: current_field_rules },
+            SchemaVersion { id: "3", build: "3-c", steps: V3, rules: current_field_rules },
+        ];
+
+        let files = pack(&[
+            ("Density/A.json", r#"{"Type": "Abs", "Source": {"Type": "Abs", "Source": {"Type": "Noise2D", "Frequency": 0.01}}}"#),
+            ("Scanners/S.json", r#"{"Type": "ColumnLinear", "Range": {"Min": 4, "Max": 8}}"#),
+        ]);
+        let fixes = plan(&versions, "1", "3", &files).unwrap();
+        let titles: Vec<&str> = fixes.iter().map(|f| f.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            [
+                "2: Rename Density type Noise2D to SimplexNoise2D",
+                "2: Rename Density field Source to Input",
+                "3: Rename Density SimplexNoise2D field Frequency to Scale",
+                "3: Rewrite Density SimplexNoise2D Scale (Reciprocal)",
+                "3: Move Scanner ColumnLinear /Range/Min to /Bounds/MinY",
+            ]
+        );
+
+        // Applying the fixes in order performs the migration
+        let mut migrated = files.clone();
+        for fix in &fixes {
+            let (_, value) = migrated.iter_mut().find(|(file, _)| *file == fix.file).unwrap();
+            apply(value, &fix.edits).unwrap();
+        }
+        assert_eq!(
+            migrated[0].1,
+            json!({"Type": "Abs", "Input": {"Type": "Abs", "Input": {"Type": "SimplexNoise2D", "Scale": 100.0}}})
+        );
+        assert_eq!(
+            migrated[1].1,
+            json!({"Type": "ColumnLinear", "Range": {"Max": 8}, "Bounds": {"MinY": 4}})
+        );
+
+        assert!(plan(&versions, "3", "3", &files).unwrap().is_empty());
+        assert!(plan(&versions, "3", "1", &files).unwrap_err().contains("back to"));
+        assert!(plan(&versions, "0", "3", &files).unwrap_err().contains("Unknown server version"));
+    }
+
+    #[test]
+    fn migrated_files_keep_key_order() {
+        use crate::commands::validate::apply_fixes;
+        use crate::schema::migration::plan;
+        use crate::schema::validation::current_field_rules;
+        use crate::schema::versions::{MigrationStep, SchemaVersion, Transform};
+        use crate::schema::AssetCategory::Density;
+
+        const V2: &[MigrationStep] = &[
+            MigrationStep::RenameType { category: Density, from: "Noise2D", to: "SimplexNoise2D" },
+            MigrationStep::Transform { category: Density, node_type: Some("SimplexNoise2D"), field: "Scale", transform: Transform::Reciprocal },
+        ];
+        let versions = [
+            SchemaVersion { id: "1", build: "1-a", steps: &[], rules: current_field_rules },
+            SchemaVersion { id: "2", build: "2-b", steps: V2, rules: current_field_rules },
+        ];
+
+        let root = std::env::temp_dir().join(format!("terranova-migrate-order-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("Density")).unwrap();
+        let content = r#"{"Type": "Noise2D", "Seed": "hills", "Scale": 0.01, "Octaves": 4}"#;
+        std::fs::write(root.join("Density/A.json"), content).unwrap();
+        let files = pack(&[("Density/A.json", content)]);
+        let fixes = plan(&versions, "1", "2", &files).unwrap();
+        apply_fixes(root.to_string_lossy().to_string(), fixes).unwrap();
+
+        let written = std::fs::read_to_string(root.join("Density/A.json")).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&written).unwrap();
+        let keys: Vec<_> = value.as_object().unwrap().keys().collect();
+        assert_eq!(keys, ["Type", "Seed", "Scale", "Octaves"]);
+        assert_eq!(value["Type"], "SimplexNoise2D");
+        assert_eq!(value["Scale"], 100.0);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn server_version_checked_against_registry() {
+        use crate::schema::validation::{validate_server_version, DiagnosticCode};
+        use crate::schema::versions::CURRENT;
+
+        assert!(validate_server_version(CURRENT).is_none());
+        let error = validate_server_version("1999.01.01").unwrap();
+        assert_eq!(error.code, DiagnosticCode::UnknownServerVersion);
+        assert_eq!(error.path, "manifest.json#/serverVersion");
+    }
+
+    #[test]
+    fn field_rules_follow_the_target_server_version() {
+        use crate::schema::validation::field_rules;
+        use crate::schema::versions::{CURRENT, VERSIONS};
+        use crate::schema::AssetCategory::Density;
+
+        for version in VERSIONS {
+            assert_eq!(field_rules(Density, "SimplexNoise2D", version.id), (version.rules)(Density, "SimplexNoise2D"));
+        }
+        // Unknown versions are validated like the current one
+        assert_eq!(field_rules(Density, "SimplexNoise2D", "1999.01.01"), field_rules(Density, "SimplexNoise2D", CURRENT));
+    }
+
+    #[test]
+    fn schema_description_covers_rust_definitions() {
+        use crate::schema::describe::{describe_schema, SlotKind, ValueKind};
+        use crate::schema::slots::known_types;
+        use crate::schema::validation::field_rules;
+        use crate::schema::versions::CURRENT;
+        use crate::schema::AssetCategory;
+        use serde_json::json;
+
+        let schema = describe_schema();
+        assert_eq!(schema.categories.len(), 19);
+        for category in &schema.categories {
+            let known = known_types(category.category);
+            if !known.is_empty() {
+                let names: Vec<_> = category.types.iter().filter_map(|t| t.name.as_deref()).collect();
+                assert_eq!(names, known, "{:?}", category.category);
+            }
+            for node in &category.types {
+                let name = format!("{:?} {:?}", category.category, node.name);
+                if category.category == AssetCategory::Density {
+                    assert!(node.doc.is_some(), "{} has no doc", name);
+                }
+                // Slots and rules must name declared fields, or the description drifts
+                for (field, _) in field_rules(category.category, node.name.as_deref().unwrap_or(""), CURRENT) {
+                    assert!(node.fields.iter().any(|f| f.name == *field), "{} rule on undeclared {}", name, field);
+                }
+                for slot in &node.slots {
+                    assert!(
+                        node.fields.iter().any(|f| f.name == slot.field),
+                        "{} slot on undeclared {}",
+                        name,
+                        slot.field
+                    );
+                }
+            }
+        }
+
+        let density = &schema.categories[0];
+        let simplex = density.types.iter().find(|t| t.name.as_deref() == Some("SimplexNoise2D")).unwrap();
+        assert!(simplex.doc.as_deref().unwrap().starts_with("2D simplex noise"));
+        let scale = simplex.fields.iter().find(|f| f.name == "Scale").unwrap();
+        assert!(matches!(scale.value, ValueKind::Number));
+        assert_eq!(scale.exclusive_minimum, Some(0.0));
+        let octaves = simplex.fields.iter().find(|f| f.name == "Octaves").unwrap();
+        assert!(matches!(octaves.value, ValueKind::Integer));
+        assert_eq!(octaves.minimum, Some(1.0));
+
+        let frequency = simplex.fields.iter().find(|f| f.name == "Frequency").unwrap();
+        assert_eq!(frequency.default, Some(json!(0.01)));
+        // Unset optional fields have no default rather than `null`
+        assert_eq!(scale.default, None);
+
+        // Clamp's walls default like its editor form, Min below Max
+        let clamp = density.types.iter().find(|t| t.name.as_deref() == Some("Clamp")).unwrap();
+        let default = |name: &str| clamp.fields.iter().find(|f| f.name == name).unwrap().default.clone();
+        assert_eq!((default("Min"), default("Max")), (Some(json!(0.0)), Some(json!(1.0))));
+        assert_eq!((default("WallA"), default("WallB")), (Some(json!(0.0)), Some(json!(1.0))));
+        assert!(clamp.fields.iter().find(|f| f.name == "WallB").unwrap().required);
+
+        let normalizer = density.types.iter().find(|t| t.name.as_deref() == Some("Normalizer")).unwrap();
+        let source_range = normalizer.fields.iter().find(|f| f.name == "SourceRange").unwrap();
+        assert_eq!(source_range.default, Some(json!({"Min": -1, "Max": 1})));
+
+        let sum = density.types.iter().find(|t| t.name.as_deref() == Some("Sum")).unwrap();
+        assert_eq!(sum.fields.iter().find(|f| f.name == "Inputs").unwrap().min_items, Some(2));
+        assert!(matches!(sum.slots[0].slot, SlotKind::Many { category: AssetCategory::Density }));
+
+        let value = serde_json::to_value(&schema).unwrap();
+        let settings = value["categories"].as_array().unwrap().last().unwrap();
+        assert_eq!(settings["category"], "Settings");
+        assert_eq!(settings["types"][0]["name"], json!(null));
+        let concurrency = &settings["types"][0]["fields"][0];
+        assert_eq!(concurrency["name"], "CustomConcurrency");
+        assert_eq!(concurrency["kind"], "integer");
+        assert_eq!(concurrency["default"], -1);
+        assert_eq!(concurrency["minimum"], -1.0);
+
+        let block_mask = value["categories"].as_array().unwrap().iter().find(|c| c["category"] == "BlockMask").unwrap();
+        let advanced = &block_mask["types"][0]["fields"][2];
+        assert_eq!(advanced["doc"], "Advanced per-source replacement rules");
+        assert_eq!(advanced["kind"], "array");
+        assert_eq!(advanced["items"]["fields"][1]["name"], "CanReplace");
+    }
 }
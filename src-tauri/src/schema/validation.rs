@@ -1,5 +1,27 @@
+use super::assignments::AssignmentType;
+use super::biome::{BiomeAsset, TerrainAsset};
+use super::block_masks::BlockMaskAsset;
+use super::curves::CurveType;
+use super::density::DensityType;
+use super::environment::{EnvironmentProviderType, TintProviderType};
+use super::framework::FrameworkType;
+use super::material::{ConditionType, LayerType, MaterialProviderType};
+use super::patch::PatchOp;
+use super::patterns::PatternType;
+use super::positions::PositionProviderType;
+use super::props::{DirectionalityType, PropType};
+use super::scanners::ScannerType;
+use super::settings::SettingsAsset;
+use super::slots::{known_types, root_category, walk};
+use super::spans::Span;
+use super::vectors::VectorProviderType;
+use super::versions;
+use super::world_structure::WorldStructureAsset;
+use super::AssetCategory;
+use crate::noise::bands::{analyze_bands, BandFindingKind};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{json, Value};
 
 /// Severity level for validation errors.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -9,13 +31,91 @@ pub enum Severity {
     Info,
 }
 
+/// Stable identifier of the rule behind a validation error, used by lint
+/// configs and inline suppressions. Serialized as e.g. `DUPLICATE_EXPORT`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DiagnosticCode {
+    /// The file is not valid JSON.
+    InvalidJson,
+    /// The file could not be read.
+    ReadFailed,
+    /// The lint config could not be read or parsed.
+    InvalidConfig,
+    /// The root value is not a JSON object.
+    NotAnObject,
+    /// An asset that needs a `Type` has none.
+    MissingType,
+    /// `Type` is not a known type for the slot's category.
+    UnknownType,
+    /// A field the node's type doesn't declare, so it is ignored.
+    UnknownField,
+    /// A value doesn't match the schema type of its field.
+    InvalidValue,
+    /// A required field is absent.
+    MissingField,
+    /// A field that must be positive is zero or negative.
+    NonPositiveValue,
+    /// A number is below the field's minimum.
+    ValueBelowMinimum,
+    /// An array has fewer items than the node needs.
+    TooFewItems,
+    /// Selector values matched by no NoiseRange band.
+    BandGap,
+    /// Two NoiseRange bands share selector values.
+    BandOverlap,
+    /// A NoiseRange band has `Min` greater than `Max`.
+    BandInverted,
+    /// A NoiseRange band lies outside the selector's range.
+    BandUnreachable,
+    /// A NoiseRange band is narrower than its transition distance.
+    BandTransitionTooWide,
+    /// A biome name matches no biome in the pack.
+    UnresolvedBiome,
+    /// A `Framework` provider names no Positions framework entry.
+    UnresolvedFramework,
+    /// Several nodes share an `ExportAs` name.
+    DuplicateExport,
+    /// An `Imported` name is exported nowhere in the pack.
+    UnresolvedImport,
+    /// An `Imported` name resolves to an export of another category.
+    TypeMismatch,
+    /// Exports import each other in a cycle.
+    CircularReference,
+    /// The project's `serverVersion` is not in the schema registry.
+    UnknownServerVersion,
+    /// The project targets an older server version than the schema.
+    OutdatedServerVersion,
+}
+
 /// A single validation error.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidationError {
+    pub code: DiagnosticCode,
     pub file: String,
     pub field: String,
+    /// JSON path of the offending value, as `<file>#<JSON pointer>`,
+    /// e.g. `Biomes/Forest.json#/Terrain/Density/Inputs/2/Scale`.
+    #[serde(default)]
+    pub path: String,
     pub message: String,
     pub severity: Severity,
+    /// Source range of the offending value, when the file text is known.
+    #[serde(default)]
+    pub span: Option<Span>,
+    /// Machine-applicable fixes, any one of which resolves the error.
+    #[serde(default)]
+    pub fixes: Vec<Fix>,
+}
+
+/// A quick fix: JSON Patch edits to a single file of the pack.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fix {
+    /// Short description for the editor, e.g. "Set 'Scale' to 1".
+    pub title: String,
+    /// Pack-relative path of the file the edits apply to.
+    pub file: String,
+    pub edits: Vec<PatchOp>,
 }
 
 /// Result of validating an asset pack.
@@ -26,281 +126,733 @@ pub struct ValidationResult {
     pub files_checked: usize,
 }
 
-/// All known V2 density type names.
-const KNOWN_DENSITY_TYPES: &[&str] = &[
-    "SimplexNoise2D", "SimplexNoise3D", "CellNoise2D", "CellNoise3D",
-    "Constant", "Sum", "Multiplier", "Abs", "Inverter", "Sqrt", "Pow",
-    "OffsetConstant", "AmplitudeConstant",
-    "Clamp", "SmoothClamp", "Floor", "SmoothFloor", "Ceiling", "SmoothCeiling",
-    "Min", "SmoothMin", "Max", "SmoothMax",
-    "Normalizer", "CurveMapper", "Offset", "Amplitude",
-    "Mix", "MultiMix",
-    "Scale", "Slider", "Rotator", "Anchor", "XOverride", "YOverride", "ZOverride",
-    "GradientWarp", "FastGradientWarp", "VectorWarp",
-    "Distance", "Cube", "Ellipsoid", "Cuboid", "Cylinder", "Plane", "Axis", "Shell", "Angle",
-    "XValue", "YValue", "ZValue",
-    "Terrain", "BaseHeight", "CellWallDistance", "DistanceToBiomeEdge",
-    "Gradient", "Cache", "Cache2D", "YSampled",
-    "Switch", "SwitchState",
-    "PositionsCellNoise", "Positions3D", "PositionsPinch", "PositionsTwist",
-    "Exported", "Imported", "Pipeline",
-];
-
-/// Known top-level asset type names (non-density).
-const KNOWN_STRUCTURE_TYPES: &[&str] = &[
-    "NoiseRange", "DAOTerrain",
-];
+/// Where a node sits in its file: the display label used for
+/// `ValidationError::file` (`file > Inputs[0]`) and its JSON pointer.
+#[derive(Debug, Clone)]
+pub(super) struct Loc<'a> {
+    pub(super) file: &'a str,
+    label: String,
+    pub(super) pointer: String,
+}
+
+impl<'a> Loc<'a> {
+    /// The node at `segments` from the root of `file`.
+    pub(super) fn at(file: &'a str, segments: &[String]) -> Self {
+        let mut loc = Loc {
+            file,
+            label: file.to_string(),
+            pointer: String::new(),
+        };
+        for segment in segments {
+            match segment.parse::<usize>() {
+                Ok(i) => loc.label.push_str(&format!("[{}]", i)),
+                Err(_) => loc.label.push_str(&format!(" > {}", segment)),
+            }
+            loc.pointer.push('/');
+            loc.pointer.push_str(&escape_pointer(segment));
+        }
+        loc
+    }
+
+    /// JSON pointer of the value reached by `segments` from this node.
+    pub(super) fn pointer_to(&self, segments: &[&str]) -> String {
+        let mut pointer = self.pointer.clone();
+        for segment in segments {
+            pointer.push('/');
+            pointer.push_str(&escape_pointer(segment));
+        }
+        pointer
+    }
+
+    /// `<file>#<pointer>` of the value reached by `segments` from this node.
+    pub(super) fn path(&self, segments: &[&str]) -> String {
+        format!("{}#{}", self.file, self.pointer_to(segments))
+    }
+
+    /// A fix applying `edits` to this node's file.
+    pub(super) fn fix(&self, title: String, edits: Vec<PatchOp>) -> Fix {
+        Fix {
+            title,
+            file: self.file.to_string(),
+            edits,
+        }
+    }
+
+    pub(super) fn error(
+        &self,
+        code: DiagnosticCode,
+        field: &str,
+        message: String,
+        severity: Severity,
+    ) -> ValidationError {
+        let segments: &[&str] = if field.is_empty() { &[] } else { &[field] };
+        ValidationError {
+            code,
+            file: self.label.clone(),
+            field: field.to_string(),
+            path: self.path(segments),
+            message,
+            severity,
+            span: None,
+            fixes: Vec::new(),
+        }
+    }
+}
+
+/// Escape a JSON pointer reference token (RFC 6901).
+fn escape_pointer(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
 
 /// Validate a single asset JSON against V2 schema rules.
-pub fn validate_asset(file_path: &str, value: &Value) -> Vec<ValidationError> {
+///
+/// The root's category comes from its folder or `Type` (see
+/// `slots::root_category`); nested assets are checked against the category
+/// their slot expects, so e.g. a curve `Clamp` gets curve rules. Field rules
+/// are those of server `version` (see `field_rules`).
+pub fn validate_asset(file_path: &str, value: &Value, version: &str) -> Vec<ValidationError> {
     let mut errors = Vec::new();
+    let loc = Loc::at(file_path, &[]);
 
     let obj = match value.as_object() {
         Some(obj) => obj,
         None => {
-            errors.push(ValidationError {
-                file: file_path.to_string(),
-                field: String::new(),
-                message: "Root value must be a JSON object".to_string(),
-                severity: Severity::Error,
-            });
+            errors.push(loc.error(
+                DiagnosticCode::NotAnObject,
+                "",
+                "Root value must be a JSON object".to_string(),
+                Severity::Error,
+            ));
             return errors;
         }
     };
 
     // Check for Type field presence on assets that should have one
     let type_name = obj.get("Type").and_then(|v| v.as_str());
+    let category = root_category(file_path, type_name);
 
     if type_name.is_none() {
         // Settings files don't have Type, Biome files might not either
         if file_path.contains("Density") || file_path.contains("WorldStructures") {
-            errors.push(ValidationError {
-                file: file_path.to_string(),
-                field: "Type".to_string(),
-                message: "Missing required 'Type' field".to_string(),
-                severity: Severity::Error,
-            });
+            errors.push(loc.error(
+                DiagnosticCode::MissingType,
+                "Type",
+                "Missing required 'Type' field".to_string(),
+                Severity::Error,
+            ));
         }
-        // For files without Type, validate Settings-specific fields
-        if file_path.contains("Settings") {
-            validate_settings(file_path, obj, &mut errors);
+        match category {
+            Some(AssetCategory::Settings) => {
+                validate_typed(&loc, AssetCategory::Settings, value, &mut errors);
+                validate_fields(&loc, AssetCategory::Settings, value, &mut errors);
+                validate_type_rules(&loc, version, AssetCategory::Settings, "", obj, &mut errors);
+            }
+            Some(category @ (AssetCategory::Biome | AssetCategory::BlockMask)) => {
+                validate_typed(&loc, category, value, &mut errors);
+                validate_fields(&loc, category, value, &mut errors);
+                walk(category, value, &mut |segments, category, node| {
+                    validate_node(
+                        &Loc::at(file_path, segments),
+                        version,
+                        category,
+                        node,
+                        &mut errors,
+                    )
+                });
+            }
+            _ => {}
         }
         return errors;
     }
 
-    let type_name = type_name.unwrap();
-
-    // Check if Type is a known type name
-    let is_known = KNOWN_DENSITY_TYPES.contains(&type_name)
-        || KNOWN_STRUCTURE_TYPES.contains(&type_name);
+    // Unrecognized roots are checked as density, the most common asset kind.
+    walk(
+        category.unwrap_or(AssetCategory::Density),
+        value,
+        &mut |segments, category, node| {
+            validate_node(
+                &Loc::at(file_path, segments),
+                version,
+                category,
+                node,
+                &mut errors,
+            )
+        },
+    );
+    errors
+}
 
-    if !is_known {
-        errors.push(ValidationError {
-            file: file_path.to_string(),
-            field: "Type".to_string(),
-            message: format!("Unknown type '{}' — not a recognized V2 type", type_name),
-            severity: Severity::Warning,
-        });
+/// Check a project's `serverVersion` against the schema registry.
+///
+/// Field rules follow the project's version, but types and fields always
+/// follow the current schema, so any other version gets a diagnostic on
+/// `manifest.json`.
+pub fn validate_server_version(version: &str) -> Option<ValidationError> {
+    let loc = Loc::at("manifest.json", &[]);
+    let current = versions::position(versions::VERSIONS, versions::CURRENT);
+    match versions::position(versions::VERSIONS, version) {
+        Some(position) if Some(position) == current => None,
+        Some(_) => Some(loc.error(
+            DiagnosticCode::OutdatedServerVersion,
+            "serverVersion",
+            format!(
+                "Project targets server {} but the schema describes {}; migrate the pack to upgrade it",
+                version,
+                versions::CURRENT
+            ),
+            Severity::Info,
+        )),
+        None => Some(loc.error(
+            DiagnosticCode::UnknownServerVersion,
+            "serverVersion",
+            format!(
+                "Server version '{}' is not a known schema version; validating against {}",
+                version,
+                versions::CURRENT
+            ),
+            Severity::Warning,
+        )),
     }
+}
 
-    // Per-type validation rules
-    match type_name {
-        "Constant" => {
-            validate_required_field(file_path, obj, "Value", &mut errors);
-        }
-        "SimplexNoise2D" => {
-            validate_positive_field(file_path, obj, "Scale", &mut errors);
-            validate_min_int_field(file_path, obj, "Octaves", 1, &mut errors);
-        }
-        "SimplexNoise3D" => {
-            validate_positive_field(file_path, obj, "ScaleXZ", &mut errors);
-            validate_positive_field(file_path, obj, "ScaleY", &mut errors);
-            validate_min_int_field(file_path, obj, "Octaves", 1, &mut errors);
-        }
-        "CellNoise2D" | "CellNoise3D" => {
-            validate_positive_field(file_path, obj, "Scale", &mut errors);
-        }
-        "Clamp" | "SmoothClamp" => {
-            validate_required_field(file_path, obj, "WallA", &mut errors);
-            validate_required_field(file_path, obj, "WallB", &mut errors);
-        }
-        "Normalizer" => {
-            validate_required_field(file_path, obj, "FromMin", &mut errors);
-            validate_required_field(file_path, obj, "FromMax", &mut errors);
-            validate_required_field(file_path, obj, "ToMin", &mut errors);
-            validate_required_field(file_path, obj, "ToMax", &mut errors);
-        }
-        "Pow" => {
-            validate_required_field(file_path, obj, "Exponent", &mut errors);
-        }
-        "OffsetConstant" => {
-            validate_required_field(file_path, obj, "Offset", &mut errors);
-        }
-        "AmplitudeConstant" => {
-            validate_required_field(file_path, obj, "Amplitude", &mut errors);
-        }
-        "Sum" | "Multiplier" => {
-            validate_min_array_length(file_path, obj, "Inputs", 2, &mut errors);
-        }
-        "Mix" => {
-            validate_min_array_length(file_path, obj, "Inputs", 2, &mut errors);
-        }
-        "Scale" => {
-            validate_required_field(file_path, obj, "X", &mut errors);
-            validate_required_field(file_path, obj, "Y", &mut errors);
-            validate_required_field(file_path, obj, "Z", &mut errors);
-        }
-        "Slider" => {
-            // At least one slide axis should be present
-        }
-        "Min" | "Max" => {
-            validate_min_array_length(file_path, obj, "Inputs", 2, &mut errors);
-        }
-        "SmoothMin" | "SmoothMax" => {
-            validate_required_field(file_path, obj, "Range", &mut errors);
-            validate_min_array_length(file_path, obj, "Inputs", 2, &mut errors);
-        }
-        "Switch" => {
-            validate_required_field(file_path, obj, "SwitchCases", &mut errors);
-        }
-        "Exported" => {
-            // Exported should have either Density or Input
-        }
-        "Imported" => {
-            validate_required_field(file_path, obj, "Name", &mut errors);
-        }
-        "CurveMapper" => {
-            validate_required_field(file_path, obj, "Curve", &mut errors);
+/// Validate a typed asset of `category`; nested assets are visited by `slots::walk`.
+fn validate_node(
+    loc: &Loc,
+    version: &str,
+    category: AssetCategory,
+    value: &Value,
+    errors: &mut Vec<ValidationError>,
+) {
+    let Some(obj) = value.as_object() else { return };
+    let type_name = obj.get("Type").and_then(|v| v.as_str()).unwrap_or_default();
+
+    // Check if Type is a known type name for the slot's category
+    if known_types(category).contains(&type_name) {
+        validate_typed(loc, category, value, errors);
+        validate_fields(loc, category, value, errors);
+    } else {
+        let mut message = format!(
+            "Unknown type '{}' — not a recognized V2 {:?} type",
+            type_name, category
+        );
+        let suggestion = closest(type_name, known_types(category));
+        if let Some(suggestion) = suggestion {
+            message.push_str(&format!("; did you mean '{}'?", suggestion));
         }
-        "Abs" | "Inverter" | "Sqrt" | "Floor" | "Ceiling" => {
-            validate_required_field(file_path, obj, "Input", &mut errors);
+        let mut error = loc.error(
+            DiagnosticCode::UnknownType,
+            "Type",
+            message,
+            Severity::Warning,
+        );
+        if let Some(suggestion) = suggestion {
+            error.fixes.push(loc.fix(
+                format!("Change type to '{}'", suggestion),
+                vec![PatchOp::Replace {
+                    path: loc.pointer_to(&["Type"]),
+                    value: json!(suggestion),
+                }],
+            ));
         }
-        "FastGradientWarp" => {
-            validate_positive_field(file_path, obj, "WarpScale", &mut errors);
-            validate_min_int_field(file_path, obj, "WarpOctaves", 1, &mut errors);
+        errors.push(error);
+    }
+
+    validate_type_rules(loc, version, category, type_name, obj, errors);
+}
+
+/// The candidate closest to `name` by case-insensitive edit distance, if
+/// it is close enough to be a likely typo.
+pub(super) fn closest<'a>(name: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    let name = name.to_lowercase();
+    let limit = (name.chars().count() / 3).max(1);
+    candidates
+        .iter()
+        .map(|&candidate| (edit_distance(&name, &candidate.to_lowercase()), candidate))
+        .filter(|&(distance, _)| distance <= limit)
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance between two strings, by character.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
         }
-        "Cache" => {
-            validate_min_int_field(file_path, obj, "Capacity", 1, &mut errors);
+    }
+    row[b.len()]
+}
+
+/// Deserialize `value` through the category's schema type and serialize it
+/// back.
+///
+/// Nested assets are held as raw JSON by the schema types, so failures here
+/// concern the node's own fields. Absent optional fields come back as
+/// `null`, so the result has every field the type declares.
+fn round_trip(category: AssetCategory, value: &Value) -> Result<Value, String> {
+    fn check<T: DeserializeOwned + Serialize>(value: &Value) -> Result<Value, String> {
+        let typed = T::deserialize(value).map_err(|e| e.to_string())?;
+        serde_json::to_value(typed).map_err(|e| e.to_string())
+    }
+    match category {
+        AssetCategory::Density => check::<DensityType>(value),
+        AssetCategory::Curve => check::<CurveType>(value),
+        AssetCategory::Pattern => check::<PatternType>(value),
+        AssetCategory::MaterialProvider => check::<MaterialProviderType>(value),
+        AssetCategory::Layer => check::<LayerType>(value),
+        AssetCategory::Condition => check::<ConditionType>(value),
+        AssetCategory::PositionProvider => check::<PositionProviderType>(value),
+        AssetCategory::Prop => check::<PropType>(value),
+        AssetCategory::Directionality => check::<DirectionalityType>(value),
+        AssetCategory::Scanner => check::<ScannerType>(value),
+        AssetCategory::Assignment => check::<AssignmentType>(value),
+        AssetCategory::VectorProvider => check::<VectorProviderType>(value),
+        AssetCategory::EnvironmentProvider => check::<EnvironmentProviderType>(value),
+        AssetCategory::TintProvider => check::<TintProviderType>(value),
+        AssetCategory::BlockMask => check::<BlockMaskAsset>(value),
+        AssetCategory::Framework => check::<FrameworkType>(value),
+        AssetCategory::WorldStructure
+            if value.get("Type").and_then(Value::as_str) == Some("DAOTerrain") =>
+        {
+            check::<TerrainAsset>(value)
         }
-        "Gradient" => {
-            validate_required_field(file_path, obj, "FromY", &mut errors);
-            validate_required_field(file_path, obj, "ToY", &mut errors);
+        AssetCategory::WorldStructure => check::<WorldStructureAsset>(value),
+        AssetCategory::Biome => check::<BiomeAsset>(value),
+        AssetCategory::Settings => check::<SettingsAsset>(value),
+    }
+}
+
+/// Warn about fields the schema type doesn't declare, which serde (and the
+/// game) silently ignore. Keys starting with `$` are editor metadata.
+fn validate_fields(
+    loc: &Loc,
+    category: AssetCategory,
+    value: &Value,
+    errors: &mut Vec<ValidationError>,
+) {
+    let type_name = value.get("Type");
+    let (known, deep) = match round_trip(category, value) {
+        Ok(known) => (known, true),
+        // Compare just the node's own keys against a bare node of its type
+        Err(_) => {
+            let bare = match type_name {
+                Some(type_name) => json!({ "Type": type_name }),
+                None => json!({}),
+            };
+            match round_trip(category, &bare) {
+                Ok(known) => (known, false),
+                Err(_) => return,
+            }
         }
-        "NoiseRange" => {
-            validate_required_field(file_path, obj, "DefaultBiome", &mut errors);
-            if let Some(biomes) = obj.get("Biomes") {
-                if let Some(arr) = biomes.as_array() {
-                    if arr.is_empty() {
-                        errors.push(ValidationError {
-                            file: file_path.to_string(),
-                            field: "Biomes".to_string(),
-                            message: "Biomes array should not be empty".to_string(),
-                            severity: Severity::Warning,
-                        });
-                    }
-                    for (i, biome) in arr.iter().enumerate() {
-                        if let Some(bobj) = biome.as_object() {
-                            if !bobj.contains_key("Biome") {
-                                errors.push(ValidationError {
-                                    file: file_path.to_string(),
-                                    field: format!("Biomes[{}].Biome", i),
-                                    message: "Biome entry missing 'Biome' field".to_string(),
-                                    severity: Severity::Error,
-                                });
-                            }
-                        }
+    };
+    let shared = if type_name.is_some() {
+        shared_fields(category)
+    } else {
+        &[]
+    };
+    unknown_fields(loc, &mut Vec::new(), value, &known, shared, deep, errors);
+}
+
+/// Fields every typed node of `category` may carry: the `BaseFields`, and
+/// for density the native `Inputs` form of any node's inputs.
+fn shared_fields(category: AssetCategory) -> &'static [&'static str] {
+    match category {
+        AssetCategory::Density => &["Skip", "ExportAs", "Input", "Inputs"],
+        _ => &["Skip", "ExportAs"],
+    }
+}
+
+/// Report keys of `value` missing from its round-tripped form `known`,
+/// descending into values both have when `deep`. `shared` keys are allowed
+/// on `value` itself but never suggested.
+fn unknown_fields(
+    loc: &Loc,
+    segments: &mut Vec<String>,
+    value: &Value,
+    known: &Value,
+    shared: &[&str],
+    deep: bool,
+    errors: &mut Vec<ValidationError>,
+) {
+    match (value, known) {
+        (Value::Object(obj), Value::Object(known_obj)) => {
+            for (key, child) in obj {
+                if key.starts_with('$') || shared.contains(&key.as_str()) {
+                    continue;
+                }
+                segments.push(key.clone());
+                match known_obj.get(key) {
+                    Some(known_child) if deep => {
+                        unknown_fields(loc, segments, child, known_child, &[], deep, errors)
                     }
+                    Some(_) => {}
+                    None => errors.push(unknown_field(loc, segments, obj, known_obj)),
                 }
+                segments.pop();
+            }
+        }
+        (Value::Array(arr), Value::Array(known_arr)) if deep => {
+            for (i, (child, known_child)) in arr.iter().zip(known_arr).enumerate() {
+                segments.push(i.to_string());
+                unknown_fields(loc, segments, child, known_child, &[], deep, errors);
+                segments.pop();
             }
         }
         _ => {}
     }
+}
 
-    // Recursively validate nested assets
-    for (key, val) in obj.iter() {
-        if key == "Type" { continue; }
-        if let Some(nested_obj) = val.as_object() {
-            if nested_obj.contains_key("Type") {
-                let nested_path = format!("{} > {}", file_path, key);
-                errors.extend(validate_asset(&nested_path, val));
-            }
+/// The warning for the unknown field at `segments`, suggesting the closest
+/// declared field the object doesn't already set.
+fn unknown_field(
+    loc: &Loc,
+    segments: &[String],
+    obj: &serde_json::Map<String, Value>,
+    known: &serde_json::Map<String, Value>,
+) -> ValidationError {
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+    let (key, parents) = segments
+        .split_last()
+        .expect("field segments are never empty");
+    let candidates: Vec<&str> = known
+        .keys()
+        .map(String::as_str)
+        .filter(|k| !obj.contains_key(*k))
+        .collect();
+    let suggestion = closest(key, &candidates);
+
+    let mut message = format!("Unknown field '{}' is ignored", key);
+    if let Some(suggestion) = suggestion {
+        message.push_str(&format!("; did you mean '{}'?", suggestion));
+    }
+    let mut error = loc.error(
+        DiagnosticCode::UnknownField,
+        &segments.join("."),
+        message,
+        Severity::Warning,
+    );
+    error.path = loc.path(&segments);
+    if let Some(suggestion) = suggestion {
+        let mut target = parents.to_vec();
+        target.push(suggestion);
+        error.fixes.push(loc.fix(
+            format!("Rename '{}' to '{}'", key, suggestion),
+            vec![PatchOp::Move {
+                from: loc.pointer_to(&segments),
+                path: loc.pointer_to(&target),
+            }],
+        ));
+    }
+    error
+}
+
+/// Report every field of `value` that fails typed deserialization.
+///
+/// Serde errors from internally tagged enums don't say which field failed,
+/// so each is located by removing values until the error changes.
+fn validate_typed(
+    loc: &Loc,
+    category: AssetCategory,
+    value: &Value,
+    errors: &mut Vec<ValidationError>,
+) {
+    let check = |v: &Value| round_trip(category, v).err();
+    let mut value = value.clone();
+    // Each round removes the blamed value, so this ends within the value count.
+    while let Some(message) = check(&value) {
+        let Some(segments) = blame(&value, &check, &message) else {
+            // Nothing present is at fault, e.g. a missing required field.
+            let missing = message
+                .strip_prefix("missing field `")
+                .and_then(|rest| rest.split('`').next());
+            let code = match missing {
+                Some(_) => DiagnosticCode::MissingField,
+                None => DiagnosticCode::InvalidValue,
+            };
+            errors.push(loc.error(
+                code,
+                missing.unwrap_or_default(),
+                format!("Invalid asset: {}", message),
+                Severity::Error,
+            ));
+            return;
+        };
+        remove_at(&mut value, &segments);
+
+        let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+        let mut error = loc.error(
+            DiagnosticCode::InvalidValue,
+            segments[0],
+            format!("Invalid value: {}", message),
+            Severity::Error,
+        );
+        error.path = loc.path(&segments);
+        errors.push(error);
+    }
+}
+
+/// Pointer segments of the deepest value in `value` whose removal changes
+/// the error `check` reports, or `None` when no present value is at fault.
+fn blame(
+    value: &Value,
+    check: &dyn Fn(&Value) -> Option<String>,
+    message: &str,
+) -> Option<Vec<String>> {
+    let mut segments: Vec<String> = Vec::new();
+    loop {
+        let pointer: String = segments
+            .iter()
+            .map(|s| format!("/{}", escape_pointer(s)))
+            .collect();
+        let keys: Vec<String> = match value.pointer(&pointer) {
+            Some(Value::Object(obj)) => obj.keys().filter(|k| *k != "Type").cloned().collect(),
+            Some(Value::Array(arr)) => (0..arr.len()).map(|i| i.to_string()).collect(),
+            _ => Vec::new(),
+        };
+        let culprit = keys.into_iter().find(|key| {
+            let mut probe = value.clone();
+            let mut path = segments.clone();
+            path.push(key.clone());
+            remove_at(&mut probe, &path);
+            check(&probe).as_deref() != Some(message)
+        });
+        match culprit {
+            Some(key) => segments.push(key),
+            None => break,
         }
-        if let Some(arr) = val.as_array() {
-            for (i, item) in arr.iter().enumerate() {
-                if let Some(item_obj) = item.as_object() {
-                    if item_obj.contains_key("Type") {
-                        let nested_path = format!("{} > {}[{}]", file_path, key, i);
-                        errors.extend(validate_asset(&nested_path, item));
-                    }
-                }
+    }
+    (!segments.is_empty()).then_some(segments)
+}
+
+/// Remove the value at `segments`, if present.
+fn remove_at(value: &mut Value, segments: &[String]) {
+    let Some((last, parents)) = segments.split_last() else {
+        return;
+    };
+    let pointer: String = parents
+        .iter()
+        .map(|s| format!("/{}", escape_pointer(s)))
+        .collect();
+    match value.pointer_mut(&pointer) {
+        Some(Value::Object(obj)) => {
+            // Keep the remaining keys in order, as they decide which error
+            // serde reports first
+            obj.shift_remove(last);
+        }
+        Some(Value::Array(arr)) => {
+            if let Some(i) = last.parse::<usize>().ok().filter(|&i| i < arr.len()) {
+                arr.remove(i);
             }
         }
+        _ => {}
     }
+}
 
-    errors
+/// A constraint on one field of a node type. The same table drives
+/// validation and the schema description returned by `get_schema`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rule {
+    /// The field must be present. Holds JSON text for the value a fix
+    /// inserts, when there is a sensible default.
+    Required(Option<&'static str>),
+    /// A number greater than zero.
+    Positive,
+    /// An integer no smaller than this.
+    AtLeast(i64),
+    /// An array with at least this many items (a warning, not an error).
+    MinItems(usize),
+}
+
+/// Field rules of `type_name` in `category` for packs targeting server
+/// `version`; settings use an empty type. Unknown versions get the current
+/// rules.
+pub fn field_rules(
+    category: AssetCategory,
+    type_name: &str,
+    version: &str,
+) -> &'static [(&'static str, Rule)] {
+    let registry = versions::VERSIONS;
+    let index = versions::position(registry, version).unwrap_or(registry.len() - 1);
+    (registry[index].rules)(category, type_name)
+}
+
+/// Field rules of the current schema version.
+pub fn current_field_rules(
+    category: AssetCategory,
+    type_name: &str,
+) -> &'static [(&'static str, Rule)] {
+    use AssetCategory::*;
+    use Rule::*;
+    match (category, type_name) {
+        (Density, "Constant") => &[("Value", Required(Some("0.0")))],
+        (Density, "SimplexNoise2D") => &[("Scale", Positive), ("Octaves", AtLeast(1))],
+        (Density, "SimplexNoise3D") => &[
+            ("ScaleXZ", Positive),
+            ("ScaleY", Positive),
+            ("Octaves", AtLeast(1)),
+        ],
+        (Density, "CellNoise2D" | "CellNoise3D") => &[("Scale", Positive)],
+        (Density, "Clamp" | "SmoothClamp") => &[
+            ("WallA", Required(Some("0.0"))),
+            ("WallB", Required(Some("1.0"))),
+        ],
+        (Density, "Normalizer") => &[
+            ("FromMin", Required(Some("-1.0"))),
+            ("FromMax", Required(Some("1.0"))),
+            ("ToMin", Required(Some("0.0"))),
+            ("ToMax", Required(Some("1.0"))),
+        ],
+        (Density, "Pow") => &[("Exponent", Required(Some("1.0")))],
+        (Density, "OffsetConstant") => &[("Offset", Required(Some("0.0")))],
+        (Density, "AmplitudeConstant") => &[("Amplitude", Required(Some("1.0")))],
+        (Density, "Sum" | "Multiplier" | "Mix" | "Min" | "Max") => &[("Inputs", MinItems(2))],
+        (Density, "SmoothMin" | "SmoothMax") => {
+            &[("Range", Required(None)), ("Inputs", MinItems(2))]
+        }
+        (Density, "Scale") => &[
+            ("X", Required(Some("1.0"))),
+            ("Y", Required(Some("1.0"))),
+            ("Z", Required(Some("1.0"))),
+        ],
+        (Density, "Switch") => &[("SwitchCases", Required(Some("[]")))],
+        (Density, "Imported") => &[("Name", Required(None))],
+        (Density, "CurveMapper") => &[("Curve", Required(None))],
+        (Density, "Abs" | "Inverter" | "Sqrt" | "Floor" | "Ceiling") => {
+            &[("Input", Required(None))]
+        }
+        (Density, "FastGradientWarp") => &[("WarpScale", Positive), ("WarpOctaves", AtLeast(1))],
+        (Density, "Cache") => &[("Capacity", AtLeast(1))],
+        (Density, "Gradient") => &[("FromY", Required(None)), ("ToY", Required(None))],
+        (WorldStructure, "NoiseRange") => &[("DefaultBiome", Required(None))],
+        (Settings, _) => &[
+            ("CustomConcurrency", AtLeast(-1)),
+            ("BufferCapacityFactor", Positive),
+            ("TargetViewDistance", Positive),
+        ],
+        _ => &[],
+    }
 }
 
-fn validate_settings(
-    file_path: &str,
+/// Per-type validation rules.
+fn validate_type_rules(
+    loc: &Loc,
+    version: &str,
+    category: AssetCategory,
+    type_name: &str,
     obj: &serde_json::Map<String, Value>,
     errors: &mut Vec<ValidationError>,
 ) {
-    if let Some(cc) = obj.get("CustomConcurrency") {
-        if let Some(n) = cc.as_i64() {
-            if n < -1 {
-                errors.push(ValidationError {
-                    file: file_path.to_string(),
-                    field: "CustomConcurrency".to_string(),
-                    message: "CustomConcurrency must be >= -1".to_string(),
-                    severity: Severity::Error,
-                });
+    for (field, rule) in field_rules(category, type_name, version) {
+        match *rule {
+            Rule::Required(default) => {
+                let default = default.map(|d| serde_json::from_str(d).expect("valid default JSON"));
+                validate_required_field(loc, obj, field, default, errors)
             }
+            Rule::Positive => validate_positive_field(loc, obj, field, errors),
+            Rule::AtLeast(min) => validate_min_int_field(loc, obj, field, min, errors),
+            Rule::MinItems(min) => validate_min_array_length(loc, obj, field, min, errors),
         }
     }
-    if let Some(bcf) = obj.get("BufferCapacityFactor") {
-        if let Some(n) = bcf.as_f64() {
-            if n <= 0.0 {
-                errors.push(ValidationError {
-                    file: file_path.to_string(),
-                    field: "BufferCapacityFactor".to_string(),
-                    message: "BufferCapacityFactor must be > 0".to_string(),
-                    severity: Severity::Error,
-                });
+
+    if (category, type_name) == (AssetCategory::WorldStructure, "NoiseRange") {
+        if let Some(biomes) = obj.get("Biomes") {
+            if let Some(arr) = biomes.as_array() {
+                if arr.is_empty() {
+                    errors.push(loc.error(
+                        DiagnosticCode::TooFewItems,
+                        "Biomes",
+                        "Biomes array should not be empty".to_string(),
+                        Severity::Warning,
+                    ));
+                }
+                for (i, biome) in arr.iter().enumerate() {
+                    if let Some(bobj) = biome.as_object() {
+                        if !bobj.contains_key("Biome") {
+                            let mut error = loc.error(
+                                DiagnosticCode::MissingField,
+                                &format!("Biomes[{}].Biome", i),
+                                "Biome entry missing 'Biome' field".to_string(),
+                                Severity::Error,
+                            );
+                            error.path = loc.path(&["Biomes", &i.to_string(), "Biome"]);
+                            errors.push(error);
+                        }
+                    }
+                }
             }
         }
+        validate_bands(loc, obj, errors);
     }
-    if let Some(tvd) = obj.get("TargetViewDistance") {
-        if let Some(n) = tvd.as_f64() {
-            if n <= 0.0 {
-                errors.push(ValidationError {
-                    file: file_path.to_string(),
-                    field: "TargetViewDistance".to_string(),
-                    message: "TargetViewDistance must be > 0".to_string(),
-                    severity: Severity::Error,
-                });
+}
+
+/// Check NoiseRange bands against the selector density's output range.
+fn validate_bands(
+    loc: &Loc,
+    obj: &serde_json::Map<String, Value>,
+    errors: &mut Vec<ValidationError>,
+) {
+    let Some(analysis) = analyze_bands(obj) else {
+        return;
+    };
+    for finding in analysis.findings {
+        let (code, severity) = match finding.kind {
+            BandFindingKind::Gap => (DiagnosticCode::BandGap, Severity::Info),
+            BandFindingKind::Overlap => (DiagnosticCode::BandOverlap, Severity::Warning),
+            BandFindingKind::Inverted => (DiagnosticCode::BandInverted, Severity::Error),
+            BandFindingKind::Unreachable => (DiagnosticCode::BandUnreachable, Severity::Warning),
+            BandFindingKind::TransitionTooWide => {
+                (DiagnosticCode::BandTransitionTooWide, Severity::Warning)
+            }
+        };
+        match finding.band {
+            Some(i) => {
+                let mut error =
+                    loc.error(code, &format!("Biomes[{}]", i), finding.message, severity);
+                error.path = loc.path(&["Biomes", &i.to_string()]);
+                errors.push(error);
             }
+            None => errors.push(loc.error(code, "Biomes", finding.message, severity)),
         }
     }
 }
 
+/// Require `field`; `default`, when there is a sensible one, is offered as
+/// a fix.
 fn validate_required_field(
-    file_path: &str,
+    loc: &Loc,
     obj: &serde_json::Map<String, Value>,
     field: &str,
+    default: Option<Value>,
     errors: &mut Vec<ValidationError>,
 ) {
     if !obj.contains_key(field) {
-        errors.push(ValidationError {
-            file: file_path.to_string(),
-            field: field.to_string(),
-            message: format!("Missing required field '{}'", field),
-            severity: Severity::Error,
-        });
+        let mut error = loc.error(
+            DiagnosticCode::MissingField,
+            field,
+            format!("Missing required field '{}'", field),
+            Severity::Error,
+        );
+        if let Some(value) = default {
+            error.fixes.push(loc.fix(
+                format!("Add '{}' with default {}", field, value),
+                vec![PatchOp::Add {
+                    path: loc.pointer_to(&[field]),
+                    value,
+                }],
+            ));
+        }
+        errors.push(error);
     }
 }
 
 fn validate_positive_field(
-    file_path: &str,
+    loc: &Loc,
     obj: &serde_json::Map<String, Value>,
     field: &str,
     errors: &mut Vec<ValidationError>,
@@ -308,19 +860,29 @@ fn validate_positive_field(
     if let Some(val) = obj.get(field) {
         if let Some(n) = val.as_f64() {
             if n <= 0.0 {
-                errors.push(ValidationError {
-                    file: file_path.to_string(),
-                    field: field.to_string(),
-                    message: format!("'{}' must be > 0 (got {})", field, n),
-                    severity: Severity::Error,
-                });
+                let mut error = loc.error(
+                    DiagnosticCode::NonPositiveValue,
+                    field,
+                    format!("'{}' must be > 0 (got {})", field, n),
+                    Severity::Error,
+                );
+                // Flip the sign if that was the slip, else fall back to 1
+                let value = if n < 0.0 { -n } else { 1.0 };
+                error.fixes.push(loc.fix(
+                    format!("Set '{}' to {}", field, value),
+                    vec![PatchOp::Replace {
+                        path: loc.pointer_to(&[field]),
+                        value: json!(value),
+                    }],
+                ));
+                errors.push(error);
             }
         }
     }
 }
 
 fn validate_min_array_length(
-    file_path: &str,
+    loc: &Loc,
     obj: &serde_json::Map<String, Value>,
     field: &str,
     min_len: usize,
@@ -330,28 +892,51 @@ fn validate_min_array_length(
         Some(val) => {
             if let Some(arr) = val.as_array() {
                 if arr.len() < min_len {
-                    errors.push(ValidationError {
-                        file: file_path.to_string(),
-                        field: field.to_string(),
-                        message: format!("'{}' should have at least {} items (got {})", field, min_len, arr.len()),
-                        severity: Severity::Warning,
-                    });
+                    errors.push(loc.error(
+                        DiagnosticCode::TooFewItems,
+                        field,
+                        format!(
+                            "'{}' should have at least {} items (got {})",
+                            field,
+                            min_len,
+                            arr.len()
+                        ),
+                        Severity::Warning,
+                    ));
                 }
             }
         }
         None => {
-            errors.push(ValidationError {
-                file: file_path.to_string(),
-                field: field.to_string(),
-                message: format!("Missing required field '{}'", field),
-                severity: Severity::Warning,
-            });
+            let mut error = loc.error(
+                DiagnosticCode::MissingField,
+                field,
+                format!("Missing required field '{}'", field),
+                Severity::Warning,
+            );
+            // A lone `Input` where `Inputs` is expected can be wrapped
+            let single = field.strip_suffix('s').filter(|s| obj.contains_key(*s));
+            if let Some(single) = single {
+                error.fixes.push(loc.fix(
+                    format!("Wrap '{}' into '{}'", single, field),
+                    vec![
+                        PatchOp::Add {
+                            path: loc.pointer_to(&[field]),
+                            value: json!([]),
+                        },
+                        PatchOp::Move {
+                            from: loc.pointer_to(&[single]),
+                            path: loc.pointer_to(&[field, "0"]),
+                        },
+                    ],
+                ));
+            }
+            errors.push(error);
         }
     }
 }
 
 fn validate_min_int_field(
-    file_path: &str,
+    loc: &Loc,
     obj: &serde_json::Map<String, Value>,
     field: &str,
     min: i64,
@@ -360,12 +945,20 @@ fn validate_min_int_field(
     if let Some(val) = obj.get(field) {
         if let Some(n) = val.as_i64() {
             if n < min {
-                errors.push(ValidationError {
-                    file: file_path.to_string(),
-                    field: field.to_string(),
-                    message: format!("'{}' must be >= {} (got {})", field, min, n),
-                    severity: Severity::Error,
-                });
+                let mut error = loc.error(
+                    DiagnosticCode::ValueBelowMinimum,
+                    field,
+                    format!("'{}' must be >= {} (got {})", field, min, n),
+                    Severity::Error,
+                );
+                error.fixes.push(loc.fix(
+                    format!("Set '{}' to {}", field, min),
+                    vec![PatchOp::Replace {
+                        path: loc.pointer_to(&[field]),
+                        value: json!(min),
+                    }],
+                ));
+                errors.push(error);
             }
         }
     }
@@ -375,17 +968,84 @@ fn validate_min_int_field(
 mod validation_tests {
     use super::*;
 
+    #[test]
+    fn every_known_type_round_trips_through_its_schema_type() {
+        use AssetCategory::*;
+        let categories = [
+            Density,
+            Curve,
+            Pattern,
+            MaterialProvider,
+            Layer,
+            Condition,
+            PositionProvider,
+            Prop,
+            Directionality,
+            Scanner,
+            Assignment,
+            VectorProvider,
+            EnvironmentProvider,
+            TintProvider,
+            Framework,
+            WorldStructure,
+        ];
+        for category in categories {
+            for &name in known_types(category) {
+                let known = round_trip(category, &json!({ "Type": name }))
+                    .unwrap_or_else(|e| panic!("{:?} '{}': {}", category, name, e));
+                assert_eq!(known["Type"], name, "{:?} '{}'", category, name);
+            }
+        }
+    }
+
+    #[test]
+    fn bundled_templates_have_no_unknown_fields() {
+        fn json_files(dir: &std::path::Path, files: &mut Vec<std::path::PathBuf>) {
+            for entry in std::fs::read_dir(dir).unwrap().flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    json_files(&path, files);
+                } else if path.extension().is_some_and(|ext| ext == "json") {
+                    files.push(path);
+                }
+            }
+        }
+        let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../templates");
+        let mut files = Vec::new();
+        json_files(&root, &mut files);
+        assert!(!files.is_empty());
+
+        for file in files.iter().filter(|f| !f.ends_with("manifest.json")) {
+            let relative = file.strip_prefix(&root).unwrap().to_string_lossy();
+            let value: Value = serde_json::from_str(&std::fs::read_to_string(file).unwrap())
+                .unwrap_or_else(|e| panic!("{}: {}", relative, e));
+            let unknown: Vec<_> = validate_asset(&relative, &value, versions::CURRENT)
+                .into_iter()
+                .filter(|e| e.code == DiagnosticCode::UnknownField)
+                .map(|e| e.path)
+                .collect();
+            assert!(unknown.is_empty(), "{:?}", unknown);
+        }
+    }
+
+    #[test]
+    fn standalone_dao_terrain_is_a_known_root_type() {
+        let value = json!({ "Type": "DAOTerrain", "Density": { "Type": "Constant", "Value": 1 } });
+        let errors = validate_asset("Terrains/Hills.json", &value, versions::CURRENT);
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
     #[test]
     fn valid_constant() {
         let json: Value = serde_json::from_str(r#"{"Type": "Constant", "Value": 1.0}"#).unwrap();
-        let errors = validate_asset("test.json", &json);
+        let errors = validate_asset("test.json", &json, versions::CURRENT);
         assert!(errors.is_empty(), "expected no errors, got: {:?}", errors);
     }
 
     #[test]
     fn missing_constant_value() {
         let json: Value = serde_json::from_str(r#"{"Type": "Constant"}"#).unwrap();
-        let errors = validate_asset("test.json", &json);
+        let errors = validate_asset("test.json", &json, versions::CURRENT);
         assert_eq!(errors.len(), 1);
         assert_eq!(errors[0].field, "Value");
         assert_eq!(errors[0].severity, Severity::Error);
@@ -397,7 +1057,7 @@ mod validation_tests {
             r#"{"Type": "SimplexNoise2D", "Scale": 1.0, "Octaves": 0}"#,
         )
         .unwrap();
-        let errors = validate_asset("test.json", &json);
+        let errors = validate_asset("test.json", &json, versions::CURRENT);
         assert!(errors.iter().any(|e| e.field == "Octaves"));
     }
 
@@ -407,14 +1067,14 @@ mod validation_tests {
             r#"{"Type": "SimplexNoise2D", "Scale": -5.0}"#,
         )
         .unwrap();
-        let errors = validate_asset("test.json", &json);
+        let errors = validate_asset("test.json", &json, versions::CURRENT);
         assert!(errors.iter().any(|e| e.field == "Scale"));
     }
 
     #[test]
     fn unknown_type_warning() {
         let json: Value = serde_json::from_str(r#"{"Type": "MadeUpType"}"#).unwrap();
-        let errors = validate_asset("test.json", &json);
+        let errors = validate_asset("test.json", &json, versions::CURRENT);
         assert!(errors.iter().any(|e| e.severity == Severity::Warning && e.field == "Type"));
     }
 
@@ -424,7 +1084,7 @@ mod validation_tests {
             r#"{"Type": "Sum", "Inputs": [{"Type": "Constant"}]}"#,
         )
         .unwrap();
-        let errors = validate_asset("test.json", &json);
+        let errors = validate_asset("test.json", &json, versions::CURRENT);
         // Nested Constant is missing Value
         assert!(errors.iter().any(|e| e.field == "Value"));
     }
@@ -435,7 +1095,7 @@ mod validation_tests {
             r#"{"CustomConcurrency": -1, "BufferCapacityFactor": 0.3, "TargetViewDistance": 512.0}"#,
         )
         .unwrap();
-        let errors = validate_asset("Settings/Settings.json", &json);
+        let errors = validate_asset("Settings/Settings.json", &json, versions::CURRENT);
         assert!(errors.is_empty(), "expected no errors, got: {:?}", errors);
     }
 
@@ -445,7 +1105,187 @@ mod validation_tests {
             r#"{"CustomConcurrency": -5, "BufferCapacityFactor": 0.3}"#,
         )
         .unwrap();
-        let errors = validate_asset("Settings/Settings.json", &json);
+        let errors = validate_asset("Settings/Settings.json", &json, versions::CURRENT);
         assert!(errors.iter().any(|e| e.field == "CustomConcurrency"));
     }
+
+    #[test]
+    fn curve_clamp_uses_curve_rules() {
+        let json: Value = serde_json::from_str(
+            r#"{"Type": "CurveMapper", "Curve": {"Type": "Clamp", "Curve": {"Type": "Manual"}}, "Input": {"Type": "Constant", "Value": 1.0}}"#,
+        )
+        .unwrap();
+        let errors = validate_asset("test.json", &json, versions::CURRENT);
+        assert!(errors.is_empty(), "expected no errors, got: {:?}", errors);
+    }
+
+    #[test]
+    fn density_type_in_curve_slot_warns() {
+        let json: Value = serde_json::from_str(
+            r#"{"Type": "CurveMapper", "Curve": {"Type": "SimplexNoise2D", "Scale": 1.0}}"#,
+        )
+        .unwrap();
+        let errors = validate_asset("test.json", &json, versions::CURRENT);
+        assert!(errors.iter().any(|e| e.file == "test.json > Curve"
+            && e.field == "Type"
+            && e.severity == Severity::Warning));
+    }
+
+    #[test]
+    fn nested_pattern_accepted() {
+        let json: Value = serde_json::from_str(
+            r#"{"Type": "Column", "Pattern": {"Type": "Floor", "Floor": {"Type": "BlockType"}}}"#,
+        )
+        .unwrap();
+        let errors = validate_asset("Props/Pillar.json", &json, versions::CURRENT);
+        assert!(errors.is_empty(), "expected no errors, got: {:?}", errors);
+    }
+
+    #[test]
+    fn biome_nested_slots() {
+        let json: Value = serde_json::from_str(
+            r#"{"Name": "Forest", "Terrain": {"Type": "DAOTerrain", "Density": {"Type": "Constant"}}, "MaterialProvider": {"Type": "Constant"}}"#,
+        )
+        .unwrap();
+        let errors = validate_asset("Biomes/Forest.json", &json, versions::CURRENT);
+        assert_eq!(errors.len(), 1, "got: {:?}", errors);
+        assert_eq!(errors[0].file, "Biomes/Forest.json > Terrain > Density");
+        assert_eq!(errors[0].field, "Value");
+    }
+
+    #[test]
+    fn typed_error_carries_json_path() {
+        let json: Value = serde_json::from_str(
+            r#"{"Terrain": {"Type": "DAOTerrain", "Density": {"Type": "Sum", "Inputs": [
+                {"Type": "Constant", "Value": 1.0},
+                {"Type": "Constant", "Value": 2.0},
+                {"Type": "SimplexNoise2D", "Scale": "large", "Octaves": 2}
+            ]}}}"#,
+        )
+        .unwrap();
+        let errors = validate_asset("Biomes/Forest.json", &json, versions::CURRENT);
+        assert_eq!(errors.len(), 1, "got: {:?}", errors);
+        assert_eq!(errors[0].path, "Biomes/Forest.json#/Terrain/Density/Inputs/2/Scale");
+        assert_eq!(errors[0].field, "Scale");
+        assert_eq!(errors[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn typed_errors_reported_per_field() {
+        let json: Value = serde_json::from_str(
+            r#"{"Type": "NoiseRange", "DefaultBiome": "Plains", "DefaultTransitionDistance": "far",
+                "Biomes": [{"Biome": "Plains", "Min": 0.0, "Max": "one"}]}"#,
+        )
+        .unwrap();
+        let errors = validate_asset("WorldStructures/Main.json", &json, versions::CURRENT);
+        let mut paths: Vec<&str> = errors.iter().map(|e| e.path.as_str()).collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            ["WorldStructures/Main.json#/Biomes/0/Max", "WorldStructures/Main.json#/DefaultTransitionDistance"]
+        );
+    }
+
+    #[test]
+    fn numeric_seed_accepted() {
+        let json: Value = serde_json::from_str(
+            r#"{"Type": "SimplexNoise2D", "Scale": 50.0, "Octaves": 2, "Seed": 42}"#,
+        )
+        .unwrap();
+        let errors = validate_asset("Density/Noise.json", &json, versions::CURRENT);
+        assert!(errors.is_empty(), "expected no errors, got: {:?}", errors);
+    }
+
+    #[test]
+    fn noise_range_band_findings() {
+        let json: Value = serde_json::from_str(
+            r#"{"Type": "NoiseRange", "DefaultBiome": "Plains", "Density": {"Type": "SimplexNoise2D", "Scale": 256.0},
+                "Biomes": [{"Biome": "Plains", "Min": 1.0, "Max": -1.0}]}"#,
+        )
+        .unwrap();
+        let errors = validate_asset("WorldStructures/Main.json", &json, versions::CURRENT);
+        let inverted = errors.iter().find(|e| e.severity == Severity::Error).expect("inverted band error");
+        assert_eq!(inverted.path, "WorldStructures/Main.json#/Biomes/0");
+        // With its only band inverted, the whole selector range falls to DefaultBiome
+        assert!(errors.iter().any(|e| e.field == "Biomes" && e.severity == Severity::Info));
+    }
+
+    /// Apply the first fix of the error on `field` and revalidate.
+    fn apply_fix(json: &mut Value, field: &str) -> Vec<ValidationError> {
+        let errors = validate_asset("test.json", json, versions::CURRENT);
+        let error = errors.iter().find(|e| e.field == field && !e.fixes.is_empty()).unwrap();
+        assert_eq!(error.fixes[0].file, "test.json");
+        crate::schema::patch::apply(json, &error.fixes[0].edits).unwrap();
+        validate_asset("test.json", json, versions::CURRENT)
+    }
+
+    #[test]
+    fn fixes_missing_field_with_default() {
+        let mut json = json!({"Type": "Clamp", "WallB": 1.0, "Input": {"Type": "Constant", "Value": 0.5}});
+        let errors = apply_fix(&mut json, "WallA");
+        assert_eq!(json["WallA"], json!(0.0));
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn fixes_non_positive_and_below_minimum_values() {
+        let mut json = json!({"Type": "SimplexNoise2D", "Scale": -50.0, "Octaves": 0});
+        apply_fix(&mut json, "Scale");
+        assert_eq!(json["Scale"], json!(50.0));
+        let errors = apply_fix(&mut json, "Octaves");
+        assert_eq!(json["Octaves"], json!(1));
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn fixes_misspelled_type() {
+        let mut json = json!({"Type": "Sum", "Inputs": [{"Type": "Constnat", "Value": 1.0}, {"Type": "Constant", "Value": 2.0}]});
+        let errors = validate_asset("test.json", &json, versions::CURRENT);
+        let error = errors.iter().find(|e| e.path == "test.json#/Inputs/0/Type").unwrap();
+        assert!(error.message.contains("did you mean 'Constant'"));
+        crate::schema::patch::apply(&mut json, &error.fixes[0].edits).unwrap();
+        assert_eq!(json["Inputs"][0]["Type"], "Constant");
+
+        // Nothing close enough, nothing suggested
+        let json = json!({"Type": "MadeUpType"});
+        let errors = validate_asset("test.json", &json, versions::CURRENT);
+        assert!(errors.iter().all(|e| e.fixes.is_empty()));
+    }
+
+    #[test]
+    fn fixes_single_input_wrapped_into_inputs() {
+        let mut json = json!({"Type": "Sum", "Input": {"Type": "Constant", "Value": 1.0}});
+        apply_fix(&mut json, "Inputs");
+        assert_eq!(json, json!({"Type": "Sum", "Inputs": [{"Type": "Constant", "Value": 1.0}]}));
+    }
+
+    #[test]
+    fn unknown_field_suggests_declared_name() {
+        let mut json = json!({"Type": "SimplexNoise2D", "Scale": 64.0, "Octave": 3, "Skip": false, "$NodeId": "n1"});
+        let errors = validate_asset("test.json", &json, versions::CURRENT);
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert_eq!(errors[0].code, DiagnosticCode::UnknownField);
+        assert_eq!(errors[0].severity, Severity::Warning);
+        assert_eq!(errors[0].path, "test.json#/Octave");
+        assert!(errors[0].message.contains("did you mean 'Octaves'"));
+
+        crate::schema::patch::apply(&mut json, &errors[0].fixes[0].edits).unwrap();
+        assert_eq!(json["Octaves"], json!(3));
+        assert!(validate_asset("test.json", &json, versions::CURRENT).is_empty());
+    }
+
+    #[test]
+    fn unknown_fields_found_in_nested_structs_and_invalid_nodes() {
+        let json = json!({"Type": "NoiseRange", "DefaultBiome": "A", "Biomes": [{"Biom": "A", "Min": -1.0, "Max": 1.0}]});
+        let errors = validate_asset("WorldStructures/Main.json", &json, versions::CURRENT);
+        let unknown = errors.iter().find(|e| e.code == DiagnosticCode::UnknownField).unwrap();
+        assert_eq!(unknown.path, "WorldStructures/Main.json#/Biomes/0/Biom");
+        assert!(unknown.message.contains("did you mean 'Biome'"));
+
+        // A node that fails typed deserialization is still checked
+        let json = json!({"Type": "SimplexNoise2D", "Scale": "big", "Detail": 0.01});
+        let errors = validate_asset("test.json", &json, versions::CURRENT);
+        assert!(errors.iter().any(|e| e.code == DiagnosticCode::InvalidValue));
+        assert!(errors.iter().any(|e| e.code == DiagnosticCode::UnknownField && e.field == "Detail"));
+    }
 }
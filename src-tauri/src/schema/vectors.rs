@@ -1,10 +1,12 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "Type")]
 pub enum VectorProviderType {
     Constant {
+        #[schemars(default = "super::vector::<0, 1, 0>")]
         #[serde(rename = "Value")]
         value: Option<Value>,
     },
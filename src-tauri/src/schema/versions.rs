@@ -0,0 +1,95 @@
+use std::path::Path;
+
+use super::validation::{current_field_rules, Rule};
+use super::AssetCategory;
+
+/// A server version the schema is known for, with the migration that
+/// upgrades packs from the previous registered version.
+#[derive(Debug, Clone)]
+pub struct SchemaVersion {
+    /// `serverVersion` as written in a project's `manifest.json`.
+    pub id: &'static str,
+    /// Full server build the schema was taken from.
+    pub build: &'static str,
+    /// Steps upgrading a pack from the previous version, applied in order.
+    pub steps: &'static [MigrationStep],
+    /// Field rules for packs targeting this version, see
+    /// `validation::field_rules`.
+    pub rules: fn(AssetCategory, &str) -> &'static [(&'static str, Rule)],
+}
+
+/// One declarative migration step. Steps apply to every node of
+/// `category` (nested ones included); `node_type` of `None` matches any type.
+#[derive(Debug, Clone)]
+pub enum MigrationStep {
+    /// Rename a node type.
+    RenameType {
+        category: AssetCategory,
+        from: &'static str,
+        to: &'static str,
+    },
+    /// Rename a field.
+    RenameField {
+        category: AssetCategory,
+        node_type: Option<&'static str>,
+        from: &'static str,
+        to: &'static str,
+    },
+    /// Move a value between JSON pointers relative to the node, e.g. from
+    /// `/Range/Min` to `/MinY`.
+    MoveField {
+        category: AssetCategory,
+        node_type: Option<&'static str>,
+        from: &'static str,
+        to: &'static str,
+    },
+    /// Rewrite a numeric field.
+    Transform {
+        category: AssetCategory,
+        node_type: Option<&'static str>,
+        field: &'static str,
+        transform: Transform,
+    },
+}
+
+/// Numeric rewrite applied by `MigrationStep::Transform`.
+#[derive(Debug, Clone, Copy)]
+pub enum Transform {
+    Multiply(f64),
+    Add(f64),
+    /// `1 / x`, e.g. a frequency becoming a scale. Zero is left alone.
+    Reciprocal,
+}
+
+/// Registered versions, oldest first. The Rust schema describes the last.
+pub const VERSIONS: &[SchemaVersion] = &[SchemaVersion {
+    id: "2026.02.05",
+    build: "2026.02.05-9ce2783f7",
+    steps: &[],
+    rules: current_field_rules,
+}];
+
+/// The version the Rust schema and validation rules describe.
+pub const CURRENT: &str = VERSIONS[VERSIONS.len() - 1].id;
+
+/// Position of `id` in `versions`.
+pub fn position(versions: &[SchemaVersion], id: &str) -> Option<usize> {
+    versions.iter().position(|v| v.id == id)
+}
+
+/// The project directory containing `pack`: the nearest directory at or
+/// above it with a `manifest.json`.
+pub fn project_root(pack: &Path) -> Option<&Path> {
+    pack.ancestors()
+        .find(|dir| dir.join("manifest.json").is_file())
+}
+
+/// The `serverVersion` of the project containing `pack`, if it records one.
+pub fn project_version(pack: &Path) -> Option<String> {
+    let manifest = std::fs::read_to_string(project_root(pack)?.join("manifest.json")).ok()?;
+    let manifest: serde_json::Value = serde_json::from_str(&manifest).ok()?;
+    manifest
+        .get("serverVersion")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+}
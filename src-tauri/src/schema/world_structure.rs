@@ -1,8 +1,9 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 /// World structure asset (Type: NoiseRange).
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 #[serde(default, rename_all = "PascalCase")]
 pub struct WorldStructureAsset {
     #[serde(rename = "Type")]
@@ -14,10 +15,12 @@ pub struct WorldStructureAsset {
     pub max_biome_edge_distance: i32,
     pub framework: Value,
     pub spawn_positions: Option<Value>,
+    /// Named content fields, such as `BaseHeight` levels, read by props and scanners.
+    pub content_fields: Vec<Value>,
 }
 
 /// Biome range entry within a world structure.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 #[serde(default, rename_all = "PascalCase")]
 pub struct BiomeRangeAsset {
     pub biome: String,
@@ -20,6 +20,8 @@ import { ExportSvgDialog } from "@/components/dialogs/ExportSvgDialog";
 import { saveRef } from "@/utils/saveRef";
 import { isMac } from "@/utils/platform";
 import { checkForUpdates } from "@/utils/updater";
+import { getSchema } from "@/utils/ipc";
+import { applySchemaDefaults } from "@/schema/defaults";
 import { useSettingsStore } from "@/stores/settingsStore";
 import { useToastStore } from "@/stores/toastStore";
 import type { SvgExportOptions } from "@/utils/exportSvg";
@@ -73,6 +75,13 @@ export default function App() {
     }
   }, []);
 
+  // ---- Field defaults from the Rust schema ----
+  useEffect(() => {
+    getSchema()
+      .then(applySchemaDefaults)
+      .catch((err) => console.warn("Schema defaults unavailable:", err));
+  }, []);
+
   // ---- Post-update verification + auto-check for updates ----
   useEffect(() => {
     const updateTarget = localStorage.getItem("tn-update-target");
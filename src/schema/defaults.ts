@@ -8,6 +8,7 @@ import type { ScannerType } from "./scanners";
 import type { AssignmentType } from "./assignments";
 import type { VectorProviderType } from "./vectors";
 import type { EnvironmentProviderType, TintProviderType, BlockMaskType, DirectionalityType } from "./environment";
+import type { AssetCategory as SchemaCategory, SchemaDescription } from "@/utils/ipc";
 
 type DefaultFields = Record<string, unknown>;
 
@@ -390,6 +391,50 @@ export const DIRECTIONALITY_DEFAULTS: Record<DirectionalityType, DefaultFields>
   Imported: { Name: "" },
 };
 
+// ---------------------------------------------------------------------------
+// Defaults from the Rust schema
+// ---------------------------------------------------------------------------
+
+const SCHEMA_TABLES: Partial<Record<SchemaCategory, Record<string, DefaultFields>>> = {
+  Density: DENSITY_DEFAULTS,
+  Curve: CURVE_DEFAULTS,
+  MaterialProvider: MATERIAL_DEFAULTS,
+  Layer: MATERIAL_DEFAULTS,
+  Condition: CONDITION_DEFAULTS,
+  Pattern: PATTERN_DEFAULTS,
+  PositionProvider: POSITION_DEFAULTS,
+  Prop: PROP_DEFAULTS,
+  Scanner: SCANNER_DEFAULTS,
+  Assignment: ASSIGNMENT_DEFAULTS,
+  VectorProvider: VECTOR_DEFAULTS,
+  EnvironmentProvider: ENVIRONMENT_DEFAULTS,
+  TintProvider: TINT_DEFAULTS,
+  BlockMask: BLOCK_MASK_DEFAULTS,
+  Directionality: DIRECTIONALITY_DEFAULTS,
+};
+
+/**
+ * Take default values from the schema the Rust definitions describe
+ * (`getSchema()`), replacing the ones above in place. The tables still pick
+ * which fields a new node starts with; only fields they already list change,
+ * so a node never gains both the editor and the exported form of a field.
+ */
+export function applySchemaDefaults(schema: SchemaDescription): void {
+  for (const category of schema.categories) {
+    const table = SCHEMA_TABLES[category.category];
+    if (!table) continue;
+    for (const node of category.types) {
+      const defaults = node.name ? table[node.name] : undefined;
+      if (!defaults) continue;
+      for (const field of node.fields) {
+        if (field.name in defaults && field.default !== null && field.default !== undefined) {
+          defaults[field.name] = field.default;
+        }
+      }
+    }
+  }
+}
+
 // ---------------------------------------------------------------------------
 // Aggregate all defaults for the palette
 // ---------------------------------------------------------------------------
@@ -1,4 +1,5 @@
-import { invoke } from "@tauri-apps/api/core";
+import { Channel, invoke } from "@tauri-apps/api/core";
+import type { ColormapId } from "@/utils/colormaps";
 
 export interface AssetPackData {
   path: string;
@@ -18,6 +19,51 @@ export interface EvaluateRequest {
   range_min: number;
   range_max: number;
   y_level: number;
+  profile?: boolean;
+  /** JSON Pointer of the node to preview as the output (defaults to the root). */
+  pointer?: string;
+  /** Additional subtrees to evaluate in the same pass, sharing caches. */
+  pointers?: string[];
+  /** Compute histogram and summary statistics for the primary output. */
+  stats?: StatsOptions;
+  contours?: ContourOptions;
+}
+
+export interface StatsOptions {
+  /** Number of equal-width histogram buckets (default 32). */
+  histogram_bins?: number;
+  /** Report the fraction of values above/below this threshold. */
+  threshold?: number;
+}
+
+export interface GridStats {
+  mean: number;
+  std_dev: number;
+  p1: number;
+  p5: number;
+  p50: number;
+  p95: number;
+  p99: number;
+  histogram: { bins: number[]; bin_edges: number[] };
+  threshold: { threshold: number; fraction_above: number; fraction_below: number } | null;
+}
+
+export interface SubgraphGrid {
+  pointer: string;
+  values: number[];
+  min_value: number;
+  max_value: number;
+}
+
+export interface NodeProfile {
+  /** JSON Pointer of the node within the evaluated graph ("" is the root). */
+  id: string;
+  node_type: string;
+  calls: number;
+  total_ms: number;
+  self_ms: number;
+  /** Fraction (0..1) of the graph's total self time spent in this node. */
+  share: number;
 }
 
 export interface EvaluateResponse {
@@ -25,6 +71,139 @@ export interface EvaluateResponse {
   resolution: number;
   min_value: number;
   max_value: number;
+  profile: NodeProfile[] | null;
+  subgraphs: SubgraphGrid[];
+  stats: GridStats | null;
+  /** Unchanged subtrees whose samples were reused from earlier requests. */
+  cached_subtrees: number;
+  contours: Contours | null;
+}
+
+export type GridDtype = "f32" | "u16";
+
+export interface BinaryGrid {
+  values: Float32Array;
+  resolution: number;
+  min_value: number;
+  max_value: number;
+}
+
+export interface PreviewJobRequest {
+  view: string;
+  graph: unknown;
+  range_min: number;
+  range_max: number;
+  y_level: number;
+  /** Refinement levels, streamed coarse to fine (default 32, 128, 512). */
+  resolutions?: number[];
+  dtype?: GridDtype;
+}
+
+export interface ContourOptions {
+  levels: number[];
+  svg?: boolean;
+}
+
+export interface ContourLevelLines {
+  level: number;
+  /** Polylines of [x, z] points in grid-cell coordinates; closed loops repeat their start. */
+  polylines: [number, number][][];
+}
+
+export interface Contours {
+  levels: ContourLevelLines[];
+  svg: string | null;
+}
+
+export interface IsosurfaceRequest {
+  graph: unknown;
+  /** World-space corners of the sampled box (inclusive). */
+  min: [number, number, number];
+  max: [number, number, number];
+  /** Samples along each axis (2..=256). */
+  resolution: [number, number, number];
+  /** Density at the surface; higher values are solid (default 0). */
+  iso_level?: number;
+  pointer?: string;
+}
+
+/** Indexed triangle mesh with flattened xyz attribute arrays. */
+export interface TriangleMesh {
+  positions: number[];
+  normals: number[];
+  indices: number[];
+}
+
+export interface VoxelMeshRequest {
+  size_x: number;
+  size_y: number;
+  size_z: number;
+  /** Block ids, y fastest then x then z (the `bridgeFetchChunk` layout); ids <= 0 are air. */
+  blocks: number[];
+  /** Block id (as a string) -> block name. */
+  palette?: Record<string, string>;
+}
+
+/** Greedy-merged quads for one block id, in block units. */
+export interface MaterialMesh {
+  block_id: number;
+  name: string | null;
+  positions: number[];
+  normals: number[];
+  /** Per-vertex ambient occlusion, 0 (open) to 3 (fully occluded). */
+  ao: number[];
+  indices: number[];
+}
+
+export interface ProbeRequest {
+  graph: unknown;
+  x: number;
+  y: number;
+  z: number;
+}
+
+export interface TraceNode {
+  /** JSON Pointer of the node within the evaluated graph ("" is the root). */
+  pointer: string;
+  node_type: string;
+  value: number;
+  /** Coordinates the node was evaluated at, after upstream transforms and warps. */
+  x: number;
+  y: number;
+  z: number;
+  /** Displacement of the evaluation point from the probed position. */
+  offset: [number, number, number];
+  children: TraceNode[];
+}
+
+export interface ProbeResponse {
+  value: number;
+  trace: TraceNode;
+}
+
+/** A value interval; `null` ends are unbounded. */
+export interface Interval {
+  min: number | null;
+  max: number | null;
+}
+
+export interface NodeRange {
+  pointer: string;
+  node_type: string;
+  inputs: Interval[];
+  output: Interval;
+}
+
+export interface RangeFinding {
+  pointer: string;
+  kind: "NoEffect" | "ConstantOutput" | "DeadInput" | "NormalizerMismatch";
+  message: string;
+}
+
+export interface RangeAnalysis {
+  output: Interval;
+  nodes: NodeRange[];
+  findings: RangeFinding[];
 }
 
 export interface ValidationResult {
@@ -33,11 +212,67 @@ export interface ValidationResult {
   files_checked: number;
 }
 
+/** Stable rule identifier; usable in `terranova.lint.json` and `$Suppress`. */
+export type DiagnosticCode =
+  | "INVALID_JSON"
+  | "READ_FAILED"
+  | "INVALID_CONFIG"
+  | "NOT_AN_OBJECT"
+  | "MISSING_TYPE"
+  | "UNKNOWN_TYPE"
+  | "UNKNOWN_FIELD"
+  | "INVALID_VALUE"
+  | "MISSING_FIELD"
+  | "NON_POSITIVE_VALUE"
+  | "VALUE_BELOW_MINIMUM"
+  | "TOO_FEW_ITEMS"
+  | "BAND_GAP"
+  | "BAND_OVERLAP"
+  | "BAND_INVERTED"
+  | "BAND_UNREACHABLE"
+  | "BAND_TRANSITION_TOO_WIDE"
+  | "UNRESOLVED_BIOME"
+  | "UNRESOLVED_FRAMEWORK"
+  | "DUPLICATE_EXPORT"
+  | "UNRESOLVED_IMPORT"
+  | "TYPE_MISMATCH"
+  | "CIRCULAR_REFERENCE"
+  | "UNKNOWN_SERVER_VERSION"
+  | "OUTDATED_SERVER_VERSION";
+
 export interface ValidationError {
+  code: DiagnosticCode;
   file: string;
   field: string;
+  /** JSON path of the offending value, e.g. `Biomes/Forest.json#/Terrain/Density/Scale`. */
+  path: string;
   message: string;
   severity: "Error" | "Warning" | "Info";
+  /** 1-based source range of the offending value, when known. */
+  span: SourceSpan | null;
+  /** Machine-applicable fixes; any one of them resolves the error. */
+  fixes: Fix[];
+}
+
+export interface SourceSpan {
+  start: { line: number; column: number };
+  end: { line: number; column: number };
+}
+
+/** A JSON Patch (RFC 6902) operation; paths are JSON pointers into the file. */
+export type PatchOp =
+  | { op: "add"; path: string; value: unknown }
+  | { op: "remove"; path: string }
+  | { op: "replace"; path: string; value: unknown }
+  | { op: "move"; from: string; path: string }
+  | { op: "copy"; from: string; path: string }
+  | { op: "test"; path: string; value: unknown };
+
+export interface Fix {
+  title: string;
+  /** Pack-relative path of the file the edits apply to. */
+  file: string;
+  edits: PatchOp[];
 }
 
 export async function openAssetPack(path: string): Promise<AssetPackData> {
@@ -64,6 +299,29 @@ export async function writeTextFile(path: string, content: string): Promise<void
   return invoke("write_text_file", { path, content });
 }
 
+export type GridNormalization =
+  | { mode: "auto" }
+  | { mode: "fixed"; min: number; max: number }
+  | { mode: "threshold"; level: number };
+
+export interface GridImageRequest {
+  path: string;
+  /** Row-major samples, width * height of them */
+  values: number[] | Float32Array;
+  width: number;
+  height: number;
+  format: "png16" | "raw16" | "png_colormap";
+  normalization: GridNormalization;
+  /** Only used by png_colormap (defaults to grayscale) */
+  colormap?: ColormapId;
+}
+
+export async function exportGridImage(request: GridImageRequest): Promise<void> {
+  return invoke("export_grid_image", {
+    request: { ...request, values: Array.from(request.values) },
+  });
+}
+
 export async function copyFile(source: string, destination: string): Promise<void> {
   return invoke("copy_file", { source, destination });
 }
@@ -90,10 +348,251 @@ export async function evaluateDensity(request: EvaluateRequest): Promise<Evaluat
   return invoke<EvaluateResponse>("evaluate_density", { request });
 }
 
+const GRID_MAGIC = "TNGR";
+const GRID_HEADER_LEN = 24;
+
+/** Decode a buffer produced by `evaluate_density_binary` (layout in preview/binary.rs). */
+export function decodeBinaryGrid(buffer: ArrayBuffer): BinaryGrid {
+  const view = new DataView(buffer);
+  const magic = String.fromCharCode(...new Uint8Array(buffer, 0, 4));
+  if (magic !== GRID_MAGIC) {
+    throw new Error(`Unexpected grid buffer magic: ${magic}`);
+  }
+  const dtype = view.getUint8(5);
+  const resolution = view.getUint32(8, true);
+  const count = view.getUint32(12, true);
+  const min_value = view.getFloat32(16, true);
+  const max_value = view.getFloat32(20, true);
+
+  let values: Float32Array;
+  if (dtype === 0) {
+    values = new Float32Array(buffer, GRID_HEADER_LEN, count);
+  } else if (dtype === 1) {
+    const quantized = new Uint16Array(buffer, GRID_HEADER_LEN, count);
+    const scale = (max_value - min_value) / 65535;
+    values = new Float32Array(count);
+    for (let i = 0; i < count; i++) {
+      values[i] = min_value + quantized[i] * scale;
+    }
+  } else {
+    throw new Error(`Unknown grid dtype: ${dtype}`);
+  }
+
+  return { values, resolution, min_value, max_value };
+}
+
+export async function evaluateDensityBinary(
+  request: EvaluateRequest,
+  dtype: GridDtype = "f32",
+): Promise<BinaryGrid> {
+  const buffer = await invoke<ArrayBuffer>("evaluate_density_binary", { request, dtype });
+  return decodeBinaryGrid(buffer);
+}
+
+/**
+ * Start a progressive preview job. `onLevel` is called once per refinement
+ * level; a newer job for the same view cancels this one.
+ */
+export async function startPreviewJob(
+  request: PreviewJobRequest,
+  onLevel: (grid: BinaryGrid) => void,
+): Promise<number> {
+  const channel = new Channel<ArrayBuffer>();
+  channel.onmessage = (buffer) => onLevel(decodeBinaryGrid(buffer));
+  return invoke<number>("start_preview_job", { request, channel });
+}
+
+export async function cancelPreviewJob(jobId: number): Promise<boolean> {
+  return invoke<boolean>("cancel_preview_job", { jobId });
+}
+
+export type TileLayer =
+  | { kind: "density" }
+  /** Samples hold the index of the first matching range, or -1 for the default biome. */
+  | { kind: "biomes"; ranges: { Biome: string; Min: number; Max: number }[] };
+
+export interface TileMapRequest {
+  graph: unknown;
+  layer: TileLayer;
+  /** Samples per tile edge (default 256) */
+  tile_size?: number;
+  /** World x/z of the map's top-left corner (default [0, 0]) */
+  origin?: [number, number];
+  /** World width of the single zoom-0 tile (default 10240) */
+  extent?: number;
+  y_level?: number;
+}
+
+export interface TileMapInfo {
+  map_id: string;
+  tile_size: number;
+  max_zoom: number;
+}
+
+export async function openTileMap(request: TileMapRequest): Promise<TileMapInfo> {
+  return invoke<TileMapInfo>("open_tile_map", { request });
+}
+
+export async function getMapTile(mapId: string, z: number, x: number, y: number): Promise<BinaryGrid> {
+  const buffer = await invoke<ArrayBuffer>("get_map_tile", { mapId, z, x, y });
+  return decodeBinaryGrid(buffer);
+}
+
+export async function probeDensity(request: ProbeRequest): Promise<ProbeResponse> {
+  return invoke<ProbeResponse>("probe_density", { request });
+}
+
+export async function analyzeDensityRanges(graph: unknown): Promise<RangeAnalysis> {
+  return invoke<RangeAnalysis>("analyze_density_ranges", { graph });
+}
+
+export async function extractIsosurface(request: IsosurfaceRequest): Promise<TriangleMesh> {
+  return invoke<TriangleMesh>("extract_isosurface", { request });
+}
+
+export async function meshVoxels(request: VoxelMeshRequest): Promise<MaterialMesh[]> {
+  return invoke<MaterialMesh[]>("mesh_voxels", { request });
+}
+
+export type MeshFormat = "obj" | "glb";
+
+export async function exportMesh(path: string, mesh: TriangleMesh, format: MeshFormat): Promise<void> {
+  return invoke("export_mesh", { path, mesh, format });
+}
+
+export interface HeightfieldExportRequest {
+  path: string;
+  format: MeshFormat;
+  /** Row-major heights, resolution * resolution of them */
+  values: number[];
+  resolution: number;
+  /** World-space x/z corners of the grid */
+  range_min: [number, number];
+  range_max: [number, number];
+  /** Multiplier from grid value to world height (default 1) */
+  height_scale?: number;
+}
+
+export async function exportHeightfield(request: HeightfieldExportRequest): Promise<void> {
+  return invoke("export_heightfield", { request });
+}
+
+export async function exportVox(path: string, volume: VoxelMeshRequest): Promise<void> {
+  return invoke("export_vox", { path, volume });
+}
+
+/** Block volume of a fetched chunk, for `meshVoxels` and `exportVox`. */
+export function chunkToVoxelVolume(chunk: ChunkDataResponse, palette?: Record<string, string>): VoxelMeshRequest {
+  return {
+    size_x: chunk.sizeX,
+    size_y: chunk.blocks.length / (chunk.sizeX * chunk.sizeZ),
+    size_z: chunk.sizeZ,
+    blocks: chunk.blocks,
+    palette,
+  };
+}
+
 export async function validateAssetPack(path: string): Promise<ValidationResult> {
   return invoke<ValidationResult>("validate_asset_pack", { path });
 }
 
+/** Apply quick fixes to the pack at `path`; returns the files changed. */
+export async function applyFixes(path: string, fixes: Fix[]): Promise<string[]> {
+  return invoke<string[]>("apply_fixes", { path, fixes });
+}
+
+export interface MigrationReport {
+  from: string;
+  to: string;
+  /** Changes in application order; files are relative to the project directory. */
+  changes: Fix[];
+  /** False for a dry run. */
+  applied: boolean;
+}
+
+/**
+ * Upgrade the pack at `path` to server version `to` (default: the schema's
+ * current version). With `dryRun` the changes are only reported.
+ */
+export async function migratePack(
+  path: string,
+  to: string | null,
+  dryRun: boolean,
+): Promise<MigrationReport> {
+  return invoke<MigrationReport>("migrate_pack", { path, to, dryRun });
+}
+
+// ── Schema description ──
+
+export type AssetCategory =
+  | "Density"
+  | "Curve"
+  | "Pattern"
+  | "MaterialProvider"
+  | "Layer"
+  | "Condition"
+  | "PositionProvider"
+  | "Prop"
+  | "Scanner"
+  | "Assignment"
+  | "VectorProvider"
+  | "EnvironmentProvider"
+  | "TintProvider"
+  | "BlockMask"
+  | "Framework"
+  | "WorldStructure"
+  | "Biome"
+  | "Settings"
+  | "Directionality";
+
+export type ValueKind =
+  | { kind: "number" | "integer" | "string" | "boolean" }
+  /** Any JSON; nested assets are described by the type's slots. */
+  | { kind: "json" }
+  | { kind: "array"; items: ValueKind }
+  | { kind: "object"; fields: FieldDescription[] };
+
+export type FieldDescription = ValueKind & {
+  /** Name as written in JSON. */
+  name: string;
+  doc: string | null;
+  required: boolean;
+  default: unknown;
+  minimum: number | null;
+  exclusive_minimum: number | null;
+  min_items: number | null;
+};
+
+export type SlotDescription = { field: string } & (
+  | { kind: "one" | "many"; category: AssetCategory }
+  | { kind: "object" | "objects"; slots: SlotDescription[] }
+);
+
+export interface TypeDescription {
+  /** The `Type` discriminator; null for untyped categories. */
+  name: string | null;
+  doc: string | null;
+  fields: FieldDescription[];
+  slots: SlotDescription[];
+}
+
+export interface CategoryDescription {
+  category: AssetCategory;
+  doc: string | null;
+  types: TypeDescription[];
+}
+
+export interface SchemaDescription {
+  /** Server version the schema was taken from. */
+  version: string;
+  categories: CategoryDescription[];
+}
+
+/** The V2 schema as the Rust definitions describe it. */
+export async function getSchema(): Promise<SchemaDescription> {
+  return invoke<SchemaDescription>("get_schema");
+}
+
 // ── Bridge types ──
 
 export interface ServerStatus {